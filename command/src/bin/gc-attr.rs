@@ -0,0 +1,107 @@
+use clap::{App, Arg};
+use gc_command::arg_utils;
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::attribute::attribute_command::AttributeCommand;
+use gc_core::attribute::attribute_command::AttributeCommand::*;
+use gc_core::graph::VertexId;
+
+fn main() {
+    let args = App::new("gc-attr")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Sets or removes attribute values on vertices and edges")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("name")
+                .long("name")
+                .short("n")
+                .help("Name of the attribute")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("vertex")
+                .long("vertex")
+                .short("v")
+                .help("Sets the attribute value of a vertex: <id> <value>")
+                .required(false)
+                .multiple(true)
+                .number_of_values(2)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("edge")
+                .long("edge")
+                .short("e")
+                .help("Sets the attribute value of a directed edge: <id> <id> <value>")
+                .required(false)
+                .multiple(true)
+                .number_of_values(3)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("remove-vertex")
+                .long("remove-vertex")
+                .help("Removes the attribute value of the provided vertices")
+                .required(false)
+                .min_values(1)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("remove-edge")
+                .long("remove-edge")
+                .help("Removes the attribute value of the provided directed edges")
+                .required(false)
+                .min_values(2)
+                .takes_value(true),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let name = args.value_of("name").unwrap();
+
+    let mut commands: Vec<AttributeCommand<String>> = vec![];
+
+    if let Some(values) = args.values_of("vertex") {
+        let values: Vec<&str> = values.collect();
+        for pair in values.chunks(2) {
+            let vid = arg_utils::parse_vertex_id(pair[0]).expect("Invalid vertex id");
+            commands.push(AddVertexAttr(VertexId(vid), pair[1].to_string()));
+        }
+    }
+
+    if let Some(values) = args.values_of("edge") {
+        let values: Vec<&str> = values.collect();
+        for triple in values.chunks(3) {
+            let src = arg_utils::parse_vertex_id(triple[0]).expect("Invalid vertex id");
+            let dst = arg_utils::parse_vertex_id(triple[1]).expect("Invalid vertex id");
+            commands.push(AddEdgeAttr(
+                VertexId(src),
+                VertexId(dst),
+                triple[2].to_string(),
+            ));
+        }
+    }
+
+    if let Some(ids) = args.values_of("remove-vertex") {
+        let vids = arg_utils::parse_vertex_id_list(ids.collect()).expect("Invalid vertex id");
+        commands.extend(vids.into_iter().map(RemoveVertexAttr));
+    }
+
+    if let Some(ids) = args.values_of("remove-edge") {
+        let edges = arg_utils::parse_edge_list(ids.collect())
+            .expect("Invalid number of vertices. Must be an even number");
+        commands.extend(edges.into_iter().map(|e| RemoveEdgeAttr(e.0, e.1)));
+    }
+
+    graph_utils::apply_attribute_commands(path, name, commands).expect("Couldn't update attribute");
+}
@@ -0,0 +1,57 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::feedback_arc_set;
+use gc_core::graph::Edge;
+
+fn main() {
+    let args = App::new("gc-break-cycles")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Makes the graph a DAG by removing a feedback arc set")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .short("n")
+                .help("Only prints the feedback arcs, without removing them from the graph")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("approximate")
+                .long("approximate")
+                .short("a")
+                .help("Uses Eades-Lin-Smyth heuristic on every strongly connected component, even small ones")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let dry_run = args.is_present("dry-run");
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let arcs = if args.is_present("approximate") {
+        feedback_arc_set::eades_lin_smyth(&graph)
+    } else {
+        feedback_arc_set::feedback_arc_set(&graph)
+    };
+
+    println!("feedback arcs: {}", arcs.len());
+    for Edge(src, dst) in &arcs {
+        println!("{} {}", src.0, dst.0);
+    }
+    if !dry_run && !arcs.is_empty() {
+        let edges = arcs.iter().map(|Edge(src, dst)| (*src, *dst)).collect();
+        graph_utils::remove_edges(path, edges).expect("Couldn't remove the feedback arcs");
+    }
+}
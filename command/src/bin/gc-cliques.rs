@@ -0,0 +1,92 @@
+use clap::{App, Arg, ArgGroup};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::cliques;
+use gc_core::graph::VertexId;
+
+fn main() {
+    let args = App::new("gc-cliques")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Finds cliques, where vertices are linked both ways, and independent sets")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("min-size")
+                .long("min-size")
+                .short("m")
+                .help("Only considers the maximal cliques with at least this number of vertices")
+                .default_value("1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("count")
+                .long("count")
+                .short("c")
+                .help("Only prints the number of maximal cliques")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("maximum")
+                .long("maximum")
+                .help("Prints a clique with the maximum number of vertices")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("independent-set")
+                .long("independent-set")
+                .short("i")
+                .help("Prints a maximal set of vertices without edges between them, found greedily")
+                .required(false)
+                .takes_value(false),
+        )
+        .group(ArgGroup::with_name("action").args(&["count", "maximum", "independent-set"]))
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let min_size = args
+        .value_of("min-size")
+        .unwrap()
+        .parse::<usize>()
+        .expect("Invalid minimum size");
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+
+    if args.is_present("maximum") {
+        println!(
+            "maximum clique: {}",
+            format_vertices(&cliques::maximum_clique(&graph))
+        );
+    } else if args.is_present("independent-set") {
+        println!(
+            "independent set: {}",
+            format_vertices(&cliques::maximal_independent_set(&graph))
+        );
+    } else {
+        let maximal = cliques::maximal_cliques(&graph).filter(|c| c.len() >= min_size);
+        if args.is_present("count") {
+            println!("count: {}", maximal.count());
+        } else {
+            maximal.for_each(|c| println!("{}", format_vertices(&c)));
+        }
+    }
+}
+
+fn format_vertices(vertices: &[VertexId]) -> String {
+    format![
+        "{:?}",
+        vertices
+            .iter()
+            .map(|VertexId(vid)| vid)
+            .collect::<Vec<&u64>>()
+    ]
+}
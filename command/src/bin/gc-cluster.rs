@@ -0,0 +1,101 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::community;
+use gc_core::attribute::attribute_command::AttributeCommand;
+use gc_core::attribute::attribute_command::AttributeCommand::*;
+use gc_core::attribute::mapping::EdgeAttrMapping;
+use gc_core::graph::{Edge, VertexId};
+
+fn main() {
+    let args = App::new("gc-cluster")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Detects communities, considering edges as undirected, and stores them in a vertex attribute")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("algorithm")
+                .long("algorithm")
+                .short("a")
+                .help("Community detection algorithm")
+                .possible_values(&["louvain", "label-propagation"])
+                .default_value("louvain")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("weight")
+                .long("weight")
+                .short("w")
+                .help("Name of the edge attribute holding the weights. Without it, all edges have a weight of 1")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .short("o")
+                .help("Name of the vertex attribute receiving the community of each vertex")
+                .default_value("community")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .short("n")
+                .help("Only prints the communities, without storing them")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let output = args.value_of("output").unwrap();
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let weights: Option<EdgeAttrMapping<f64>> = args.value_of("weight").map(|name| {
+        graph_utils::load_edge_attribute(path, name).expect("Couldn't load weight attribute")
+    });
+    let weight = |e: &Edge| -> f64 {
+        weights
+            .as_ref()
+            .and_then(|mapping| mapping.get(e).copied())
+            .unwrap_or(1.0)
+    };
+
+    let communities = match args.value_of("algorithm").unwrap() {
+        "label-propagation" => community::label_propagation(&graph, weight),
+        _ => community::louvain(&graph, weight),
+    };
+    let members = community::members(&communities);
+    println!("communities: {}", members.len());
+    println!(
+        "modularity: {:.6}",
+        community::modularity(&graph, weight, &communities)
+    );
+    if args.is_present("dry-run") {
+        for (index, group) in members.iter().enumerate() {
+            println!(
+                "{}: {:?}",
+                index,
+                group.iter().map(|VertexId(vid)| vid).collect::<Vec<&u64>>()
+            );
+        }
+        return;
+    }
+    let mut communities: Vec<(VertexId, usize)> = communities.into_iter().collect();
+    communities.sort();
+    let commands: Vec<AttributeCommand<usize>> = communities
+        .into_iter()
+        .map(|(vertex, community)| AddVertexAttr(vertex, community))
+        .collect();
+    graph_utils::apply_attribute_commands(path, output, commands)
+        .expect("Couldn't store the communities");
+}
@@ -0,0 +1,118 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::coloring;
+use gc_core::attribute::attribute_command::AttributeCommand;
+use gc_core::attribute::attribute_command::AttributeCommand::*;
+use gc_core::graph::VertexId;
+
+fn main() {
+    let args = App::new("gc-color")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Colors the vertices so that adjacent vertices have different colors, ignoring edge directions")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("algorithm")
+                .long("algorithm")
+                .short("a")
+                .help("Coloring algorithm. The exact one only works on small graphs")
+                .possible_values(&["dsatur", "welsh-powell", "exact"])
+                .default_value("dsatur")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-colors")
+                .long("max-colors")
+                .short("k")
+                .help("Maximum number of colors, searched exhaustively when the algorithm needs more")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .short("o")
+                .help("Name of the vertex attribute receiving the color of each vertex")
+                .default_value(coloring::COLOR_ATTRIBUTE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .short("n")
+                .help("Only prints the vertices of each color, without storing them")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let output = args.value_of("output").unwrap();
+    let max_colors = args.value_of("max-colors").map(|k| {
+        k.parse::<usize>()
+            .expect("Invalid maximum number of colors")
+    });
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let algorithm = args.value_of("algorithm").unwrap();
+    let colors = match algorithm {
+        "exact" => match coloring::min_coloring(&graph) {
+            Some(colors) => colors,
+            None => {
+                println!(
+                    "Graph is too big for an exact coloring: more than {} vertices.",
+                    coloring::EXACT_COLORING_LIMIT
+                );
+                return;
+            }
+        },
+        "welsh-powell" => coloring::welsh_powell(&graph),
+        _ => coloring::dsatur(&graph),
+    };
+    let colors = match max_colors {
+        Some(k) if coloring::color_count(&colors) > k => match coloring::k_coloring(&graph, k) {
+            Some(colors) => colors,
+            None => {
+                println!("No coloring with at most {} colors.", k);
+                return;
+            }
+        },
+        _ => colors,
+    };
+
+    if algorithm == "exact" {
+        println!("chromatic number: {}", coloring::color_count(&colors));
+    } else {
+        println!(
+            "chromatic number upper bound: {}",
+            coloring::color_count(&colors)
+        );
+    }
+    if args.is_present("dry-run") {
+        for (color, class) in coloring::color_classes(&colors).iter().enumerate() {
+            println!(
+                "{}: {:?}",
+                color,
+                class.iter().map(|VertexId(vid)| vid).collect::<Vec<&u64>>()
+            );
+        }
+        return;
+    }
+    let mut colors: Vec<(VertexId, usize)> = colors.iter().map(|(v, c)| (*v, *c)).collect();
+    colors.sort();
+    let commands: Vec<AttributeCommand<usize>> = colors
+        .into_iter()
+        .map(|(vertex, color)| AddVertexAttr(vertex, color))
+        .collect();
+    graph_utils::apply_attribute_commands(path, output, commands)
+        .expect("Couldn't store the colors");
+}
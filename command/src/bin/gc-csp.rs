@@ -2,10 +2,12 @@ use clap::{App, Arg};
 use gc_command::graph_utils;
 use gc_command::arg_utils;
 use gc_command::version;
+use gc_core::attribute::mapping::EdgeAttrMapping;
 use gc_core::directed_graph::DirectedGraph;
-use gc_core::graph::VertexId;
+use gc_core::graph::{Edge, VertexId};
 use gc_core::constraint::constraint::Constraint;
 use gc_core::path::ScoredPath;
+use gc_core::weight::Real;
 
 fn main() {
     let args = App::new("gc-csp")
@@ -131,6 +133,34 @@ fn main() {
                 .min_values(1)
                 .max_values(1),
         )
+        .arg(
+            Arg::with_name("objectives")
+                .long("objectives")
+                .help("Finds the Pareto front for these edge attributes, separated by commas")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("resource")
+                .long("resource")
+                .help("Bounds the total of an edge attribute along the path, as <name>=<limit>")
+                .required(false)
+                .takes_value(true)
+                .min_values(1)
+                .validator(|arg| match parse_resource(&arg) {
+                    Some(_) => Ok(()),
+                    None => Err(format!["Invalid resource '{}', expected <name>=<limit>", arg]),
+                })
+                .conflicts_with("objectives"),
+        )
+        .arg(
+            Arg::with_name("weight")
+                .long("weight")
+                .help("Minimises the total of this edge attribute instead of the number of edges")
+                .required(false)
+                .takes_value(true)
+                .conflicts_with("objectives"),
+        )
         .get_matches();
 
     let path = args.value_of("path").unwrap();
@@ -213,6 +243,31 @@ fn main() {
         max_score,
         exact_score,
     );
+    if let Some(objectives) = args.value_of("objectives") {
+        let names: Vec<&str> = objectives.split(',').collect();
+        print_pareto_front(&graph, path, &names, start_vertex, end_vertex, constraints);
+        return;
+    }
+    if args.is_present("resource") || args.is_present("weight") {
+        let resources: Vec<(&str, Real)> = args
+            .values_of("resource")
+            .map(|values| {
+                values
+                    .map(|v| parse_resource(v).expect("Resources are validated"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        print_resource_constrained_path(
+            &graph,
+            path,
+            args.value_of("weight"),
+            &resources,
+            start_vertex,
+            end_vertex,
+            constraints,
+        );
+        return;
+    }
     match shortest_path_with_constraints(&graph, start_vertex, end_vertex, constraints) {
         Some(ScoredPath {
             score,
@@ -256,6 +311,180 @@ fn shortest_path_with_constraints(
     )
 }
 
+// Score constraints apply to the first objective
+fn print_pareto_front(
+    graph: &DirectedGraph,
+    path: &str,
+    names: &[&str],
+    start: VertexId,
+    end: VertexId,
+    constraints: Vec<Constraint>,
+) {
+    use gc_core::search::pareto;
+
+    let weights: Vec<EdgeAttrMapping<Real>> = names
+        .iter()
+        .map(|name| {
+            graph_utils::load_edge_attribute(path, name).expect("Couldn't load objective attribute")
+        })
+        .collect();
+    for (name, values) in names.iter().zip(&weights) {
+        if let Err(message) = check_edge_values(graph, name, values) {
+            println!("{}", message);
+            return;
+        }
+    }
+    let objectives: Vec<_> = weights
+        .iter()
+        .map(|w| move |e: &Edge| *w.get(e).expect("Objectives are checked"))
+        .collect();
+    let constraints = constraints
+        .iter()
+        .map(|c| c.map_score(&|score: &i64| Real(*score as f64)))
+        .collect();
+
+    let front = match pareto::pareto_shortest_paths(graph, &objectives, start, end, constraints) {
+        Ok(front) => front,
+        Err(_) => {
+            println!("The scores of the paths overflow.");
+            return;
+        }
+    };
+    if front.is_empty() {
+        println!(
+            "Vertex {} is not reachable from vertex {} within the given constraints.",
+            end.0, start.0
+        );
+        return;
+    }
+    println!(
+        "Pareto front from vertex {} to vertex {} on {}: {} paths.",
+        start.0,
+        end.0,
+        names.join(", "),
+        front.len()
+    );
+    for ScoredPath { path, score } in front {
+        let scores: Vec<String> = names
+            .iter()
+            .zip(score)
+            .map(|(name, s)| format!["{}={}", name, s])
+            .collect();
+        let vertices: Vec<u64> = path.to_vertex_list().map(|v| v.0).collect();
+        println!("{} {:?}", scores.join(" "), vertices);
+    }
+}
+
+// Paths are expanded by increasing scores, so every edge needs a value which is not negative
+fn check_edge_values(
+    graph: &DirectedGraph,
+    name: &str,
+    values: &EdgeAttrMapping<Real>,
+) -> Result<(), String> {
+    let invalid = graph
+        .edges()
+        .filter(|e| values.get(e).is_none_or(|v| *v < Real(0.0)))
+        .min();
+    match invalid.map(|e| (e, values.get(e))) {
+        Some((Edge(VertexId(src), VertexId(dst)), None)) => {
+            Err(format!["Edge {} -> {} has no {}.", src, dst, name])
+        }
+        Some((Edge(VertexId(src), VertexId(dst)), Some(value))) => Err(format![
+            "Edge {} -> {} has a negative {} of {}.",
+            src, dst, name, value
+        ]),
+        None => Ok(()),
+    }
+}
+
+// Parses a resource limit given as <name>=<limit>
+fn parse_resource(arg: &str) -> Option<(&str, Real)> {
+    let mut parts = arg.splitn(2, '=');
+    match (parts.next(), parts.next().and_then(|l| l.parse::<Real>().ok())) {
+        (Some(name), Some(limit)) => Some((name, limit)),
+        _ => None,
+    }
+}
+
+// Score constraints apply to the total weight
+fn print_resource_constrained_path(
+    graph: &DirectedGraph,
+    path: &str,
+    weight: Option<&str>,
+    resources: &[(&str, Real)],
+    start: VertexId,
+    end: VertexId,
+    constraints: Vec<Constraint>,
+) {
+    use gc_core::search::resource_constrained::{self, Resource, ResourcePath};
+
+    let weights: Option<EdgeAttrMapping<Real>> = weight.map(|name| {
+        graph_utils::load_edge_attribute(path, name).expect("Couldn't load weight attribute")
+    });
+    let consumptions: Vec<EdgeAttrMapping<Real>> = resources
+        .iter()
+        .map(|(name, _)| {
+            graph_utils::load_edge_attribute(path, name).expect("Couldn't load resource attribute")
+        })
+        .collect();
+    let checked = weight
+        .zip(weights.as_ref())
+        .into_iter()
+        .chain(resources.iter().map(|(name, _)| *name).zip(&consumptions));
+    for (name, values) in checked {
+        if let Err(message) = check_edge_values(graph, name, values) {
+            println!("{}", message);
+            return;
+        }
+    }
+    let scorefn = |e: &Edge| match &weights {
+        Some(w) => *w.get(e).expect("Weights are checked"),
+        None => Real(1.0),
+    };
+    let limits: Vec<_> = resources
+        .iter()
+        .zip(&consumptions)
+        .map(|((_, limit), c)| Resource {
+            consumption: move |e: &Edge| *c.get(e).expect("Resources are checked"),
+            limit: *limit,
+        })
+        .collect();
+    let constraints = constraints
+        .iter()
+        .map(|c| c.map_score(&|score: &i64| Real(*score as f64)))
+        .collect();
+
+    match resource_constrained::resource_constrained_shortest_path(
+        graph,
+        scorefn,
+        &limits,
+        start,
+        end,
+        constraints,
+    ) {
+        Ok(Some(ResourcePath {
+            scored_path: ScoredPath { score, path: shortest },
+            consumption,
+        })) => {
+            println!(
+                "Constrained shortest path from vertex {} to vertex {} with total cost of {}.",
+                start.0, end.0, score
+            );
+            for ((name, limit), total) in resources.iter().zip(consumption) {
+                println!("{}: {} / {}", name, total, limit);
+            }
+            for vertex in shortest.to_vertex_list() {
+                println!("{}", vertex.0);
+            }
+        }
+        Ok(None) => println!(
+            "Vertex {} is not reachable from vertex {} within the given constraints.",
+            end.0, start.0
+        ),
+        Err(_) => println!("The total cost of the path overflows."),
+    }
+}
+
 // Constraints
 
 fn build_all_constraints(
@@ -3,9 +3,11 @@ use gc_command::arg_utils;
 use gc_command::graph_utils;
 use gc_command::version;
 use gc_core::algorithm::cycle;
+use gc_core::algorithm::hamiltonian;
+use gc_core::attribute::mapping::EdgeAttrMapping;
 use gc_core::constraint::constraint::Constraint;
 use gc_core::directed_graph::DirectedGraph;
-use gc_core::graph::VertexId;
+use gc_core::graph::{Edge, VertexId};
 use gc_core::iter::iter_cycle;
 use gc_core::iter::iter_cycle::Cycle;
 use gc_core::path;
@@ -40,6 +42,14 @@ fn main() {
                 .required(false)
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("weighted")
+                .long("weighted")
+                .help("Find the hamiltonian cycle with the minimum total weight, using the provided edge attribute")
+                .requires("hamiltonian")
+                .required(false)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("count")
                 .long("count")
@@ -171,6 +181,7 @@ fn main() {
 
     let girth = args.is_present("girth");
     let hamiltonian = args.is_present("hamiltonian");
+    let weighted = args.value_of("weighted");
     // Action
     let count = args.is_present("count");
     let shortest = args.is_present("shortest");
@@ -238,7 +249,7 @@ fn main() {
     );
 
     // Iterates on cycles, using the constraints to filter candidates
-    let iterator = iter_cycle::cycle_iter(&graph).filter(|cycle| {
+    let iterator = iter_cycle::circuit_iter(&graph).filter(|cycle| {
         check(
             &graph,
             cycle,
@@ -249,6 +260,34 @@ fn main() {
 
     if girth {
         println!("girth: {}", format_girth(cycle::girth(&graph)));
+    }  else if let Some(name) = weighted {
+        if graph.vertex_count() > hamiltonian::WEIGHTED_HELD_KARP_LIMIT {
+            println!(
+                "Graph is too big for a weighted search: more than {} vertices.",
+                hamiltonian::WEIGHTED_HELD_KARP_LIMIT
+            );
+            return;
+        }
+        let weights: EdgeAttrMapping<i64> =
+            graph_utils::load_edge_attribute(path, name).expect("Couldn't load weight attribute");
+        // Self loops are never part of a hamiltonian cycle, so they don't need a weight
+        let missing = graph
+            .edges()
+            .filter(|e| e.0 != e.1 && weights.get(e).is_none())
+            .min();
+        if let Some(Edge(VertexId(src), VertexId(dst))) = missing {
+            println!("Edge {} -> {} has no weight.", src, dst);
+            return;
+        }
+        let weight = |e: &Edge| weights.get(e).copied().unwrap_or(0);
+        match hamiltonian::min_hamiltonian_cycle(&graph, weight) {
+            Ok(Some((cycle, total))) => {
+                println!("hamiltonian: {}", format_cycle(&cycle));
+                println!("weight: {}", total);
+            }
+            Ok(None) => println!("hamiltonian: N/A"),
+            Err(_) => println!("The total weight of the cycle overflows."),
+        }
     }  else if hamiltonian {
             println!("hamiltonian: {}", format_cycle_opt(cycle::hamiltonian(&graph).as_ref()));
     }  else if count {
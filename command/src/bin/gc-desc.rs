@@ -1,6 +1,7 @@
 use clap::{App, Arg};
 use gc_command::graph_utils;
 use gc_command::version;
+use gc_core::algorithm::connectivity;
 use gc_core::graph::VertexId;
 
 fn main() {
@@ -43,6 +44,14 @@ fn main() {
             .map(|x| x.to_string())
             .unwrap_or_else(|| "-".to_string())
     );
+    println!(
+        "Weakly connected components: {}",
+        connectivity::weakly_connected_components(&graph).len()
+    );
+    println!(
+        "Strongly connected components: {}",
+        connectivity::strongly_connected_components(&graph).len()
+    );
 
-    // FIXME compute more indicators (avg node degree, max node degree, min node degree, DAG yes/no, components, ...)
+    // FIXME compute more indicators (avg node degree, max node degree, min node degree, DAG yes/no, ...)
 }
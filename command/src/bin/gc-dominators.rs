@@ -0,0 +1,91 @@
+use clap::{App, Arg};
+use gc_command::arg_utils;
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::dominators;
+use gc_core::graph::VertexId;
+
+fn main() {
+    let args = App::new("gc-dominators")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Computes the immediate dominators of the vertices reachable from a root vertex")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("root")
+                .long("root")
+                .short("r")
+                .help("Entry vertex of the graph, or exit vertex when computing post-dominators")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("post")
+                .long("post")
+                .help("Computes the post-dominators of the vertices that can reach the root vertex")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("frontiers")
+                .long("frontiers")
+                .short("f")
+                .help("Also prints the dominance frontier of each vertex")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let root = args
+        .value_of("root")
+        .and_then(arg_utils::parse_vertex_id)
+        .map(VertexId)
+        .expect("Invalid root vertex id");
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let tree = if args.is_present("post") {
+        dominators::post_dominator_tree(&graph, root)
+    } else {
+        dominators::dominator_tree(&graph, root)
+    };
+    let tree = match tree {
+        Some(tree) => tree,
+        None => {
+            println!("Vertex {} is not in the graph.", root.0);
+            return;
+        }
+    };
+
+    let mut vertices: Vec<VertexId> = graph
+        .vertices()
+        .copied()
+        .filter(|v| tree.contains(*v))
+        .collect();
+    vertices.sort();
+
+    println!("immediate dominators:");
+    for vertex in &vertices {
+        if let Some(VertexId(idom)) = tree.immediate_dominator(*vertex) {
+            println!("{} {}", vertex.0, idom);
+        }
+    }
+
+    if args.is_present("frontiers") {
+        let frontiers = dominators::dominance_frontiers(&graph, &tree);
+        println!("dominance frontiers:");
+        for vertex in &vertices {
+            let mut frontier: Vec<u64> = frontiers[vertex].iter().map(|v| v.0).collect();
+            frontier.sort();
+            println!("{}: {:?}", vertex.0, frontier);
+        }
+    }
+}
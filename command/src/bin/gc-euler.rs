@@ -0,0 +1,74 @@
+use clap::{App, Arg, ArgGroup};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::euler;
+use gc_core::graph::Edge;
+use gc_core::path::Path;
+
+fn main() {
+    let args = App::new("gc-euler")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Finds a walk going through every edge exactly once")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("circuit")
+                .long("circuit")
+                .short("c")
+                .help("Only accepts walks ending on their first vertex")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("postman")
+                .long("postman")
+                .help("Finds a shortest circuit going through every edge at least once")
+                .required(false)
+                .takes_value(false),
+        )
+        .group(ArgGroup::with_name("kind").args(&["circuit", "postman"]))
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+
+    if args.is_present("postman") {
+        match euler::chinese_postman(&graph) {
+            Some(route) => {
+                println!("extra edges: {}", route.extra_edge_count());
+                let mut extra: Vec<(&Edge, &u64)> = route.extra_traversals.iter().collect();
+                extra.sort();
+                for (Edge(src, dst), count) in extra {
+                    println!("{} {} x{}", src.0, dst.0, count);
+                }
+                println!("circuit: {}", format_path(&route.circuit));
+            }
+            None => println!("N/A: the graph is not strongly connected"),
+        }
+    } else if args.is_present("circuit") {
+        match euler::eulerian_circuit(&graph) {
+            Some(circuit) => println!("eulerian circuit: {}", format_path(&circuit)),
+            None => println!("N/A"),
+        }
+    } else {
+        match euler::eulerian_path(&graph) {
+            Some(path) => println!("eulerian path: {}", format_path(&path)),
+            None => println!("N/A"),
+        }
+    }
+}
+
+fn format_path(path: &Path) -> String {
+    format![
+        "{:?}",
+        path.to_vertex_list().map(|v| v.0).collect::<Vec<u64>>()
+    ]
+}
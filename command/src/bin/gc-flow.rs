@@ -0,0 +1,82 @@
+use clap::{App, Arg};
+use gc_command::flow_utils;
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::max_flow;
+use gc_core::algorithm::max_flow::Cost;
+use gc_core::algorithm::min_cost_flow;
+use gc_core::attribute::mapping::EdgeAttrMapping;
+use gc_core::graph::Edge;
+
+fn main() {
+    let args = flow_utils::flow_args(App::new("gc-flow"))
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Computes a maximum flow between sources and sinks, of minimum cost with --min-cost")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("min-cost")
+                .long("min-cost")
+                .help("Name of the edge attribute holding the cost of a unit of flow. Computes the maximum flow of minimum cost")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("default-cost")
+                .long("default-cost")
+                .help("Cost of the edges without a cost attribute")
+                .default_value("0")
+                .required(false)
+                .takes_value(true),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let (sources, sinks) = flow_utils::parse_terminals(&args);
+    let default_cost = args
+        .value_of("default-cost")
+        .and_then(|x| x.parse::<Cost>().ok())
+        .expect("Invalid default cost");
+
+    if sources.iter().any(|source| sinks.contains(source)) {
+        println!("A vertex can't be both a source and a sink.");
+        return;
+    }
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let capacity = flow_utils::load_capacity(path, &args);
+
+    match args.value_of("min-cost") {
+        Some(name) => {
+            let costs: EdgeAttrMapping<Cost> =
+                graph_utils::load_edge_attribute(path, name).expect("Couldn't load cost attribute");
+            let cost = |e: &Edge| -> Cost { costs.get(e).copied().unwrap_or(default_cost) };
+            match min_cost_flow::multi_source_min_cost_max_flow(
+                &graph, capacity, cost, &sources, &sinks,
+            ) {
+                Some(result) => {
+                    println!("max flow: {}", result.value);
+                    println!("cost: {}", result.cost);
+                    println!("flows:");
+                    flow_utils::print_flows(&result.flows);
+                }
+                None => println!("The total cost of the flow overflows."),
+            }
+        }
+        None => {
+            let result = max_flow::multi_source_max_flow(&graph, capacity, &sources, &sinks)
+                .expect("Sources and sinks are disjoint");
+            println!("max flow: {}", result.value);
+            println!("flows:");
+            flow_utils::print_flows(&result.flows);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::isomorphism;
+use gc_core::algorithm::isomorphism::{Mapping, MatchKind};
+use gc_core::attribute::mapping::{EdgeAttrMapping, VertexAttrMapping};
+use gc_core::graph::{Edge, VertexId};
+
+fn main() {
+    let args = App::new("gc-match-pattern")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Finds the occurrences of a pattern graph in the graph")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("pattern")
+                .long("pattern")
+                .help("Directory of the pattern graph")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("induced")
+                .long("induced")
+                .short("i")
+                .help("Matched vertices can't have more edges between them than in the pattern")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("vertex-attr")
+                .long("vertex-attr")
+                .help("Vertex attribute of the pattern that matched vertices must have too")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("edge-attr")
+                .long("edge-attr")
+                .help("Edge attribute of the pattern that matched edges must have too")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("count")
+                .long("count")
+                .short("c")
+                .help("Only prints the number of occurrences")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .short("n")
+                .help("Maximum number of occurrences to find")
+                .required(false)
+                .takes_value(true),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let pattern_path = args.value_of("pattern").unwrap();
+    let kind = if args.is_present("induced") {
+        MatchKind::InducedSubgraph
+    } else {
+        MatchKind::Monomorphism
+    };
+    let limit = args
+        .value_of("limit")
+        .map(|n| n.parse::<usize>().expect("Invalid limit"))
+        .unwrap_or(usize::MAX);
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let pattern = graph_utils::load_graph(pattern_path).expect("Couldn't load pattern");
+
+    // Pattern vertices and edges without the attribute match anything
+    let vertex_attrs: Option<(VertexAttrMapping<String>, VertexAttrMapping<String>)> =
+        match args.value_of("vertex-attr") {
+            Some(name) => match (
+                graph_utils::load_vertex_attribute(pattern_path, name),
+                graph_utils::load_vertex_attribute(path, name),
+            ) {
+                (Ok(pattern_attr), Ok(attr)) => Some((pattern_attr, attr)),
+                (Err(err), _) => {
+                    println!("Couldn't load the vertex attribute of the pattern. {}", err);
+                    return;
+                }
+                (_, Err(err)) => {
+                    println!("Couldn't load the vertex attribute of the graph. {}", err);
+                    return;
+                }
+            },
+            None => None,
+        };
+    let edge_attrs: Option<(EdgeAttrMapping<String>, EdgeAttrMapping<String>)> =
+        match args.value_of("edge-attr") {
+            Some(name) => match (
+                graph_utils::load_edge_attribute(pattern_path, name),
+                graph_utils::load_edge_attribute(path, name),
+            ) {
+                (Ok(pattern_attr), Ok(attr)) => Some((pattern_attr, attr)),
+                (Err(err), _) => {
+                    println!("Couldn't load the edge attribute of the pattern. {}", err);
+                    return;
+                }
+                (_, Err(err)) => {
+                    println!("Couldn't load the edge attribute of the graph. {}", err);
+                    return;
+                }
+            },
+            None => None,
+        };
+    let vertex_match = |p: &VertexId, t: &VertexId| match &vertex_attrs {
+        Some((pattern_attr, attr)) => pattern_attr.get(p).is_none_or(|v| attr.get(t) == Some(v)),
+        None => true,
+    };
+    let edge_match = |p: &Edge, t: &Edge| match &edge_attrs {
+        Some((pattern_attr, attr)) => pattern_attr.get(p).is_none_or(|v| attr.get(t) == Some(v)),
+        None => true,
+    };
+
+    let matches =
+        isomorphism::iter_matches(&pattern, &graph, kind, vertex_match, edge_match).take(limit);
+    if args.is_present("count") {
+        println!("count: {}", matches.count());
+    } else {
+        matches.for_each(|m| println!("{}", format_mapping(&m)));
+    }
+}
+
+fn format_mapping(mapping: &Mapping) -> String {
+    let mut pairs: Vec<(&VertexId, &VertexId)> = mapping.iter().collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(VertexId(p), VertexId(t))| format!["{}:{}", p, t])
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,85 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::matching;
+use gc_core::algorithm::max_flow::Cost;
+use gc_core::attribute::mapping::EdgeAttrMapping;
+use gc_core::graph::{Edge, VertexId};
+
+fn main() {
+    let args = App::new("gc-match")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Computes a maximum matching of a bipartite graph, where all edges go from a left vertex to a right vertex")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("cost")
+                .long("cost")
+                .short("c")
+                .help("Name of the edge attribute holding the costs. Computes the maximum matching of minimum cost")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("default-cost")
+                .long("default-cost")
+                .help("Cost of the edges without a cost attribute")
+                .default_value("0")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("maximize")
+                .long("maximize")
+                .help("Computes the maximum matching of maximum cost instead")
+                .required(false)
+                .requires("cost")
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let default_cost = args
+        .value_of("default-cost")
+        .and_then(|x| x.parse::<Cost>().ok())
+        .expect("Invalid default cost");
+    let maximize = args.is_present("maximize");
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    if !matching::is_bipartite(&graph) {
+        println!("Graph is not bipartite: a vertex has both inbound and outbound edges.");
+        return;
+    }
+
+    match args.value_of("cost") {
+        Some(name) => {
+            let costs: EdgeAttrMapping<Cost> =
+                graph_utils::load_edge_attribute(path, name).expect("Couldn't load cost attribute");
+            let sign = if maximize { -1 } else { 1 };
+            let cost = |e: &Edge| -> Cost { sign * costs.get(e).copied().unwrap_or(default_cost) };
+            let assignment = matching::hungarian(&graph, cost).expect("Graph is bipartite");
+            println!("matching size: {}", assignment.edges.len());
+            println!("cost: {}", sign * assignment.cost);
+            print_edges(&assignment.edges);
+        }
+        None => {
+            let edges = matching::hopcroft_karp(&graph).expect("Graph is bipartite");
+            println!("matching size: {}", edges.len());
+            print_edges(&edges);
+        }
+    }
+}
+
+fn print_edges(edges: &[Edge]) {
+    for Edge(VertexId(src), VertexId(dst)) in edges {
+        println!("{} {}", src, dst);
+    }
+}
@@ -0,0 +1,81 @@
+use clap::{App, Arg};
+use gc_command::flow_utils;
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::max_flow;
+use gc_core::graph::{Edge, VertexId};
+
+fn main() {
+    let args = flow_utils::flow_args(App::new("gc-max-flow"))
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Computes the maximum flow and a minimum cut between sources and sinks")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("cut-only")
+                .long("cut-only")
+                .help("Only prints the minimum cut, not the flow on each edge")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let (sources, sinks) = flow_utils::parse_terminals(&args);
+    let cut_only = args.is_present("cut-only");
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let capacity = flow_utils::load_capacity(path, &args);
+
+    let result = match max_flow::multi_source_max_flow(&graph, capacity, &sources, &sinks) {
+        Some(result) => result,
+        None => {
+            println!("A vertex can't be both a source and a sink.");
+            return;
+        }
+    };
+
+    println!("max flow: {}", result.value);
+    if !cut_only {
+        println!("flows:");
+        flow_utils::print_flows(&result.flows);
+    }
+    println!("min cut:");
+    for Edge(VertexId(src), VertexId(dst)) in sorted(result.cut.edges.iter()) {
+        println!("{} {}", src, dst);
+    }
+    println!(
+        "source side: {}",
+        format_vertices(result.cut.source_side.iter())
+    );
+    println!(
+        "sink side: {}",
+        format_vertices(result.cut.sink_side.iter())
+    );
+}
+
+// Formatter
+
+fn format_vertices<'a>(vertices: impl Iterator<Item = &'a VertexId>) -> String {
+    format![
+        "{:?}",
+        sorted(vertices)
+            .iter()
+            .map(|VertexId(vid)| *vid)
+            .collect::<Vec<u64>>()
+    ]
+}
+
+fn sorted<'a, T: Ord + Copy + 'a>(values: impl Iterator<Item = &'a T>) -> Vec<T> {
+    let mut values: Vec<T> = values.copied().collect();
+    values.sort();
+    values
+}
@@ -4,6 +4,7 @@ use clap::{App, Arg};
 use gc_command::arg_utils;
 use gc_command::graph_utils;
 use gc_command::version;
+use gc_core::algorithm::connectivity;
 use gc_core::directed_graph::DirectedGraph;
 use itertools::Itertools;
 
@@ -75,7 +76,15 @@ fn main() {
         .unwrap_or(vertex_count * 3);
 
 
-    let maybe_graph = creates_random_dag(vertex_count, edge_count);
+    let connected = args.is_present("connected");
+
+    let maybe_graph = creates_random_dag(vertex_count, edge_count).map(|graph| {
+        if connected {
+            connect_components(graph)
+        } else {
+            graph
+        }
+    });
 
     if let Some(random_graph) = maybe_graph {
         if !force {
@@ -131,3 +140,14 @@ fn creates_random_dag(vertex_count: usize, edge_count: usize) -> Option<Directed
 
     Some(graph)
 }
+
+// Links each weakly connected component to the next one with a single edge
+// Edges go from the smaller to the bigger vertex id, so a DAG stays a DAG
+fn connect_components(mut graph: DirectedGraph) -> DirectedGraph {
+    let components = connectivity::weakly_connected_components(&graph);
+    for pair in components.windows(2) {
+        let (this, next) = (pair[0][0], pair[1][0]);
+        graph.add_edge(Edge(this.min(next), this.max(next)));
+    }
+    graph
+}
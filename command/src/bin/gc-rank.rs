@@ -0,0 +1,131 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::centrality;
+use gc_core::algorithm::centrality::PageRankParams;
+use gc_core::attribute::mapping::EdgeAttrMapping;
+use gc_core::graph::{Edge, VertexId};
+
+fn main() {
+    let args = App::new("gc-rank")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Ranks the vertices of a graph using a centrality measure")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metric")
+                .long("metric")
+                .short("m")
+                .help("Centrality measure used to rank the vertices")
+                .possible_values(&[
+                    "in-degree",
+                    "out-degree",
+                    "betweenness",
+                    "closeness",
+                    "harmonic",
+                    "pagerank",
+                ])
+                .default_value("pagerank")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("top")
+                .long("top")
+                .short("n")
+                .help("Only prints the n best ranked vertices")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("weight")
+                .long("weight")
+                .short("w")
+                .help("Name of the edge attribute holding the weights used by the betweenness. Weights must be positive. Without it, all edges have a weight of 1")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("damping")
+                .long("damping")
+                .help("Damping factor of the PageRank")
+                .default_value("0.85")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tolerance")
+                .long("tolerance")
+                .help("PageRank iterations stop when the ranks change less than the tolerance")
+                .default_value("0.000001")
+                .required(false)
+                .takes_value(true),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let metric = args.value_of("metric").unwrap();
+    let top = args
+        .value_of("top")
+        .map(|x| x.parse::<usize>().expect("Invalid number of vertices"));
+    let params = PageRankParams {
+        damping: args
+            .value_of("damping")
+            .and_then(|x| x.parse::<f64>().ok())
+            .expect("Invalid damping factor"),
+        tolerance: args
+            .value_of("tolerance")
+            .and_then(|x| x.parse::<f64>().ok())
+            .expect("Invalid tolerance"),
+        ..PageRankParams::default()
+    };
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let scores = match metric {
+        "in-degree" => centrality::degree_in_centrality(&graph),
+        "out-degree" => centrality::degree_out_centrality(&graph),
+        "betweenness" => match args.value_of("weight") {
+            Some(name) => {
+                let weights: EdgeAttrMapping<i64> = graph_utils::load_edge_attribute(path, name)
+                    .expect("Couldn't load weight attribute");
+                let invalid = graph
+                    .edges()
+                    .filter(|e| weights.get(e).is_some_and(|w| *w <= 0))
+                    .min();
+                if let Some(Edge(VertexId(src), VertexId(dst))) = invalid {
+                    println!(
+                        "Edge {} -> {} has a weight which is not positive.",
+                        src, dst
+                    );
+                    return;
+                }
+                let weight = |e: &Edge| -> i64 { weights.get(e).copied().unwrap_or(1) };
+                match centrality::weighted_betweenness(&graph, weight) {
+                    Ok(scores) => scores,
+                    Err(_) => {
+                        println!("The lengths of the shortest paths overflow.");
+                        return;
+                    }
+                }
+            }
+            None => centrality::betweenness(&graph),
+        },
+        "closeness" => centrality::closeness(&graph),
+        "harmonic" => centrality::harmonic(&graph),
+        _ => centrality::page_rank(&graph, &params),
+    };
+
+    let ranking = centrality::ranking(&scores);
+    let count = top.unwrap_or(ranking.len());
+    for (VertexId(vid), score) in ranking.into_iter().take(count) {
+        println!("{} {:.6}", vid, score);
+    }
+}
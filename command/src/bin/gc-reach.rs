@@ -0,0 +1,114 @@
+use clap::{App, Arg, ArgGroup};
+use gc_command::arg_utils;
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::reachability;
+use gc_core::algorithm::reachability::ReachabilityIndex;
+use gc_core::algorithm::topo_sort;
+use gc_core::graph::VertexId;
+use std::collections::HashSet;
+
+fn main() {
+    let args = App::new("gc-reach")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Answers reachability queries: ancestors, descendants, or paths between vertices")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("from")
+                .long("from")
+                .short("f")
+                .help("Vertex the paths start from")
+                .requires("to")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("to")
+                .long("to")
+                .short("t")
+                .help("Vertices to reach from the start vertex")
+                .requires("from")
+                .min_values(1)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("ancestors")
+                .long("ancestors")
+                .short("a")
+                .help("Prints the vertices from which the vertex is reachable")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("descendants")
+                .long("descendants")
+                .short("d")
+                .help("Prints the vertices reachable from the vertex")
+                .takes_value(true),
+        )
+        .group(
+            ArgGroup::with_name("query")
+                .args(&["from", "ancestors", "descendants"])
+                .multiple(true)
+                .required(true),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+
+    if let Some(vertex) = args.value_of("ancestors") {
+        let vertex = parse_vertex(vertex);
+        println!(
+            "ancestors: {}",
+            format_vertices(reachability::ancestors(&graph, vertex))
+        );
+    }
+    if let Some(vertex) = args.value_of("descendants") {
+        let vertex = parse_vertex(vertex);
+        println!(
+            "descendants: {}",
+            format_vertices(reachability::descendants(&graph, vertex))
+        );
+    }
+    if let Some(from) = args.value_of("from") {
+        let from = parse_vertex(from);
+        let targets = args
+            .values_of("to")
+            .and_then(|ids| arg_utils::parse_vertex_id_list(ids.collect()))
+            .expect("Invalid target vertex id");
+        // On a DAG, the index answers each query without searching the graph
+        let index = topo_sort::try_dag(&graph).map(|dag| ReachabilityIndex::new(&dag));
+        for to in targets {
+            let reachable = match &index {
+                Some(index) => index.is_reachable(from, to),
+                None => reachability::is_reachable(&graph, from, to),
+            };
+            println!(
+                "{} -> {}: {}",
+                from.0,
+                to.0,
+                if reachable { "yes" } else { "no" }
+            );
+        }
+    }
+}
+
+fn parse_vertex(vertex: &str) -> VertexId {
+    arg_utils::parse_vertex_id(vertex)
+        .map(VertexId)
+        .expect("Invalid vertex id")
+}
+
+fn format_vertices(vertices: HashSet<VertexId>) -> String {
+    let mut vertices: Vec<u64> = vertices.iter().map(|VertexId(vid)| *vid).collect();
+    vertices.sort();
+    format!["{:?}", vertices]
+}
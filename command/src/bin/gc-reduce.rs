@@ -0,0 +1,56 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::topo_sort;
+use gc_core::algorithm::transitive;
+use gc_core::graph::Edge;
+
+fn main() {
+    let args = App::new("gc-reduce")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Removes the redundant edges of a DAG, keeping only its transitive reduction")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .short("n")
+                .help("Only prints the redundant edges, without removing them from the graph")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let dry_run = args.is_present("dry-run");
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let dag = match topo_sort::try_dag(&graph) {
+        Some(dag) => dag,
+        None => {
+            println!("Graph is not a DAG.");
+            return;
+        }
+    };
+
+    let redundant = transitive::redundant_edges(&dag);
+    println!("redundant edges: {}", redundant.len());
+    for Edge(src, dst) in &redundant {
+        println!("{} {}", src.0, dst.0);
+    }
+    if !dry_run && !redundant.is_empty() {
+        let edges = redundant
+            .iter()
+            .map(|Edge(src, dst)| (*src, *dst))
+            .collect();
+        graph_utils::remove_edges(path, edges).expect("Couldn't remove the redundant edges");
+    }
+}
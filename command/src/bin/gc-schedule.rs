@@ -0,0 +1,121 @@
+use clap::{App, Arg};
+use gc_command::graph_utils;
+use gc_command::version;
+use gc_core::algorithm::critical_path;
+use gc_core::algorithm::critical_path::Duration;
+use gc_core::algorithm::topo_sort;
+use gc_core::attribute::mapping::VertexAttrMapping;
+use gc_core::graph::VertexId;
+
+fn main() {
+    let args = App::new("gc-schedule")
+        .version(version::VERSION)
+        .author(version::AUTHOR)
+        .about("Schedules the tasks of a DAG and finds its critical path (CPM)")
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .short("p")
+                .help("Use the specified directory instead of the current one")
+                .default_value(".")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("duration")
+                .long("duration")
+                .short("d")
+                .help("Name of the vertex attribute holding the duration of each task. Durations must not be negative")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("default-duration")
+                .long("default-duration")
+                .help("Duration of the tasks without a duration attribute")
+                .default_value("0")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("critical")
+                .long("critical")
+                .short("c")
+                .help("Only prints the critical path")
+                .required(false)
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let path = args.value_of("path").unwrap();
+    let name = args.value_of("duration").unwrap();
+    let default_duration = args
+        .value_of("default-duration")
+        .unwrap()
+        .parse::<Duration>()
+        .expect("Invalid default duration");
+    if default_duration < 0 {
+        println!("The default duration is negative.");
+        return;
+    }
+
+    let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let durations: VertexAttrMapping<Duration> =
+        graph_utils::load_vertex_attribute(path, name).expect("Couldn't load duration attribute");
+    // Tasks are scheduled along the longest paths, which are only meaningful without negative durations
+    let negative = graph
+        .vertices()
+        .filter(|v| durations.get(v).is_some_and(|d| *d < 0))
+        .min();
+    if let Some(vertex) = negative {
+        let VertexId(vid) = vertex;
+        println!(
+            "Vertex {} has a negative duration of {}.",
+            vid,
+            durations.get(vertex).unwrap()
+        );
+        return;
+    }
+    let duration =
+        |v: &VertexId| -> Duration { durations.get(v).copied().unwrap_or(default_duration) };
+    let dag = match topo_sort::try_dag(&graph) {
+        Some(dag) => dag,
+        None => {
+            println!("Graph is not a DAG.");
+            return;
+        }
+    };
+
+    let schedule = match critical_path::schedule(&dag, duration) {
+        Some(schedule) => schedule,
+        None => {
+            println!("The durations of the tasks overflow.");
+            return;
+        }
+    };
+    println!("makespan: {}", schedule.makespan);
+    println!(
+        "critical path: {:?}",
+        schedule
+            .critical_path
+            .iter()
+            .map(|VertexId(vid)| vid)
+            .collect::<Vec<&u64>>()
+    );
+    if args.is_present("critical") {
+        return;
+    }
+    let mut tasks: Vec<_> = schedule.tasks.iter().collect();
+    tasks.sort_by_key(|(vid, task)| (task.earliest_start, **vid));
+    println!("vertex earliest_start earliest_finish latest_start latest_finish slack");
+    for (VertexId(vid), task) in tasks {
+        println!(
+            "{} {} {} {} {} {}",
+            vid,
+            task.earliest_start,
+            task.earliest_finish,
+            task.latest_start,
+            task.latest_finish,
+            task.slack()
+        );
+    }
+}
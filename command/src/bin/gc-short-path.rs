@@ -2,8 +2,10 @@ use clap::{App, Arg};
 use gc_command::arg_utils;
 use gc_command::graph_utils;
 use gc_command::version;
-use gc_core::directed_graph::DirectedGraph;
-use gc_core::graph::VertexId;
+use gc_core::algorithm::temporal;
+use gc_core::algorithm::temporal::{Timestamp, Validity};
+use gc_core::attribute::mapping::EdgeAttrMapping;
+use gc_core::graph::{GraphView, VertexId};
 use gc_core::path::ScoredPath;
 
 fn main() {
@@ -38,6 +40,20 @@ fn main() {
                 .takes_value(true)
                 .max_values(1),
         )
+        .arg(
+            Arg::with_name("at")
+                .long("at")
+                .help("Only uses the edges valid at this timestamp")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("validity")
+                .long("validity")
+                .help("Edge attribute holding validity intervals, like 10..20,30..40")
+                .default_value("validity")
+                .takes_value(true),
+        )
         .get_matches();
 
     let path = args.value_of("path").unwrap();
@@ -52,9 +68,22 @@ fn main() {
         .and_then(arg_utils::parse_vertex_id)
         .map(|id| VertexId(id))
         .unwrap();
+    let at = args
+        .value_of("at")
+        .map(|t| t.parse::<Timestamp>().expect("Invalid timestamp"));
     let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
+    let shortest = match at {
+        Some(time) => {
+            let name = args.value_of("validity").unwrap();
+            let validity: EdgeAttrMapping<Validity> = graph_utils::load_edge_attribute(path, name)
+                .expect("Couldn't load validity attribute");
+            let valid_graph = temporal::valid_at(&graph, &validity, time);
+            shortest_path(&valid_graph, start_vertex, end_vertex)
+        }
+        None => shortest_path(&graph, start_vertex, end_vertex),
+    };
 
-    match shortest_path(&graph, start_vertex, end_vertex) {
+    match shortest {
         Some(ScoredPath {
             score,
             path: shortest,
@@ -74,7 +103,7 @@ fn main() {
     }
 }
 
-fn shortest_path(graph: &DirectedGraph, start: VertexId, end: VertexId) -> Option<ScoredPath> {
+fn shortest_path<G: GraphView>(graph: &G, start: VertexId, end: VertexId) -> Option<ScoredPath> {
     use gc_core::search::a_star;
     a_star::shortest_path(
         graph,
@@ -1,7 +1,11 @@
-use clap::{App, Arg};
+use clap::{App, Arg, ArgGroup};
 use gc_command::graph_utils;
 use gc_command::version;
+use gc_core::algorithm::cycle;
 use gc_core::algorithm::topo_sort;
+use gc_core::directed_graph::DirectedGraph;
+use gc_core::graph::Edge;
+use gc_core::graph::VertexId;
 
 
 fn main() {
@@ -18,20 +22,108 @@ fn main() {
                 .required(true)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("explain")
+                .long("explain")
+                .short("e")
+                .help("Prints a cycle of the graph when it is not a DAG")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("all")
+                .long("all")
+                .short("a")
+                .help("Prints all the topological orders, in lexicographic order")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .short("n")
+                .help("Maximum number of topological orders to print")
+                .requires("all")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("layers")
+                .long("layers")
+                .short("l")
+                .help("Prints the level of each vertex: the longest path ending on it")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("waves")
+                .long("waves")
+                .short("w")
+                .help("Prints the vertices grouped by level, to process in parallel")
+                .required(false)
+                .takes_value(false),
+        )
+        .group(ArgGroup::with_name("output").args(&["all", "layers", "waves"]))
         .get_matches();
 
     let path = args.value_of("path").unwrap();
+    let explain = args.is_present("explain");
 
     let graph = graph_utils::load_graph(path).expect("Couldn't load graph");
 
-    match topo_sort::topological_sort(&graph) {
-        Some(vertices) => {
-            print!("Topological order: ");
-            for v in vertices {
-                print!("{} ", v.0);
+    let dag = match topo_sort::try_dag(&graph) {
+        Some(dag) => dag,
+        None => {
+            println!("Graph is not a DAG.");
+            if explain {
+                println!("cycle: {}", format_cycle(&graph));
             }
-            println!()
+            return;
         }
-        None => println!("Graph is not a DAG."),
+    };
+
+    if args.is_present("all") {
+        let limit = args
+            .value_of("limit")
+            .map(|n| n.parse::<usize>().expect("Invalid limit"))
+            .unwrap_or(usize::MAX);
+        for order in topo_sort::all_topological_sorts(&dag).take(limit) {
+            println!("{}", format_vertices(&order));
+        }
+    } else if args.is_present("layers") {
+        let mut levels: Vec<(VertexId, usize)> = topo_sort::layering(&dag).into_iter().collect();
+        levels.sort();
+        for (VertexId(vid), level) in levels {
+            println!("{} {}", vid, level);
+        }
+    } else if args.is_present("waves") {
+        for (index, wave) in topo_sort::waves(&dag).iter().enumerate() {
+            println!("wave {}: {}", index, format_vertices(wave));
+        }
+    } else {
+        let order = topo_sort::lexicographic_topological_sort(&graph)
+            .expect("A DAG has a topological order");
+        println!("Topological order: {}", format_vertices(&order));
+    }
+}
+
+fn format_vertices(vertices: &[VertexId]) -> String {
+    vertices
+        .iter()
+        .map(|VertexId(vid)| vid.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// A self loop is the smallest cycle, but it is not a Cycle
+fn format_cycle(graph: &DirectedGraph) -> String {
+    if let Some(Edge(vid, _)) = graph.edges().find(|Edge(src, dst)| src == dst) {
+        return format!["{:?}", vec![vid.0]];
+    }
+    match cycle::first(graph) {
+        Some(cycle) => format![
+            "{:?}",
+            cycle.iter().map(|VertexId(vid)| vid).collect::<Vec<&u64>>()
+        ],
+        None => "N/A".to_string(),
     }
 }
@@ -0,0 +1,92 @@
+//! Arguments and formatting shared by the flow commands
+
+use crate::arg_utils;
+use crate::graph_utils;
+use clap::{App, Arg, ArgMatches};
+use gc_core::algorithm::max_flow::{Capacity, Flow};
+use gc_core::attribute::mapping::EdgeAttrMapping;
+use gc_core::graph::{Edge, VertexId};
+use std::collections::HashMap;
+
+/// Adds the sources, the sinks and the capacities arguments
+pub fn flow_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("source")
+            .long("source")
+            .short("s")
+            .help("Source vertices")
+            .required(true)
+            .min_values(1)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("sink")
+            .long("sink")
+            .short("t")
+            .help("Sink vertices")
+            .required(true)
+            .min_values(1)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("capacity")
+            .long("capacity")
+            .short("c")
+            .help("Name of the edge attribute holding the capacities. Without it, all edges have the default capacity")
+            .required(false)
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("default-capacity")
+            .long("default-capacity")
+            .help("Capacity of the edges without a capacity attribute")
+            .default_value("1")
+            .required(false)
+            .takes_value(true),
+    )
+}
+
+/// Parses the source and the sink vertices
+pub fn parse_terminals(args: &ArgMatches) -> (Vec<VertexId>, Vec<VertexId>) {
+    let sources = args
+        .values_of("source")
+        .and_then(|ids| arg_utils::parse_vertex_id_list(ids.collect()))
+        .expect("Invalid source vertex id");
+    let sinks = args
+        .values_of("sink")
+        .and_then(|ids| arg_utils::parse_vertex_id_list(ids.collect()))
+        .expect("Invalid sink vertex id");
+    (sources, sinks)
+}
+
+/// Loads the capacity attribute, falling back on the default capacity for the missing edges
+pub fn load_capacity(root_dir: &str, args: &ArgMatches) -> impl Fn(&Edge) -> Capacity {
+    let default_capacity = args
+        .value_of("default-capacity")
+        .and_then(|x| x.parse::<Capacity>().ok())
+        .expect("Invalid default capacity");
+    let capacities: Option<EdgeAttrMapping<Capacity>> = args.value_of("capacity").map(|name| {
+        graph_utils::load_edge_attribute(root_dir, name).expect("Couldn't load capacity attribute")
+    });
+    move |e: &Edge| -> Capacity {
+        capacities
+            .as_ref()
+            .and_then(|mapping| mapping.get(e).copied())
+            .unwrap_or(default_capacity)
+    }
+}
+
+/// Prints the edges carrying some flow, with their flow and their capacity
+pub fn print_flows(flows: &HashMap<Edge, (Flow, Capacity)>) {
+    let mut edges: Vec<Edge> = flows
+        .iter()
+        .filter(|(_, (flow, _))| *flow > 0)
+        .map(|(edge, _)| *edge)
+        .collect();
+    edges.sort();
+    for edge in edges {
+        let Edge(VertexId(src), VertexId(dst)) = edge;
+        let (flow, capacity) = flows[&edge];
+        println!("{} {} {}/{}", src, dst, flow, capacity);
+    }
+}
@@ -1,5 +1,7 @@
 //! Collection of useful functions for command-line tools
 
+use gc_core::attribute::attribute_command::AttributeCommand;
+use gc_core::attribute::mapping::{EdgeAttrMapping, VertexAttrMapping};
 use gc_core::graph::VertexId;
 use gc_core::graph_command::GraphCommand;
 use gc_core::graph_command::GraphCommand::AddEdge;
@@ -7,16 +9,20 @@ use gc_core::graph_command::GraphCommand::AddVertex;
 use gc_core::graph_command::GraphCommand::RemoveEdge;
 use gc_core::graph_command::GraphCommand::RemoveVertex;
 use std::error::Error;
+use std::fmt::Display;
 use std::fs;
 use std::io;
 use std::path;
+use std::str::FromStr;
 
 use gc_core::directed_graph::DirectedGraph;
+use gc_core::format::attr;
 use gc_core::format::gcmd;
 
 pub const GRAPH_ROOT_DIR: &str = ".graph";
 pub const COMMANDS_FILE: &str = "commands";
 pub const LOCK_FILE: &str = "lock";
+pub const ATTRIBUTES_DIR: &str = "attributes";
 
 /// Init the directories / files necessary to have a working empty graph
 pub fn init(root_dir: &str) -> io::Result<()> {
@@ -130,6 +136,44 @@ pub fn remove_edges(root_dir: &str, edges: Vec<(VertexId, VertexId)>) -> std::io
     )
 }
 
+/// Loads a vertex attribute of the graph into memory
+/// Values are parsed into the requested type
+pub fn load_vertex_attribute<T: FromStr>(
+    root_dir: &str,
+    name: &str,
+) -> Result<VertexAttrMapping<T>, String> {
+    match fs::File::open(attribute_path(root_dir, name).as_ref()) {
+        Ok(commands) => attr::read_vertex_mapping(commands, name),
+        Err(io_err) => Err(format!["Attribute '{}': {}", name, io_err]),
+    }
+}
+
+/// Loads an edge attribute of the graph into memory
+/// Values are parsed into the requested type
+pub fn load_edge_attribute<T: FromStr>(
+    root_dir: &str,
+    name: &str,
+) -> Result<EdgeAttrMapping<T>, String> {
+    match fs::File::open(attribute_path(root_dir, name).as_ref()) {
+        Ok(commands) => attr::read_edge_mapping(commands, name),
+        Err(io_err) => Err(format!["Attribute '{}': {}", name, io_err]),
+    }
+}
+
+/// Applies a list of AttributeCommand to the given attribute
+/// The attribute is created if it doesn't exist yet
+pub fn apply_attribute_commands<T: Display>(
+    root_dir: &str,
+    name: &str,
+    commands: Vec<AttributeCommand<T>>,
+) -> std::io::Result<()> {
+    with_lock(root_dir, || {
+        let path = attribute_path(root_dir, name);
+        path.parent().map(fs::create_dir_all).transpose()?;
+        attr::add_commands(path.as_ref().to_str().unwrap(), commands)
+    })
+}
+
 /// Helpers
 
 fn touch(path: &path::Path) -> io::Result<()> {
@@ -159,6 +203,14 @@ fn command_path(root_dir: &str) -> Box<path::Path> {
         .into_boxed_path()
 }
 
+fn attribute_path(root_dir: &str, name: &str) -> Box<path::Path> {
+    path::Path::new(root_dir)
+        .join(GRAPH_ROOT_DIR)
+        .join(ATTRIBUTES_DIR)
+        .join(name)
+        .into_boxed_path()
+}
+
 // Applies a GraphCommand on the file, making sure the lock is acquired and released
 fn apply_graph_commands(root_dir: &str, commands: Vec<GraphCommand>) -> std::io::Result<()> {
     with_lock(root_dir, || {
@@ -1,4 +1,5 @@
 pub mod arg_utils;
+pub mod flow_utils;
 pub mod graph_utils;
 pub mod path_formatter;
 pub mod version;
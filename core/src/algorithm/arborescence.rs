@@ -0,0 +1,317 @@
+//! Minimum spanning arborescence and minimum spanning forest
+use crate::algorithm::connectivity::UnionFind;
+use crate::directed_graph::DirectedGraph;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use crate::iter::iter_breadth;
+use std::collections::HashMap;
+
+/// Minimum spanning arborescence: the cheapest tree of edges going from the root to
+/// every vertex reachable from it. Vertices that cannot be reached from the root are left out.
+/// Returns None if the root is not in the graph
+/// Implementation of Chu-Liu/Edmonds algorithm, running in O(VE)
+/// See https://en.wikipedia.org/wiki/Edmonds%27_algorithm
+pub fn min_spanning_arborescence<G, WFn>(
+    graph: &G,
+    weight: WFn,
+    root: VertexId,
+) -> Option<DirectedGraph>
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> i64,
+{
+    if !graph.contains_vertex(root) {
+        return None;
+    }
+    let mut vertices: Vec<VertexId> = iter_breadth::bfs_iter_from(graph, root).collect();
+    vertices.sort();
+    let indices: HashMap<VertexId, usize> =
+        vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut edges: Vec<Edge> = graph
+        .edges()
+        .filter(|Edge(src, _)| indices.contains_key(src))
+        .collect();
+    edges.sort();
+    let arcs: Vec<WeightedArc> = edges
+        .iter()
+        .enumerate()
+        .map(|(origin, edge)| WeightedArc {
+            src: indices[&edge.0],
+            dst: indices[&edge.1],
+            weight: weight(edge),
+            origin,
+        })
+        .collect();
+    let chosen = chu_liu_edmonds(vertices.len(), indices[&root], &arcs)
+        .expect("All vertices are reachable from the root");
+
+    let mut tree = DirectedGraph::new();
+    tree.add_vertex(root);
+    for arc in chosen {
+        tree.add_edge(edges[arcs[arc].origin]);
+    }
+    Some(tree)
+}
+
+/// Minimum spanning forest of the underlying undirected graph
+/// Edges keep their direction in the forest, but the direction is ignored to build it.
+/// Implementation of Kruskal algorithm, running in O(E log E)
+/// See https://en.wikipedia.org/wiki/Kruskal%27s_algorithm
+pub fn min_spanning_forest<G, WFn>(graph: &G, weight: WFn) -> DirectedGraph
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> i64,
+{
+    let mut vertices: Vec<VertexId> = graph.vertices().collect();
+    vertices.sort();
+    let indices: HashMap<VertexId, usize> =
+        vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut edges: Vec<(i64, Edge)> = graph.edges().map(|e| (weight(&e), e)).collect();
+    edges.sort();
+
+    let mut forest = DirectedGraph::new();
+    for vertex in &vertices {
+        forest.add_vertex(*vertex);
+    }
+    let mut sets = UnionFind::new(vertices.len());
+    for (_, edge) in edges {
+        let Edge(src, dst) = edge;
+        if sets.union(indices[&src], indices[&dst]) {
+            forest.add_edge(edge);
+        }
+    }
+    forest
+}
+
+// Edge between indexed vertices. Arcs of a contracted graph keep track of the arc they
+// come from in the graph before contraction
+struct WeightedArc {
+    src: usize,
+    dst: usize,
+    weight: i64,
+    origin: usize,
+}
+
+// Picks the cheapest entering arc of each vertex. If they form cycles, each cycle is contracted
+// into a single vertex and the algorithm is run again on the contracted graph. The arc entering
+// a contracted cycle then replaces the cheapest entering arc of the vertex it enters.
+// Returns the indices of the chosen arcs, or None if a vertex has no entering arc
+fn chu_liu_edmonds(size: usize, root: usize, arcs: &[WeightedArc]) -> Option<Vec<usize>> {
+    let mut cheapest: Vec<Option<usize>> = vec![None; size];
+    for (index, arc) in arcs.iter().enumerate() {
+        if arc.src != arc.dst
+            && arc.dst != root
+            && cheapest[arc.dst].is_none_or(|c| arc.weight < arcs[c].weight)
+        {
+            cheapest[arc.dst] = Some(index);
+        }
+    }
+    let mut parent = vec![root; size];
+    for vertex in (0..size).filter(|v| *v != root) {
+        parent[vertex] = arcs[cheapest[vertex]?].src;
+    }
+
+    // Following the parents from each vertex either ends at the root or loops on a cycle
+    let mut component: Vec<Option<usize>> = vec![None; size];
+    let mut visited_from: Vec<Option<usize>> = vec![None; size];
+    let mut cycles = 0;
+    for start in 0..size {
+        let mut vertex = start;
+        while vertex != root && visited_from[vertex].is_none() {
+            visited_from[vertex] = Some(start);
+            vertex = parent[vertex];
+        }
+        if vertex != root && visited_from[vertex] == Some(start) && component[vertex].is_none() {
+            let mut member = vertex;
+            loop {
+                component[member] = Some(cycles);
+                member = parent[member];
+                if member == vertex {
+                    break;
+                }
+            }
+            cycles += 1;
+        }
+    }
+    if cycles == 0 {
+        return Some((0..size).filter_map(|v| cheapest[v]).collect());
+    }
+
+    let mut count = cycles;
+    let component: Vec<usize> = component
+        .into_iter()
+        .map(|c| {
+            c.unwrap_or_else(|| {
+                count += 1;
+                count - 1
+            })
+        })
+        .collect();
+    let in_cycle = |vertex: usize| component[vertex] < cycles;
+    let contracted: Vec<WeightedArc> = arcs
+        .iter()
+        .enumerate()
+        .filter(|(_, arc)| component[arc.src] != component[arc.dst])
+        .map(|(index, arc)| WeightedArc {
+            src: component[arc.src],
+            dst: component[arc.dst],
+            // Entering a cycle saves the cost of the cheapest arc it replaces
+            weight: match cheapest[arc.dst] {
+                Some(replaced) if in_cycle(arc.dst) => arc.weight - arcs[replaced].weight,
+                _ => arc.weight,
+            },
+            origin: index,
+        })
+        .collect();
+    let chosen = chu_liu_edmonds(count, component[root], &contracted)?;
+
+    let mut entered = vec![false; size];
+    let mut res: Vec<usize> = vec![];
+    for arc in chosen {
+        let origin = contracted[arc].origin;
+        entered[arcs[origin].dst] = true;
+        res.push(origin);
+    }
+    for vertex in (0..size).filter(|v| in_cycle(*v) && !entered[*v]) {
+        res.extend(cheapest[vertex]);
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn min_spanning_arborescence_should_pick_the_cheapest_entering_edges() {
+        let (g, weightfn) = weighted_graph_of(vec![(0, 1, 3), (0, 2, 1), (2, 1, 1), (1, 3, 2)]);
+        let tree = min_spanning_arborescence(&g, &weightfn, VertexId(0)).unwrap();
+        assert_eq![sorted_edges(&tree), edges(vec![(0, 2), (1, 3), (2, 1)])];
+    }
+
+    #[test]
+    fn min_spanning_arborescence_should_break_cycles_of_cheapest_edges() {
+        // Cheapest entering edges of 1 and 2 form the cycle 1 -> 2 -> 1
+        let (g, weightfn) =
+            weighted_graph_of(vec![(0, 1, 5), (1, 2, 1), (2, 1, 1), (0, 2, 6), (2, 3, 2)]);
+        let tree = min_spanning_arborescence(&g, &weightfn, VertexId(0)).unwrap();
+        assert_eq![sorted_edges(&tree), edges(vec![(0, 1), (1, 2), (2, 3)])];
+        assert_eq![total_weight(&tree, &weightfn), 8];
+    }
+
+    #[test]
+    fn min_spanning_arborescence_should_match_an_exhaustive_search() {
+        // Pseudo random weights on a dense graph
+        let mut weighted_edges = vec![];
+        let mut seed: i64 = 7;
+        for src in 0..6 {
+            for dst in 1..6 {
+                seed = (seed * 1103 + 12345) % 1009;
+                if src != dst && seed % 3 != 0 {
+                    weighted_edges.push((src, dst, seed % 20));
+                }
+            }
+        }
+        let (g, weightfn) = weighted_graph_of(weighted_edges);
+        let tree = min_spanning_arborescence(&g, &weightfn, VertexId(0)).unwrap();
+        assert_eq![tree.vertex_count(), g.vertex_count()];
+        assert_eq![tree.edge_count(), g.vertex_count() - 1];
+        assert_eq![
+            iter_breadth::bfs_iter_from(&tree, VertexId(0)).count(),
+            g.vertex_count()
+        ];
+        assert_eq![
+            total_weight(&tree, &weightfn),
+            exhaustive_min_arborescence(&g, &weightfn, VertexId(0))
+        ];
+    }
+
+    #[test]
+    fn min_spanning_arborescence_should_leave_out_unreachable_vertices() {
+        let (g, weightfn) = weighted_graph_of(vec![(0, 1, 1), (2, 1, 0), (1, 3, 1)]);
+        let tree = min_spanning_arborescence(&g, &weightfn, VertexId(0)).unwrap();
+        assert_eq![sorted_edges(&tree), edges(vec![(0, 1), (1, 3)])];
+        assert![!tree.contains_vertex(VertexId(2))];
+        assert![min_spanning_arborescence(&g, &weightfn, VertexId(42)).is_none()];
+    }
+
+    #[test]
+    fn min_spanning_forest_should_ignore_the_direction_of_edges() {
+        let (mut g, weightfn) =
+            weighted_graph_of(vec![(1, 2, 1), (3, 2, 2), (1, 3, 3), (3, 4, 1), (5, 6, 1)]);
+        g.add_vertex(VertexId(7));
+        let forest = min_spanning_forest(&g, &weightfn);
+        assert_eq![
+            sorted_edges(&forest),
+            edges(vec![(1, 2), (3, 2), (3, 4), (5, 6)])
+        ];
+        assert_eq![forest.vertex_count(), 7];
+    }
+
+    // Helpers
+
+    // Tries all the combinations of one entering edge per vertex
+    fn exhaustive_min_arborescence<WFn: Fn(&Edge) -> i64>(
+        g: &DirectedGraph,
+        weightfn: &WFn,
+        root: VertexId,
+    ) -> i64 {
+        let mut others: Vec<VertexId> = g.vertices().copied().filter(|v| *v != root).collect();
+        others.sort();
+        let choices: Vec<Vec<Edge>> = others
+            .iter()
+            .map(|v| g.inbound_edges(*v).copied().collect())
+            .collect();
+        let mut best = i64::MAX;
+        let mut selection = vec![0; choices.len()];
+        loop {
+            let mut candidate = DirectedGraph::new();
+            for (choice, index) in choices.iter().zip(selection.iter()) {
+                candidate.add_edge(choice[*index]);
+            }
+            if iter_breadth::bfs_iter_from(&candidate, root).count() == g.vertex_count() {
+                best = best.min(total_weight(&candidate, weightfn));
+            }
+            // Next combination
+            let mut position = 0;
+            while position < selection.len() && selection[position] + 1 == choices[position].len() {
+                selection[position] = 0;
+                position += 1;
+            }
+            if position == selection.len() {
+                return best;
+            }
+            selection[position] += 1;
+        }
+    }
+
+    fn total_weight<WFn: Fn(&Edge) -> i64>(g: &DirectedGraph, weightfn: &WFn) -> i64 {
+        g.edges().map(weightfn).sum()
+    }
+
+    fn weighted_graph_of(edges: Vec<(u64, u64, i64)>) -> (DirectedGraph, impl Fn(&Edge) -> i64) {
+        let mut g = DirectedGraph::new();
+        let mut weights: HashMap<Edge, i64> = HashMap::new();
+        for (src, dst, w) in edges {
+            g.add_edge(edge(src, dst));
+            weights.insert(edge(src, dst), w);
+        }
+        (g, move |e: &Edge| weights[e])
+    }
+
+    fn sorted_edges(g: &DirectedGraph) -> Vec<Edge> {
+        let mut res: Vec<Edge> = g.edges().copied().collect();
+        res.sort();
+        res
+    }
+
+    fn edges(pairs: Vec<(u64, u64)>) -> Vec<Edge> {
+        pairs.into_iter().map(|(src, dst)| edge(src, dst)).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
@@ -0,0 +1,484 @@
+//! Centrality measures, ranking the vertices of a graph by importance
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use crate::iter::iter_datastructure::{MinPriorityQueue, Queue, SearchQueue};
+use crate::weight::Overflow;
+use std::collections::HashMap;
+
+/// Score of each vertex of the graph
+pub type Centrality = HashMap<VertexId, f64>;
+
+/// Parameters of the PageRank algorithm
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct PageRankParams {
+    /// Probability to follow an outbound edge rather than jumping to a random vertex
+    pub damping: f64,
+    /// Iterations stop when the sum of the rank changes is below the tolerance
+    pub tolerance: f64,
+    /// Iterations stop after this number of iterations even if the ranks did not converge
+    pub max_iterations: usize,
+}
+
+impl Default for PageRankParams {
+    fn default() -> Self {
+        PageRankParams {
+            damping: 0.85,
+            tolerance: 1.0e-6,
+            max_iterations: 100,
+        }
+    }
+}
+
+/// In-degree of each vertex, divided by the number of other vertices
+pub fn degree_in_centrality<G: GraphView>(graph: &G) -> Centrality {
+    degree_centrality(graph, |vid| graph.degree_in(vid))
+}
+
+/// Out-degree of each vertex, divided by the number of other vertices
+pub fn degree_out_centrality<G: GraphView>(graph: &G) -> Centrality {
+    degree_centrality(graph, |vid| graph.degree_out(vid))
+}
+
+/// Betweenness centrality: number of shortest paths between other vertices going through a vertex
+/// Shortest paths are counted in number of edges
+/// Implementation of Brandes algorithm, running in O(VE)
+/// See https://en.wikipedia.org/wiki/Betweenness_centrality
+pub fn betweenness<G: GraphView>(graph: &G) -> Centrality {
+    let indexed = IndexedGraph::new(graph, |_| 1);
+    let mut scores = vec![0.0; indexed.len()];
+    for source in 0..indexed.len() {
+        let paths = indexed.bfs_shortest_paths(source);
+        paths.accumulate(&mut scores);
+    }
+    indexed.centrality(scores)
+}
+
+/// Betweenness centrality, with shortest paths computed using the weight of the edges
+/// Weights must be positive. Returns an error if the length of a path overflows
+/// Implementation of Brandes algorithm using Dijkstra algorithm, running in O(VE + V^2 log V)
+pub fn weighted_betweenness<G, WFn>(graph: &G, weight: WFn) -> Result<Centrality, Overflow>
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> i64,
+{
+    let indexed = IndexedGraph::new(graph, weight);
+    let mut scores = vec![0.0; indexed.len()];
+    for source in 0..indexed.len() {
+        let paths = indexed.dijkstra_shortest_paths(source)?;
+        paths.accumulate(&mut scores);
+    }
+    Ok(indexed.centrality(scores))
+}
+
+/// Closeness centrality: inverse of the average distance to the vertices reachable from a vertex
+/// The score is scaled by the fraction of the graph that is reachable (Wasserman and Faust),
+/// so that a vertex reaching few vertices does not get a high score
+/// See https://en.wikipedia.org/wiki/Closeness_centrality
+pub fn closeness<G: GraphView>(graph: &G) -> Centrality {
+    let indexed = IndexedGraph::new(graph, |_| 1);
+    let others = indexed.len().saturating_sub(1) as f64;
+    let scores = (0..indexed.len())
+        .map(|source| {
+            let distances: Vec<usize> = indexed
+                .bfs_distances(source)
+                .into_iter()
+                .flatten()
+                .collect();
+            let reached = (distances.len() - 1) as f64;
+            let total: usize = distances.iter().sum();
+            if total == 0 {
+                0.0
+            } else {
+                (reached / total as f64) * (reached / others)
+            }
+        })
+        .collect();
+    indexed.centrality(scores)
+}
+
+/// Harmonic centrality: sum of the inverse of the distances to all other vertices
+/// Unreachable vertices do not contribute to the score
+/// See https://en.wikipedia.org/wiki/Closeness_centrality#Harmonic_centrality
+pub fn harmonic<G: GraphView>(graph: &G) -> Centrality {
+    let indexed = IndexedGraph::new(graph, |_| 1);
+    let scores = (0..indexed.len())
+        .map(|source| {
+            indexed
+                .bfs_distances(source)
+                .into_iter()
+                .flatten()
+                .filter(|d| *d > 0)
+                .map(|d| 1.0 / d as f64)
+                .sum()
+        })
+        .collect();
+    indexed.centrality(scores)
+}
+
+/// PageRank of each vertex. Ranks sum to 1
+/// The rank of vertices without outbound edges is evenly distributed to all vertices
+/// See https://en.wikipedia.org/wiki/PageRank
+pub fn page_rank<G: GraphView>(graph: &G, params: &PageRankParams) -> Centrality {
+    let indexed = IndexedGraph::new(graph, |_| 1);
+    let n = indexed.len();
+    if n == 0 {
+        return Centrality::new();
+    }
+    let uniform = 1.0 / n as f64;
+    let mut ranks = vec![uniform; n];
+    for _ in 0..params.max_iterations {
+        let dangling: f64 = (0..n)
+            .filter(|v| indexed.adjacency[*v].is_empty())
+            .map(|v| ranks[v])
+            .sum();
+        let base = (1.0 - params.damping) * uniform + params.damping * dangling * uniform;
+        let mut next = vec![base; n];
+        for (v, rank) in ranks.iter().enumerate() {
+            let share = params.damping * rank / indexed.adjacency[v].len() as f64;
+            for (w, _) in &indexed.adjacency[v] {
+                next[*w] += share;
+            }
+        }
+        let change: f64 = next
+            .iter()
+            .zip(ranks.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        ranks = next;
+        if change < params.tolerance {
+            break;
+        }
+    }
+    indexed.centrality(ranks)
+}
+
+/// Sorts vertices by decreasing score, and by increasing id for equal scores
+pub fn ranking(centrality: &Centrality) -> Vec<(VertexId, f64)> {
+    let mut ranking: Vec<(VertexId, f64)> = centrality.iter().map(|(v, s)| (*v, *s)).collect();
+    ranking.sort_by(|(v1, s1), (v2, s2)| s2.total_cmp(s1).then(v1.cmp(v2)));
+    ranking
+}
+
+fn degree_centrality<G, DFn>(graph: &G, degree: DFn) -> Centrality
+where
+    G: GraphView,
+    DFn: Fn(VertexId) -> usize,
+{
+    let others = graph.vertex_count().saturating_sub(1);
+    graph
+        .vertices()
+        .map(|vid| {
+            let score = if others == 0 {
+                0.0
+            } else {
+                degree(vid) as f64 / others as f64
+            };
+            (vid, score)
+        })
+        .collect()
+}
+
+// Graph with indexed vertices and weighted outbound edges, to avoid hashing during traversals
+struct IndexedGraph {
+    vertices: Vec<VertexId>,
+    adjacency: Vec<Vec<(usize, i64)>>,
+}
+
+// Shortest paths from a source, as needed by the accumulation phase of Brandes algorithm
+struct ShortestPaths {
+    source: usize,
+    // Vertices in order of non decreasing distance from the source
+    order: Vec<usize>,
+    predecessors: Vec<Vec<usize>>,
+    // Number of shortest paths from the source
+    counts: Vec<f64>,
+}
+
+impl IndexedGraph {
+    fn new<G, WFn>(graph: &G, weight: WFn) -> IndexedGraph
+    where
+        G: GraphView,
+        WFn: Fn(&Edge) -> i64,
+    {
+        let mut vertices: Vec<VertexId> = graph.vertices().collect();
+        vertices.sort();
+        let indices: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let adjacency = vertices
+            .iter()
+            .map(|vid| {
+                graph
+                    .outbound_edges(*vid)
+                    .map(|edge| (indices[&edge.1], weight(&edge)))
+                    .collect()
+            })
+            .collect();
+        IndexedGraph {
+            vertices,
+            adjacency,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn centrality(&self, scores: Vec<f64>) -> Centrality {
+        self.vertices.iter().copied().zip(scores).collect()
+    }
+
+    fn bfs_distances(&self, source: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue: Queue<usize> = Queue::<usize>::new();
+        distances[source] = Some(0);
+        queue.push(source);
+        while let Some(v) = queue.pop() {
+            let distance = distances[v].map(|d| d + 1);
+            for (w, _) in &self.adjacency[v] {
+                if distances[*w].is_none() {
+                    distances[*w] = distance;
+                    queue.push(*w);
+                }
+            }
+        }
+        distances
+    }
+
+    fn bfs_shortest_paths(&self, source: usize) -> ShortestPaths {
+        let mut paths = ShortestPaths::new(source, self.len());
+        let mut distances: Vec<Option<usize>> = vec![None; self.len()];
+        let mut queue: Queue<usize> = Queue::<usize>::new();
+        distances[source] = Some(0);
+        queue.push(source);
+        while let Some(v) = queue.pop() {
+            paths.order.push(v);
+            let distance = distances[v].map(|d| d + 1);
+            for (w, _) in &self.adjacency[v] {
+                if distances[*w].is_none() {
+                    distances[*w] = distance;
+                    queue.push(*w);
+                }
+                if distances[*w] == distance {
+                    paths.counts[*w] += paths.counts[v];
+                    paths.predecessors[*w].push(v);
+                }
+            }
+        }
+        paths
+    }
+
+    fn dijkstra_shortest_paths(&self, source: usize) -> Result<ShortestPaths, Overflow> {
+        let mut paths = ShortestPaths::new(source, self.len());
+        let mut distances: Vec<Option<i64>> = vec![None; self.len()];
+        let mut done = vec![false; self.len()];
+        let mut queue: MinPriorityQueue<(i64, usize)> = MinPriorityQueue::<(i64, usize)>::new();
+        distances[source] = Some(0);
+        queue.push((0, source));
+        while let Some((distance, v)) = queue.pop() {
+            if done[v] {
+                continue;
+            }
+            done[v] = true;
+            paths.order.push(v);
+            for (w, weight) in &self.adjacency[v] {
+                let candidate = distance.checked_add(*weight).ok_or(Overflow)?;
+                match distances[*w] {
+                    Some(current) if candidate > current => {}
+                    Some(current) if candidate == current => {
+                        paths.counts[*w] += paths.counts[v];
+                        paths.predecessors[*w].push(v);
+                    }
+                    _ => {
+                        distances[*w] = Some(candidate);
+                        paths.counts[*w] = paths.counts[v];
+                        paths.predecessors[*w] = vec![v];
+                        queue.push((candidate, *w));
+                    }
+                }
+            }
+        }
+        Ok(paths)
+    }
+}
+
+impl ShortestPaths {
+    fn new(source: usize, size: usize) -> ShortestPaths {
+        let mut counts = vec![0.0; size];
+        counts[source] = 1.0;
+        ShortestPaths {
+            source,
+            order: vec![],
+            predecessors: vec![vec![]; size],
+            counts,
+        }
+    }
+
+    // Adds the dependency of the source on each vertex to its score
+    fn accumulate(&self, scores: &mut [f64]) {
+        let mut dependencies = vec![0.0; scores.len()];
+        for w in self.order.iter().rev() {
+            for v in &self.predecessors[*w] {
+                dependencies[*v] += self.counts[*v] / self.counts[*w] * (1.0 + dependencies[*w]);
+            }
+            if *w != self.source {
+                scores[*w] += dependencies[*w];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn degree_centrality_should_divide_degrees_by_the_number_of_other_vertices() {
+        let g = graph_of(vec![(1, 2), (1, 3), (2, 3), (3, 1), (4, 3)]);
+        let in_degrees = degree_in_centrality(&g);
+        let out_degrees = degree_out_centrality(&g);
+        assert_score(&in_degrees, 3, 1.0);
+        assert_score(&in_degrees, 4, 0.0);
+        assert_score(&out_degrees, 1, 2.0 / 3.0);
+        assert_score(&out_degrees, 3, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn betweenness_should_count_the_shortest_paths_going_through_a_vertex() {
+        // 1 -> 2 -> 3 -> 4 : 2 is on the paths 1-3 and 1-4, 3 on the paths 1-4 and 2-4
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 4)]);
+        let scores = betweenness(&g);
+        assert_score(&scores, 1, 0.0);
+        assert_score(&scores, 2, 2.0);
+        assert_score(&scores, 3, 2.0);
+        assert_score(&scores, 4, 0.0);
+    }
+
+    #[test]
+    fn betweenness_should_split_the_score_between_equivalent_shortest_paths() {
+        // 2 shortest paths from 1 to 4, through 2 and through 3
+        let g = graph_of(vec![(1, 2), (1, 3), (2, 4), (3, 4)]);
+        let scores = betweenness(&g);
+        assert_score(&scores, 2, 0.5);
+        assert_score(&scores, 3, 0.5);
+        assert_score(&scores, 4, 0.0);
+    }
+
+    #[test]
+    fn weighted_betweenness_should_use_the_weights_of_the_edges() {
+        // Going through 3 is shorter despite having more edges
+        let mut weights = HashMap::new();
+        weights.insert(edge(1, 2), 10);
+        weights.insert(edge(1, 3), 1);
+        weights.insert(edge(3, 2), 1);
+        let g = graph_of(vec![(1, 2), (1, 3), (3, 2)]);
+        let scores = weighted_betweenness(&g, |e| weights[e]).unwrap();
+        assert_score(&scores, 3, 1.0);
+        assert_score(&betweenness(&g), 3, 0.0);
+    }
+
+    #[test]
+    fn weighted_betweenness_should_not_depend_on_the_vertex_ids() {
+        // 2 shortest paths from 0 to 1, directly and through 2
+        let mut weights = HashMap::new();
+        weights.insert(edge(0, 1), 2);
+        weights.insert(edge(0, 2), 1);
+        weights.insert(edge(2, 1), 1);
+        let g = graph_of(vec![(0, 1), (0, 2), (2, 1)]);
+        let scores = weighted_betweenness(&g, |e| weights[e]).unwrap();
+        assert_score(&scores, 2, 0.5);
+        // Same graph with 1 and 2 swapped
+        let mut weights = HashMap::new();
+        weights.insert(edge(0, 2), 2);
+        weights.insert(edge(0, 1), 1);
+        weights.insert(edge(1, 2), 1);
+        let g = graph_of(vec![(0, 2), (0, 1), (1, 2)]);
+        let scores = weighted_betweenness(&g, |e| weights[e]).unwrap();
+        assert_score(&scores, 1, 0.5);
+    }
+
+    #[test]
+    fn weighted_betweenness_should_detect_overflows() {
+        let mut weights = HashMap::new();
+        weights.insert(edge(1, 2), i64::MAX);
+        weights.insert(edge(2, 3), 1);
+        let g = graph_of(vec![(1, 2), (2, 3)]);
+        assert_eq![weighted_betweenness(&g, |e| weights[e]), Err(Overflow)];
+    }
+
+    #[test]
+    fn closeness_should_be_scaled_by_the_reachable_fraction_of_the_graph() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 4)]);
+        let scores = closeness(&g);
+        // 1 reaches 3 vertices at distances 1, 2, 3
+        assert_score(&scores, 1, 3.0 / 6.0);
+        // 3 reaches 1 vertex at distance 1
+        assert_score(&scores, 3, 1.0 / 3.0);
+        assert_score(&scores, 4, 0.0);
+    }
+
+    #[test]
+    fn harmonic_should_sum_the_inverse_of_the_distances() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 4)]);
+        let scores = harmonic(&g);
+        assert_score(&scores, 1, 1.0 + 0.5 + 1.0 / 3.0);
+        assert_score(&scores, 4, 0.0);
+    }
+
+    #[test]
+    fn page_rank_should_sum_to_one_and_favor_vertices_with_many_inbound_edges() {
+        let g = graph_of(vec![(1, 4), (2, 4), (3, 4), (4, 1), (5, 6)]);
+        let scores = page_rank(&g, &PageRankParams::default());
+        let total: f64 = scores.values().sum();
+        assert![(total - 1.0).abs() < 1.0e-6];
+        assert_eq![ranking(&scores)[0].0, VertexId(4)];
+        assert![scores[&VertexId(6)] > scores[&VertexId(5)]];
+    }
+
+    #[test]
+    fn page_rank_should_be_uniform_on_a_cycle() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1)]);
+        let scores = page_rank(&g, &PageRankParams::default());
+        for vid in 1..=3 {
+            assert_score(&scores, vid, 1.0 / 3.0);
+        }
+    }
+
+    #[test]
+    fn ranking_should_sort_by_decreasing_score_then_by_vertex_id() {
+        let mut scores = Centrality::new();
+        scores.insert(VertexId(3), 1.0);
+        scores.insert(VertexId(1), 0.5);
+        scores.insert(VertexId(2), 1.0);
+        assert_eq![
+            ranking(&scores),
+            vec![(VertexId(2), 1.0), (VertexId(3), 1.0), (VertexId(1), 0.5)]
+        ];
+    }
+
+    // Helpers
+
+    fn assert_score(scores: &Centrality, vid: u64, expected: f64) {
+        let score = scores[&VertexId(vid)];
+        assert![
+            (score - expected).abs() < 1.0e-6,
+            "Score of {} is {}, expected {}",
+            vid,
+            score,
+            expected
+        ];
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
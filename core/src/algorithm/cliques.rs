@@ -0,0 +1,285 @@
+//! Cliques and independent sets
+//! Cliques are searched on the mutual graph: u and v are adjacent if both u -> v and v -> u exist.
+use crate::graph::{Edge, GraphView, VertexId};
+use std::collections::{HashMap, HashSet};
+
+/// Iterates over the maximal cliques of the mutual graph, computed lazily
+/// Implementation of Bron-Kerbosch algorithm with pivoting, where the pivot is the vertex
+/// with the most neighbours among the candidates. Vertices of a clique are sorted
+/// See Tomita, Tanaka and Takahashi, "The worst-case time complexity for generating all maximal
+/// cliques and computational experiments" (2006)
+pub struct MaximalCliques {
+    vertices: Vec<VertexId>,
+    neighbours: Vec<HashSet<usize>>,
+    clique: Vec<usize>,
+    // Vertices adjacent to all the clique, and the ones among them that can still extend it
+    subgraph: HashSet<usize>,
+    candidates: HashSet<usize>,
+    // Candidates left to try to extend the clique
+    extensions: Vec<usize>,
+    stack: Vec<(HashSet<usize>, HashSet<usize>, Vec<usize>)>,
+}
+
+impl Iterator for MaximalCliques {
+    type Item = Vec<VertexId>;
+
+    fn next(&mut self) -> Option<Vec<VertexId>> {
+        loop {
+            match self.extensions.pop() {
+                Some(vertex) => {
+                    self.candidates.remove(&vertex);
+                    self.clique.push(vertex);
+                    let neighbours = &self.neighbours[vertex];
+                    let subgraph: HashSet<usize> =
+                        self.subgraph.intersection(neighbours).copied().collect();
+                    if subgraph.is_empty() {
+                        let mut clique: Vec<VertexId> =
+                            self.clique.iter().map(|v| self.vertices[*v]).collect();
+                        clique.sort();
+                        self.clique.pop();
+                        return Some(clique);
+                    }
+                    let candidates: HashSet<usize> =
+                        self.candidates.intersection(neighbours).copied().collect();
+                    if candidates.is_empty() {
+                        // Vertices already tried make the clique not maximal
+                        self.clique.pop();
+                        continue;
+                    }
+                    let extensions = self.pivot_extensions(&subgraph, &candidates);
+                    let subgraph = std::mem::replace(&mut self.subgraph, subgraph);
+                    let candidates = std::mem::replace(&mut self.candidates, candidates);
+                    let extensions = std::mem::replace(&mut self.extensions, extensions);
+                    self.stack.push((subgraph, candidates, extensions));
+                }
+                None => {
+                    let (subgraph, candidates, extensions) = self.stack.pop()?;
+                    self.subgraph = subgraph;
+                    self.candidates = candidates;
+                    self.extensions = extensions;
+                    self.clique.pop();
+                }
+            }
+        }
+    }
+}
+
+impl MaximalCliques {
+    // Candidates not adjacent to the pivot: cliques with a neighbour of the pivot
+    // are found when extending with the pivot or one of its non neighbours
+    fn pivot_extensions(
+        &self,
+        subgraph: &HashSet<usize>,
+        candidates: &HashSet<usize>,
+    ) -> Vec<usize> {
+        let pivot = subgraph
+            .iter()
+            .max_by_key(|u| {
+                (
+                    candidates.intersection(&self.neighbours[**u]).count(),
+                    std::cmp::Reverse(**u),
+                )
+            })
+            .unwrap();
+        let mut res: Vec<usize> = candidates
+            .iter()
+            .filter(|v| !self.neighbours[*pivot].contains(v))
+            .copied()
+            .collect();
+        // Popped from the end, so the smallest vertices are tried first
+        res.sort_by(|a, b| b.cmp(a));
+        res
+    }
+}
+
+/// Returns an iterator over the maximal cliques of the mutual graph
+pub fn maximal_cliques<G: GraphView>(graph: &G) -> MaximalCliques {
+    let (vertices, neighbours) = mutual_graph(graph);
+    let all: HashSet<usize> = (0..vertices.len()).collect();
+    let mut cliques = MaximalCliques {
+        vertices,
+        neighbours,
+        clique: vec![],
+        subgraph: all.clone(),
+        candidates: all,
+        extensions: vec![],
+        stack: vec![],
+    };
+    if !cliques.vertices.is_empty() {
+        cliques.extensions = cliques.pivot_extensions(&cliques.subgraph, &cliques.candidates);
+    }
+    cliques
+}
+
+/// Finds a clique with the maximum number of vertices in the mutual graph
+/// If several cliques have the maximum size, the first one found is returned
+pub fn maximum_clique<G: GraphView>(graph: &G) -> Vec<VertexId> {
+    maximal_cliques(graph).fold(vec![], |best, clique| {
+        if clique.len() > best.len() {
+            clique
+        } else {
+            best
+        }
+    })
+}
+
+/// Checks if the vertices are all linked to each other both ways
+pub fn is_clique<G: GraphView>(graph: &G, vertices: &[VertexId]) -> bool {
+    vertices.iter().all(|u| {
+        vertices
+            .iter()
+            .all(|v| u == v || graph.contains_edge(Edge(*u, *v)))
+    })
+}
+
+/// Greedy maximal independent set of the underlying undirected graph: no edge in either
+/// direction links two of its vertices. It is maximal, but not necessarily maximum.
+/// Vertices with the fewest remaining neighbours are picked first, then the smallest ones.
+/// Vertices with a self loop are left out. Returned vertices are sorted
+pub fn maximal_independent_set<G: GraphView>(graph: &G) -> Vec<VertexId> {
+    let mut neighbours: HashMap<VertexId, HashSet<VertexId>> = graph
+        .vertices()
+        .filter(|v| !graph.contains_edge(Edge(*v, *v)))
+        .map(|v| (v, HashSet::new()))
+        .collect();
+    for Edge(src, dst) in graph.edges() {
+        if neighbours.contains_key(&src) && neighbours.contains_key(&dst) {
+            neighbours.get_mut(&src).unwrap().insert(dst);
+            neighbours.get_mut(&dst).unwrap().insert(src);
+        }
+    }
+    let mut res = vec![];
+    while let Some(vertex) = neighbours
+        .iter()
+        .min_by_key(|(v, n)| (n.len(), **v))
+        .map(|(v, _)| *v)
+    {
+        res.push(vertex);
+        // Neighbours of the vertex can't be in the set anymore
+        let removed = neighbours.remove(&vertex).unwrap();
+        for next in &removed {
+            neighbours.remove(next);
+        }
+        for others in neighbours.values_mut() {
+            others.retain(|v| !removed.contains(v));
+        }
+    }
+    res.sort();
+    res
+}
+
+/// Checks that no edge links two of the vertices, in either direction
+pub fn is_independent_set<G: GraphView>(graph: &G, vertices: &[VertexId]) -> bool {
+    let members: HashSet<&VertexId> = vertices.iter().collect();
+    graph
+        .edges()
+        .all(|Edge(src, dst)| !members.contains(&src) || !members.contains(&dst))
+}
+
+// Mutual graph with vertices indexed in increasing order
+fn mutual_graph<G: GraphView>(graph: &G) -> (Vec<VertexId>, Vec<HashSet<usize>>) {
+    let mut vertices: Vec<VertexId> = graph.vertices().collect();
+    vertices.sort();
+    let index: HashMap<VertexId, usize> =
+        vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut neighbours = vec![HashSet::new(); vertices.len()];
+    for Edge(src, dst) in graph.edges() {
+        if src != dst && graph.contains_edge(Edge(dst, src)) {
+            neighbours[index[&src]].insert(index[&dst]);
+        }
+    }
+    (vertices, neighbours)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn maximal_cliques_should_only_use_mutual_edges() {
+        let mut g = DirectedGraph::new();
+        add_clique(&mut g, &[1, 2, 3, 4]);
+        add_clique(&mut g, &[4, 5]);
+        add_clique(&mut g, &[5, 6, 7]);
+        // One way edges are not part of cliques
+        g.add_edge(edge(1, 5));
+        g.add_edge(edge(6, 1));
+        g.add_vertex(VertexId(8));
+
+        let mut cliques: Vec<Vec<VertexId>> = maximal_cliques(&g).collect();
+        cliques.sort();
+        assert_eq!(
+            cliques,
+            vec![
+                vertices(vec![1, 2, 3, 4]),
+                vertices(vec![4, 5]),
+                vertices(vec![5, 6, 7]),
+                vertices(vec![8]),
+            ]
+        );
+        assert_eq!(maximum_clique(&g), vertices(vec![1, 2, 3, 4]));
+        assert!(cliques.iter().all(|c| is_clique(&g, c)));
+    }
+
+    #[test]
+    fn maximal_cliques_should_find_each_clique_once() {
+        // Moon-Moser graph: 3 groups of 3 vertices, linked across groups only: 3^3 maximal cliques
+        let mut g = DirectedGraph::new();
+        for u in 0..9 {
+            for v in 0..9 {
+                if u / 3 != v / 3 {
+                    g.add_edge(edge(u, v));
+                }
+            }
+        }
+        let cliques: Vec<Vec<VertexId>> = maximal_cliques(&g).collect();
+        assert_eq!(cliques.len(), 27);
+        assert_eq!(cliques.iter().collect::<HashSet<_>>().len(), 27);
+        assert!(cliques.iter().all(|c| c.len() == 3 && is_clique(&g, c)));
+    }
+
+    #[test]
+    fn maximal_cliques_of_an_empty_graph_is_empty() {
+        assert_eq!(maximal_cliques(&DirectedGraph::new()).count(), 0);
+        assert!(maximum_clique(&DirectedGraph::new()).is_empty());
+    }
+
+    #[test]
+    fn maximal_independent_set_should_not_contain_adjacent_vertices() {
+        // Star 1 -> {2, 3, 4} with 4 -> 5, and a self loop on 6
+        let g = graph_of(vec![(1, 2), (1, 3), (1, 4), (4, 5), (6, 6)]);
+        let set = maximal_independent_set(&g);
+        assert_eq!(set, vertices(vec![2, 3, 4]));
+        assert!(is_independent_set(&g, &set));
+        assert!(!is_independent_set(&g, &vertices(vec![1, 5, 4])));
+    }
+
+    // Helpers
+
+    fn add_clique(g: &mut DirectedGraph, ids: &[u64]) {
+        for u in ids {
+            for v in ids {
+                if u != v {
+                    g.add_edge(edge(*u, *v));
+                }
+            }
+        }
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
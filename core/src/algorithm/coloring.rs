@@ -0,0 +1,275 @@
+//! Vertex coloring of the underlying undirected graph: adjacent vertices get different colors
+//! Colors are numbered from 0. Self loops are ignored, as they can't be properly colored.
+use crate::attribute::mapping::{AttributeMapping, VertexAttrMapping};
+use crate::graph::{Edge, GraphView, VertexId};
+use std::collections::{BTreeSet, HashMap};
+
+/// Name of the attribute mappings returned by the colorings
+pub const COLOR_ATTRIBUTE: &str = "color";
+
+/// Graphs up to this number of vertices can be colored with the minimum number of colors
+pub const EXACT_COLORING_LIMIT: usize = 64;
+
+/// Greedy DSatur coloring, running in O(V^2 + E)
+/// The next vertex to color is the one with the most distinct colors among its neighbours,
+/// then the one with the biggest degree. It takes the smallest color available.
+/// See Brélaz, "New methods to color the vertices of a graph" (1979)
+pub fn dsatur<G: GraphView>(graph: &G) -> VertexAttrMapping<usize> {
+    let undirected = UndirectedGraph::new(graph);
+    let mut colors: Vec<Option<usize>> = vec![None; undirected.len()];
+    let mut saturation: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); undirected.len()];
+    for _ in 0..undirected.len() {
+        let vertex = (0..undirected.len())
+            .filter(|v| colors[*v].is_none())
+            .max_by_key(|v| {
+                (
+                    saturation[*v].len(),
+                    undirected.neighbours[*v].len(),
+                    std::cmp::Reverse(*v),
+                )
+            })
+            .unwrap();
+        let color = (0..).find(|c| !saturation[vertex].contains(c)).unwrap();
+        colors[vertex] = Some(color);
+        for next in &undirected.neighbours[vertex] {
+            saturation[*next].insert(color);
+        }
+    }
+    undirected.mapping(&colors)
+}
+
+/// Greedy Welsh-Powell coloring, running in O(V log V + E)
+/// Vertices are colored by decreasing degree, each taking the smallest color available.
+pub fn welsh_powell<G: GraphView>(graph: &G) -> VertexAttrMapping<usize> {
+    let undirected = UndirectedGraph::new(graph);
+    let mut colors: Vec<Option<usize>> = vec![None; undirected.len()];
+    for vertex in undirected.by_decreasing_degree() {
+        colors[vertex] = Some(undirected.smallest_available_color(vertex, &colors));
+    }
+    undirected.mapping(&colors)
+}
+
+/// Finds a coloring using at most the given number of colors, with a backtracking search
+/// Returns None if there is no such coloring. It takes an exponential time in the worst case.
+pub fn k_coloring<G: GraphView>(graph: &G, max_colors: usize) -> Option<VertexAttrMapping<usize>> {
+    let undirected = UndirectedGraph::new(graph);
+    undirected
+        .k_coloring(max_colors)
+        .map(|colors| undirected.mapping(&colors))
+}
+
+/// Coloring with the minimum number of colors, ie the chromatic number of the graph
+/// Returns None if the graph has more than EXACT_COLORING_LIMIT vertices
+pub fn min_coloring<G: GraphView>(graph: &G) -> Option<VertexAttrMapping<usize>> {
+    if graph.vertex_count() > EXACT_COLORING_LIMIT {
+        return None;
+    }
+    let undirected = UndirectedGraph::new(graph);
+    let upper_bound = color_count(&dsatur(graph));
+    let colors = (0..upper_bound)
+        .find_map(|k| undirected.k_coloring(k))
+        .or_else(|| undirected.k_coloring(upper_bound))
+        .expect("DSatur found a coloring with this number of colors");
+    Some(undirected.mapping(&colors))
+}
+
+/// Number of colors used by a coloring
+pub fn color_count(colors: &VertexAttrMapping<usize>) -> usize {
+    colors.iter().map(|(_, c)| c + 1).max().unwrap_or(0)
+}
+
+/// Checks that all the vertices are colored, with adjacent vertices having different colors
+pub fn is_proper_coloring<G: GraphView>(graph: &G, colors: &VertexAttrMapping<usize>) -> bool {
+    graph.vertices().all(|v| colors.get(&v).is_some())
+        && graph
+            .edges()
+            .filter(|Edge(src, dst)| src != dst)
+            .all(|Edge(src, dst)| colors.get(&src) != colors.get(&dst))
+}
+
+/// Groups the vertices by color, each group being sorted
+pub fn color_classes(colors: &VertexAttrMapping<usize>) -> Vec<Vec<VertexId>> {
+    let mut res: Vec<Vec<VertexId>> = vec![vec![]; color_count(colors)];
+    for (vertex, color) in colors.iter() {
+        res[*color].push(*vertex);
+    }
+    for class in res.iter_mut() {
+        class.sort();
+    }
+    res
+}
+
+// Undirected graph with indexed vertices, in increasing order, without self loops
+struct UndirectedGraph {
+    vertices: Vec<VertexId>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl UndirectedGraph {
+    fn new<G: GraphView>(graph: &G) -> UndirectedGraph {
+        let mut vertices: Vec<VertexId> = graph.vertices().collect();
+        vertices.sort();
+        let index: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut neighbours: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); vertices.len()];
+        for Edge(src, dst) in graph.edges().filter(|Edge(src, dst)| src != dst) {
+            neighbours[index[&src]].insert(index[&dst]);
+            neighbours[index[&dst]].insert(index[&src]);
+        }
+        UndirectedGraph {
+            vertices,
+            neighbours: neighbours
+                .into_iter()
+                .map(|n| n.into_iter().collect())
+                .collect(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn by_decreasing_degree(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|v| (std::cmp::Reverse(self.neighbours[*v].len()), *v));
+        order
+    }
+
+    fn smallest_available_color(&self, vertex: usize, colors: &[Option<usize>]) -> usize {
+        (0..)
+            .find(|c| {
+                self.neighbours[vertex]
+                    .iter()
+                    .all(|n| colors[*n] != Some(*c))
+            })
+            .unwrap()
+    }
+
+    // Backtracking on the vertices by decreasing degree. A vertex can only take a color already
+    // used or the next new one, so that permutations of colors are not searched again
+    fn k_coloring(&self, max_colors: usize) -> Option<Vec<Option<usize>>> {
+        let order = self.by_decreasing_degree();
+        let mut colors: Vec<Option<usize>> = vec![None; self.len()];
+        // Next color to try, and number of colors used before each position
+        let mut next = vec![0; self.len()];
+        let mut used = vec![0; self.len() + 1];
+        let mut position = 0;
+        while position < self.len() {
+            let vertex = order[position];
+            colors[vertex] = None;
+            let limit = max_colors.min(used[position] + 1);
+            let color = (next[position]..limit).find(|c| {
+                self.neighbours[vertex]
+                    .iter()
+                    .all(|n| colors[*n] != Some(*c))
+            });
+            match color {
+                Some(color) => {
+                    colors[vertex] = Some(color);
+                    next[position] = color + 1;
+                    used[position + 1] = used[position].max(color + 1);
+                    position += 1;
+                    if position < self.len() {
+                        next[position] = 0;
+                    }
+                }
+                None if position == 0 => return None,
+                None => position -= 1,
+            }
+        }
+        Some(colors)
+    }
+
+    fn mapping(&self, colors: &[Option<usize>]) -> VertexAttrMapping<usize> {
+        let mut res = AttributeMapping::<VertexId, usize>::new(COLOR_ATTRIBUTE);
+        for (vertex, color) in self.vertices.iter().zip(colors.iter()) {
+            res.add(*vertex, color.expect("All the vertices are colored"));
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn greedy_colorings_should_be_proper() {
+        // Pseudo random graphs
+        let mut seed: u64 = 3;
+        for _ in 0..20 {
+            let mut g = DirectedGraph::new();
+            for src in 0..12 {
+                g.add_vertex(VertexId(src));
+                for dst in 0..12 {
+                    seed = (seed * 1103 + 12345) % 1009;
+                    if seed.is_multiple_of(5) {
+                        g.add_edge(edge(src, dst));
+                    }
+                }
+            }
+            let exact = color_count(&min_coloring(&g).unwrap());
+            for colors in [dsatur(&g), welsh_powell(&g), min_coloring(&g).unwrap()] {
+                assert!(is_proper_coloring(&g, &colors));
+                assert!(color_count(&colors) >= exact);
+            }
+            assert!(k_coloring(&g, exact).is_some());
+            assert!(exact == 0 || k_coloring(&g, exact - 1).is_none());
+        }
+    }
+
+    #[test]
+    fn min_coloring_of_an_odd_cycle_needs_three_colors() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 4), (4, 5), (5, 1)]);
+        assert_eq!(color_count(&min_coloring(&g).unwrap()), 3);
+        assert!(k_coloring(&g, 2).is_none());
+    }
+
+    #[test]
+    fn dsatur_should_color_a_bipartite_graph_with_two_colors() {
+        // Crown graph: i -> j + 10 for all i != j, where greedy colorings in id order use 4 colors
+        let mut g = DirectedGraph::new();
+        for i in 1..=4 {
+            for j in 1..=4 {
+                if i != j {
+                    g.add_edge(edge(i, j + 10));
+                }
+            }
+        }
+        let colors = dsatur(&g);
+        assert_eq!(color_count(&colors), 2);
+        assert_eq!(
+            color_classes(&colors),
+            vec![vertices(vec![1, 2, 3, 4]), vertices(vec![11, 12, 13, 14])]
+        );
+        assert_eq!(colors.name(), COLOR_ATTRIBUTE);
+    }
+
+    #[test]
+    fn colorings_should_ignore_direction_and_self_loops() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1), (1, 4), (4, 1), (4, 4)]);
+        let colors = min_coloring(&g).unwrap();
+        assert_eq!(color_count(&colors), 3);
+        assert!(is_proper_coloring(&g, &colors));
+        assert_eq!(color_count(&welsh_powell(&DirectedGraph::new())), 0);
+    }
+
+    // Helpers
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
@@ -0,0 +1,425 @@
+//! Community detection: groups of vertices densely connected to each other
+//! Edges are treated as undirected, and the weights of u -> v and v -> u add up.
+//! Weights are expected to be positive.
+use crate::graph::{Edge, GraphView, VertexId};
+use std::collections::HashMap;
+
+/// Community of each vertex, numbered from 0 in the order of their smallest vertex
+pub type Communities = HashMap<VertexId, usize>;
+
+/// Maximum number of rounds of label propagation
+pub const LABEL_PROPAGATION_MAX_ITERATIONS: usize = 100;
+
+/// Maximum number of passes over the vertices when moving them between communities
+pub const LOUVAIN_MAX_PASSES: usize = 100;
+
+// Smallest modularity gain of a move, so that rounding errors don't swap vertices endlessly
+const LOUVAIN_MIN_GAIN: f64 = 1e-12;
+
+/// Label propagation: each vertex takes the label with the biggest weight among its neighbours,
+/// until labels don't change anymore
+/// Vertices are visited in a shuffled order and ties are broken arbitrarily, unless the current
+/// label is one of them. A fixed seed makes the result reproducible.
+/// See Raghavan, Albert and Kumara, "Near linear time algorithm to detect community structures
+/// in large-scale networks" (2007)
+pub fn label_propagation<G, WFn>(graph: &G, weight: WFn) -> Communities
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> f64,
+{
+    let undirected = UndirectedGraph::new(graph, weight);
+    let mut labels: Vec<usize> = (0..undirected.len()).collect();
+    let mut order: Vec<usize> = (0..undirected.len()).collect();
+    let mut random = Xorshift::new();
+    for _ in 0..LABEL_PROPAGATION_MAX_ITERATIONS {
+        random.shuffle(&mut order);
+        let mut changed = false;
+        for vertex in order.iter().copied() {
+            let mut weights: HashMap<usize, f64> = HashMap::new();
+            for (next, w) in &undirected.neighbours[vertex] {
+                if *next != vertex {
+                    *weights.entry(labels[*next]).or_insert(0.0) += w;
+                }
+            }
+            let best = match weights.values().cloned().fold(None, max_weight) {
+                Some(best) => best,
+                None => continue,
+            };
+            if weights.get(&labels[vertex]) != Some(&best) {
+                let mut candidates: Vec<usize> = weights
+                    .iter()
+                    .filter(|(_, w)| **w == best)
+                    .map(|(label, _)| *label)
+                    .collect();
+                candidates.sort();
+                labels[vertex] = candidates[random.below(candidates.len())];
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    undirected.communities(&labels)
+}
+
+/// Louvain modularity optimisation
+/// Vertices are moved to the neighbouring community increasing the modularity the most,
+/// then each community is merged into a single vertex, until the modularity stops increasing.
+/// See Blondel, Guillaume, Lambiotte and Lefebvre, "Fast unfolding of communities in large
+/// networks" (2008)
+pub fn louvain<G, WFn>(graph: &G, weight: WFn) -> Communities
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> f64,
+{
+    let undirected = UndirectedGraph::new(graph, weight);
+    // Community of each vertex of the original graph
+    let mut labels: Vec<usize> = (0..undirected.len()).collect();
+    let mut current = undirected.clone();
+    loop {
+        let (moved, level) = current.local_moves();
+        if !moved {
+            break;
+        }
+        let (level, count) = renumber(&level);
+        for label in labels.iter_mut() {
+            *label = level[*label];
+        }
+        current = current.aggregate(&level, count);
+    }
+    undirected.communities(&labels)
+}
+
+/// Modularity of a partition of the graph into communities, between -1/2 and 1
+/// See https://en.wikipedia.org/wiki/Modularity_(networks)
+pub fn modularity<G, WFn>(graph: &G, weight: WFn, communities: &Communities) -> f64
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> f64,
+{
+    let undirected = UndirectedGraph::new(graph, weight);
+    let labels: Vec<usize> = undirected.vertices.iter().map(|v| communities[v]).collect();
+    undirected.modularity(&labels)
+}
+
+/// Groups the vertices by community, each group being sorted
+pub fn members(communities: &Communities) -> Vec<Vec<VertexId>> {
+    let mut res: Vec<Vec<VertexId>> = vec![vec![]; communities.values().max().map_or(0, |c| c + 1)];
+    for (vertex, community) in communities {
+        res[*community].push(*vertex);
+    }
+    for group in res.iter_mut() {
+        group.sort();
+    }
+    res
+}
+
+// Undirected graph with indexed vertices. A self loop is only stored once,
+// but counts twice in the degree of its vertex
+#[derive(Clone)]
+struct UndirectedGraph {
+    vertices: Vec<VertexId>,
+    neighbours: Vec<Vec<(usize, f64)>>,
+    degrees: Vec<f64>,
+    // Twice the total weight of the edges
+    total: f64,
+}
+
+impl UndirectedGraph {
+    fn new<G, WFn>(graph: &G, weight: WFn) -> UndirectedGraph
+    where
+        G: GraphView,
+        WFn: Fn(&Edge) -> f64,
+    {
+        let mut vertices: Vec<VertexId> = graph.vertices().collect();
+        vertices.sort();
+        let index: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); vertices.len()];
+        for edge in graph.edges() {
+            let (src, dst) = (index[&edge.0], index[&edge.1]);
+            let w = weight(&edge);
+            *weights[src].entry(dst).or_insert(0.0) += w;
+            if src != dst {
+                *weights[dst].entry(src).or_insert(0.0) += w;
+            }
+        }
+        UndirectedGraph::from_weights(vertices, weights)
+    }
+
+    fn from_weights(vertices: Vec<VertexId>, weights: Vec<HashMap<usize, f64>>) -> UndirectedGraph {
+        let neighbours: Vec<Vec<(usize, f64)>> = weights
+            .into_iter()
+            .map(|w| {
+                let mut n: Vec<(usize, f64)> = w.into_iter().collect();
+                n.sort_by_key(|(v, _)| *v);
+                n
+            })
+            .collect();
+        let degrees: Vec<f64> = neighbours
+            .iter()
+            .enumerate()
+            .map(|(vertex, n)| {
+                n.iter()
+                    .map(|(next, w)| if *next == vertex { 2.0 * w } else { *w })
+                    .sum()
+            })
+            .collect();
+        let total = degrees.iter().sum();
+        UndirectedGraph {
+            vertices,
+            neighbours,
+            degrees,
+            total,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    // Moves vertices between communities while the modularity increases, for at most
+    // LOUVAIN_MAX_PASSES passes
+    // Returns whether a vertex moved and the community of each vertex
+    fn local_moves(&self) -> (bool, Vec<usize>) {
+        let mut community: Vec<usize> = (0..self.len()).collect();
+        let mut totals: Vec<f64> = self.degrees.clone();
+        let mut moved = false;
+        if self.total == 0.0 {
+            return (moved, community);
+        }
+        for _ in 0..LOUVAIN_MAX_PASSES {
+            let mut improved = false;
+            for vertex in 0..self.len() {
+                let current = community[vertex];
+                let degree = self.degrees[vertex];
+                totals[current] -= degree;
+                let mut links: HashMap<usize, f64> = HashMap::new();
+                links.insert(current, 0.0);
+                for (next, w) in &self.neighbours[vertex] {
+                    if *next != vertex {
+                        *links.entry(community[*next]).or_insert(0.0) += w;
+                    }
+                }
+                // Modularity gain of adding the vertex to a community, up to a constant factor
+                let gain = |c: &usize| links[c] - totals[*c] * degree / self.total;
+                let best_gain = links.keys().map(gain).fold(None, max_weight).unwrap();
+                // Gains are scaled by the total weight of the graph
+                let best = if best_gain - gain(&current) <= LOUVAIN_MIN_GAIN * self.total {
+                    current
+                } else {
+                    *links.keys().filter(|c| gain(c) == best_gain).min().unwrap()
+                };
+                totals[best] += degree;
+                if best != current {
+                    community[vertex] = best;
+                    improved = true;
+                    moved = true;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+        (moved, community)
+    }
+
+    // Graph where each community is merged into a single vertex
+    fn aggregate(&self, community: &[usize], count: usize) -> UndirectedGraph {
+        let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        for (vertex, neighbours) in self.neighbours.iter().enumerate() {
+            for (next, w) in neighbours {
+                let (src, dst) = (community[vertex], community[*next]);
+                // Edges inside a community are seen from both ends, except self loops
+                let w = if src == dst && vertex != *next {
+                    w / 2.0
+                } else {
+                    *w
+                };
+                *weights[src].entry(dst).or_insert(0.0) += w;
+            }
+        }
+        UndirectedGraph::from_weights(vec![], weights)
+    }
+
+    fn modularity(&self, community: &[usize]) -> f64 {
+        if self.total == 0.0 {
+            return 0.0;
+        }
+        let mut inside = 0.0;
+        let mut totals: HashMap<usize, f64> = HashMap::new();
+        for (vertex, neighbours) in self.neighbours.iter().enumerate() {
+            *totals.entry(community[vertex]).or_insert(0.0) += self.degrees[vertex];
+            for (next, w) in neighbours {
+                if community[vertex] == community[*next] {
+                    inside += if vertex == *next { 2.0 * w } else { *w };
+                }
+            }
+        }
+        inside / self.total
+            - totals
+                .values()
+                .map(|t| (t / self.total) * (t / self.total))
+                .sum::<f64>()
+    }
+
+    fn communities(&self, labels: &[usize]) -> Communities {
+        let (labels, _) = renumber(labels);
+        self.vertices.iter().copied().zip(labels).collect()
+    }
+}
+
+// Numbers the labels from 0 in the order they first appear
+// Returns the new labels, and the number of distinct labels
+fn renumber(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut numbers: HashMap<usize, usize> = HashMap::new();
+    let res = labels
+        .iter()
+        .map(|label| {
+            let count = numbers.len();
+            *numbers.entry(*label).or_insert(count)
+        })
+        .collect();
+    (res, numbers.len())
+}
+
+// Xorshift pseudo random generator, with a fixed seed
+struct Xorshift {
+    state: u64,
+}
+
+impl Xorshift {
+    fn new() -> Xorshift {
+        Xorshift {
+            state: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    // Fisher-Yates shuffle
+    fn shuffle(&mut self, values: &mut [usize]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn max_weight(best: Option<f64>, w: f64) -> Option<f64> {
+    match best {
+        Some(b) if b >= w => Some(b),
+        _ => Some(w),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn louvain_should_split_two_triangles_linked_by_an_edge() {
+        let g = build_two_triangles();
+        let communities = louvain(&g, unit_weight);
+        assert_eq!(
+            members(&communities),
+            vec![vertices(vec![1, 2, 3]), vertices(vec![4, 5, 6])]
+        );
+        // 2 * (3 / 7 - (7 / 14)^2)
+        let q = modularity(&g, unit_weight, &communities);
+        assert!((q - 5.0 / 14.0).abs() < 1e-9, "modularity was {}", q);
+    }
+
+    #[test]
+    fn louvain_should_not_depend_on_the_scale_of_the_weights() {
+        let g = build_two_triangles();
+        for scale in [1e-20, 1e20] {
+            let communities = louvain(&g, |e| scale * unit_weight(e));
+            assert_eq!(
+                members(&communities),
+                vec![vertices(vec![1, 2, 3]), vertices(vec![4, 5, 6])]
+            );
+        }
+    }
+
+    #[test]
+    fn label_propagation_should_split_two_triangles_linked_by_an_edge() {
+        let g = build_two_triangles();
+        let communities = label_propagation(&g, unit_weight);
+        assert_eq!(
+            members(&communities),
+            vec![vertices(vec![1, 2, 3]), vertices(vec![4, 5, 6])]
+        );
+    }
+
+    #[test]
+    fn louvain_should_follow_the_heaviest_edges() {
+        // Ring 1 - 2 - 3 - 4 - 1 where 2 - 3 and 4 - 1 are heavy
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        g.add_edge(edge(3, 4));
+        g.add_edge(edge(4, 1));
+        let weight = |e: &Edge| {
+            if e.0 == VertexId(2) || e.0 == VertexId(4) {
+                10.0
+            } else {
+                1.0
+            }
+        };
+        let communities = louvain(&g, weight);
+        assert_eq!(
+            members(&communities),
+            vec![vertices(vec![1, 4]), vertices(vec![2, 3])]
+        );
+        assert!(
+            modularity(&g, weight, &communities)
+                > modularity(&g, weight, &louvain(&g, unit_weight))
+        );
+    }
+
+    #[test]
+    fn isolated_vertices_should_be_their_own_community() {
+        let mut g = DirectedGraph::new();
+        g.add_vertex(VertexId(1));
+        g.add_vertex(VertexId(2));
+        let communities = louvain(&g, unit_weight);
+        assert_eq!(communities[&VertexId(1)], 0);
+        assert_eq!(communities[&VertexId(2)], 1);
+        assert_eq!(label_propagation(&g, unit_weight), communities);
+        assert_eq!(modularity(&g, unit_weight, &communities), 0.0);
+    }
+
+    // Helpers
+
+    // Triangles 1 - 2 - 3 and 4 - 5 - 6, linked by 3 - 4
+    fn build_two_triangles() -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in [(1, 2), (2, 3), (3, 1), (4, 5), (5, 6), (6, 4), (3, 4)] {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn unit_weight(_: &Edge) -> f64 {
+        1.0
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
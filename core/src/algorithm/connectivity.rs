@@ -0,0 +1,388 @@
+//! Connected components, articulation points and bridges
+use crate::algorithm::reachability;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use std::collections::{HashMap, HashSet};
+
+/// Weakly connected components: components of the underlying undirected graph
+/// Each component is sorted, and components are sorted by their smallest vertex
+pub fn weakly_connected_components<G: GraphView>(graph: &G) -> Vec<Vec<VertexId>> {
+    let vertices = sorted_vertices(graph);
+    let indices = indices_of(&vertices);
+    let mut sets = UnionFind::new(vertices.len());
+    for Edge(src, dst) in graph.edges() {
+        sets.union(indices[&src], indices[&dst]);
+    }
+    let mut components: Vec<Vec<VertexId>> = vec![];
+    let mut component_of_root: HashMap<usize, usize> = HashMap::new();
+    // Vertices are sorted, so components and their content end up sorted as well
+    for (index, vertex) in vertices.iter().enumerate() {
+        let root = sets.find(index);
+        let component = *component_of_root.entry(root).or_insert_with(|| {
+            components.push(vec![]);
+            components.len() - 1
+        });
+        components[component].push(*vertex);
+    }
+    components
+}
+
+/// Tests if the underlying undirected graph is connected. An empty graph is connected
+pub fn is_weakly_connected<G: GraphView>(graph: &G) -> bool {
+    weakly_connected_components(graph).len() <= 1
+}
+
+/// Tests if every vertex can be reached from every other vertex. An empty graph is connected
+pub fn is_strongly_connected<G: GraphView>(graph: &G) -> bool {
+    match graph.vertices().next() {
+        None => true,
+        Some(vertex) => {
+            let others = graph.vertex_count() - 1;
+            reachability::descendants(graph, vertex).len() >= others
+                && reachability::ancestors(graph, vertex).len() >= others
+        }
+    }
+}
+
+/// Strongly connected components, using Tarjan algorithm
+/// Each component is sorted. Components are returned in reverse topological order:
+/// edges between components go from a component to one listed before it
+/// See https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm
+pub fn strongly_connected_components<G: GraphView>(graph: &G) -> Vec<Vec<VertexId>> {
+    let vertices = sorted_vertices(graph);
+    let indices = indices_of(&vertices);
+    let adjacency: Vec<Vec<usize>> = vertices
+        .iter()
+        .map(|v| graph.outbound_edges(*v).map(|e| indices[&e.1]).collect())
+        .collect();
+    let mut order: Vec<Option<usize>> = vec![None; vertices.len()];
+    let mut low: Vec<usize> = vec![0; vertices.len()];
+    let mut on_stack = vec![false; vertices.len()];
+    let mut stack: Vec<usize> = vec![];
+    let mut components: Vec<Vec<VertexId>> = vec![];
+    let mut counter = 0;
+    for root in 0..vertices.len() {
+        if order[root].is_some() {
+            continue;
+        }
+        // Iterative depth-first search, holding the next adjacent vertex to visit
+        let mut calls: Vec<(usize, usize)> = vec![(root, 0)];
+        order[root] = Some(counter);
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((vertex, next)) = calls.last_mut() {
+            let vertex = *vertex;
+            if let Some(w) = adjacency[vertex].get(*next).copied() {
+                *next += 1;
+                match order[w] {
+                    None => {
+                        order[w] = Some(counter);
+                        low[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        calls.push((w, 0));
+                    }
+                    Some(w_order) if on_stack[w] => low[vertex] = low[vertex].min(w_order),
+                    Some(_) => {}
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some((parent, _)) = calls.last() {
+                low[*parent] = low[*parent].min(low[vertex]);
+            }
+            if Some(low[vertex]) == order[vertex] {
+                let mut component = vec![];
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(vertices[w]);
+                    if w == vertex {
+                        break;
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// Vertices whose removal disconnects their component of the underlying undirected graph
+/// Returned vertices are sorted
+pub fn articulation_points<G: GraphView>(graph: &G) -> Vec<VertexId> {
+    let (mut points, _) = cut_vertices_and_edges(graph);
+    points.sort();
+    points
+}
+
+/// Edges whose removal disconnects their component of the underlying undirected graph
+/// When a bridge links 2 vertices in both directions, both edges are returned.
+/// Returned edges are sorted
+pub fn bridges<G: GraphView>(graph: &G) -> Vec<Edge> {
+    let (_, links) = cut_vertices_and_edges(graph);
+    let mut bridges: Vec<Edge> = graph
+        .edges()
+        .filter(|Edge(src, dst)| links.contains(&(*src.min(dst), *src.max(dst))))
+        .collect();
+    bridges.sort();
+    bridges
+}
+
+// Depth-first search on the underlying undirected graph, computing the lowest discovery time
+// reachable from each subtree through a back edge
+fn cut_vertices_and_edges<G: GraphView>(
+    graph: &G,
+) -> (Vec<VertexId>, HashSet<(VertexId, VertexId)>) {
+    let vertices = sorted_vertices(graph);
+    let indices = indices_of(&vertices);
+    let adjacency: Vec<Vec<usize>> = vertices
+        .iter()
+        .enumerate()
+        .map(|(index, v)| {
+            let outbound = graph.outbound_edges(*v).map(|e| indices[&e.1]);
+            let inbound = graph.inbound_edges(*v).map(|e| indices[&e.0]);
+            let mut neighbours: Vec<usize> =
+                outbound.chain(inbound).filter(|w| *w != index).collect();
+            neighbours.sort_unstable();
+            neighbours.dedup();
+            neighbours
+        })
+        .collect();
+    let mut discovery: Vec<Option<usize>> = vec![None; vertices.len()];
+    let mut low: Vec<usize> = vec![0; vertices.len()];
+    let mut points: HashSet<usize> = HashSet::new();
+    let mut links: HashSet<(VertexId, VertexId)> = HashSet::new();
+    let mut counter = 0;
+    for root in 0..vertices.len() {
+        if discovery[root].is_some() {
+            continue;
+        }
+        discovery[root] = Some(counter);
+        low[root] = counter;
+        counter += 1;
+        let mut root_children = 0;
+        let mut calls: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+        while let Some((vertex, parent, next)) = calls.last_mut() {
+            let (vertex, parent) = (*vertex, *parent);
+            if let Some(w) = adjacency[vertex].get(*next).copied() {
+                *next += 1;
+                if Some(w) == parent {
+                    continue;
+                }
+                match discovery[w] {
+                    Some(w_discovery) => low[vertex] = low[vertex].min(w_discovery),
+                    None => {
+                        discovery[w] = Some(counter);
+                        low[w] = counter;
+                        counter += 1;
+                        if vertex == root {
+                            root_children += 1;
+                        }
+                        calls.push((w, Some(vertex), 0));
+                    }
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some(parent) = parent {
+                low[parent] = low[parent].min(low[vertex]);
+                let parent_discovery = discovery[parent].expect("A parent is discovered");
+                if low[vertex] > parent_discovery {
+                    let (a, b) = (vertices[parent], vertices[vertex]);
+                    links.insert((a.min(b), a.max(b)));
+                }
+                if parent != root && low[vertex] >= parent_discovery {
+                    points.insert(parent);
+                }
+            }
+        }
+        if root_children > 1 {
+            points.insert(root);
+        }
+    }
+    (points.into_iter().map(|i| vertices[i]).collect(), links)
+}
+
+fn sorted_vertices<G: GraphView>(graph: &G) -> Vec<VertexId> {
+    let mut vertices: Vec<VertexId> = graph.vertices().collect();
+    vertices.sort();
+    vertices
+}
+
+fn indices_of(vertices: &[VertexId]) -> HashMap<VertexId, usize> {
+    vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect()
+}
+
+/// Disjoint sets of indices, with path compression and union by rank
+/// See https://en.wikipedia.org/wiki/Disjoint-set_data_structure
+pub(crate) struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+}
+
+impl UnionFind {
+    pub(crate) fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..size).collect(),
+            ranks: vec![0; size],
+        }
+    }
+
+    /// Representative of the set containing the index
+    pub(crate) fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = index;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the sets of both indices. Returns false if they were already in the same set
+    pub(crate) fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        match self.ranks[a].cmp(&self.ranks[b]) {
+            std::cmp::Ordering::Less => self.parents[a] = b,
+            std::cmp::Ordering::Greater => self.parents[b] = a,
+            std::cmp::Ordering::Equal => {
+                self.parents[b] = a;
+                self.ranks[a] += 1;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn weakly_connected_components_should_ignore_the_direction_of_edges() {
+        let mut g = graph_of(vec![(1, 2), (3, 2), (4, 5), (6, 5)]);
+        g.add_vertex(VertexId(7));
+        assert_eq![
+            weakly_connected_components(&g),
+            vec![
+                vertices(vec![1, 2, 3]),
+                vertices(vec![4, 5, 6]),
+                vertices(vec![7])
+            ]
+        ];
+        assert![!is_weakly_connected(&g)];
+        assert![is_weakly_connected(&graph_of(vec![(1, 2), (3, 2)]))];
+        assert![is_weakly_connected(&DirectedGraph::new())];
+    }
+
+    #[test]
+    fn is_strongly_connected_should_require_paths_in_both_directions() {
+        assert![is_strongly_connected(&graph_of(vec![
+            (1, 2),
+            (2, 3),
+            (3, 1)
+        ]))];
+        assert![!is_strongly_connected(&graph_of(vec![
+            (1, 2),
+            (2, 3),
+            (3, 2)
+        ]))];
+        assert![is_strongly_connected(&DirectedGraph::new())];
+    }
+
+    #[test]
+    fn strongly_connected_components_should_be_in_reverse_topological_order() {
+        // {1, 2, 3} -> {4, 5} -> {6}
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4), (5, 6)]);
+        assert_eq![
+            strongly_connected_components(&g),
+            vec![
+                vertices(vec![6]),
+                vertices(vec![4, 5]),
+                vertices(vec![1, 2, 3])
+            ]
+        ];
+    }
+
+    #[test]
+    fn articulation_points_should_find_the_vertices_linking_components() {
+        // Triangle 1-2-3, linked through 3 to 4, linked to a triangle 4-5-6, and 6 to 7
+        let g = build_test_graph();
+        assert_eq![articulation_points(&g), vertices(vec![3, 4, 6])];
+    }
+
+    #[test]
+    fn bridges_should_find_the_edges_linking_components() {
+        let mut g = build_test_graph();
+        g.add_edge(edge(4, 3));
+        assert_eq![bridges(&g), vec![edge(3, 4), edge(4, 3), edge(6, 7)]];
+        assert_eq![articulation_points(&g), vertices(vec![3, 4, 6])];
+    }
+
+    #[test]
+    fn articulation_points_should_handle_the_root_of_the_search() {
+        // 1 is the first vertex visited, and the center of a star
+        let g = graph_of(vec![(1, 2), (1, 3), (4, 1)]);
+        assert_eq![articulation_points(&g), vertices(vec![1])];
+        assert_eq![bridges(&g), vec![edge(1, 2), edge(1, 3), edge(4, 1)]];
+        assert_eq![
+            articulation_points(&graph_of(vec![(1, 2), (2, 3), (3, 1)])),
+            vec![]
+        ];
+    }
+
+    #[test]
+    fn union_find_should_merge_sets() {
+        let mut sets = UnionFind::new(4);
+        assert![sets.union(0, 1)];
+        assert![sets.union(2, 3)];
+        assert![!sets.union(1, 0)];
+        assert_ne![sets.find(0), sets.find(2)];
+        assert![sets.union(1, 3)];
+        assert_eq![sets.find(0), sets.find(2)];
+    }
+
+    // Helpers
+
+    fn build_test_graph() -> DirectedGraph {
+        graph_of(vec![
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 4),
+            (6, 7),
+        ])
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
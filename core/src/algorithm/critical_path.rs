@@ -0,0 +1,244 @@
+//! Critical path method (CPM) to schedule the tasks of a project
+//! Vertices are tasks with a duration, and an edge u -> v means v can only start once u is done.
+use crate::algorithm::longest_path;
+use crate::algorithm::topo_sort;
+use crate::algorithm::topo_sort::DAG;
+use crate::directed_graph::DirectedGraph;
+use crate::graph::{Edge, GraphView, VertexId};
+use std::collections::HashMap;
+
+pub type Duration = i64;
+
+/// Time window of a task in a schedule
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Task {
+    pub earliest_start: Duration,
+    pub earliest_finish: Duration,
+    pub latest_start: Duration,
+    pub latest_finish: Duration,
+}
+
+impl Task {
+    /// Delay the task can take without delaying the whole project
+    pub fn slack(&self) -> Duration {
+        self.latest_start - self.earliest_start
+    }
+
+    /// A critical task delays the whole project when it is delayed
+    pub fn is_critical(&self) -> bool {
+        self.slack() == 0
+    }
+}
+
+/// Schedule of all the tasks of a project, starting at time 0
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Schedule {
+    pub makespan: Duration,
+    pub tasks: HashMap<VertexId, Task>,
+    pub critical_path: Vec<VertexId>,
+}
+
+/// Computes the earliest and latest times of each task and a critical path
+/// The critical path is the longest path of the DAG, where the length of a path
+/// is the sum of the durations of its vertices. Durations are expected to be non negative.
+/// When several paths are critical, the smallest vertices are preferred.
+/// Returns None if the durations overflow
+pub fn schedule<G, DFn>(dag: &DAG<G>, duration: DFn) -> Option<Schedule>
+where
+    G: GraphView,
+    DFn: Fn(&VertexId) -> Duration,
+{
+    let graph = dag.as_graph();
+    let order =
+        topo_sort::lexicographic_topological_sort(graph).expect("A DAG has a topological order");
+
+    // Forward pass: a task starts when all its predecessors are finished
+    let earliest_start = earliest_starts(graph, &duration)?;
+    let makespan = order.iter().try_fold(0, |makespan: Duration, v| {
+        earliest_start[v]
+            .checked_add(duration(v))
+            .map(|finish| makespan.max(finish))
+    })?;
+
+    // Backward pass: a task finishes before all its successors must start
+    let mut latest_start: HashMap<VertexId, Duration> = HashMap::new();
+    for vertex in order.iter().rev() {
+        let finish = graph
+            .outbound_edges(*vertex)
+            .map(|Edge(_, dst)| latest_start[&dst])
+            .min()
+            .unwrap_or(makespan);
+        latest_start.insert(*vertex, finish.checked_sub(duration(vertex))?);
+    }
+
+    let tasks: HashMap<VertexId, Task> = order
+        .iter()
+        .map(|v| {
+            let task = Task {
+                earliest_start: earliest_start[v],
+                earliest_finish: earliest_start[v].checked_add(duration(v))?,
+                latest_start: latest_start[v],
+                latest_finish: latest_start[v].checked_add(duration(v))?,
+            };
+            Some((*v, task))
+        })
+        .collect::<Option<_>>()?;
+    let critical_path = critical_path(dag, &tasks, makespan);
+    Some(Schedule {
+        makespan,
+        tasks,
+        critical_path,
+    })
+}
+
+// The earliest start of a task is the longest path to it from a virtual vertex preceding the
+// tasks without predecessors, where the score of an edge is the duration of its source
+fn earliest_starts<G, DFn>(graph: &G, duration: &DFn) -> Option<HashMap<VertexId, Duration>>
+where
+    G: GraphView,
+    DFn: Fn(&VertexId) -> Duration,
+{
+    let start = (0..)
+        .map(VertexId)
+        .find(|v| !graph.contains_vertex(*v))
+        .expect("A graph can't use every vertex id");
+    let mut project = DirectedGraph::new();
+    project.add_vertex(start);
+    for vertex in graph.vertices() {
+        project.add_vertex(vertex);
+        if graph.degree_in(vertex) == 0 {
+            project.add_edge(Edge(start, vertex));
+        }
+    }
+    for edge in graph.edges() {
+        project.add_edge(edge);
+    }
+    let dag = topo_sort::try_dag(&project).expect("Adding a source vertex keeps the graph acyclic");
+    let scorefn = |Edge(src, _): &Edge| if *src == start { 0 } else { duration(src) };
+    let paths = longest_path::dag_longest_paths(dag, scorefn, start).ok()?;
+    Some(graph.vertices().map(|v| (v, paths[&v].score)).collect())
+}
+
+// Walks back from a critical task finishing last, through critical predecessors
+// finishing exactly when the current task starts
+fn critical_path<G: GraphView>(
+    dag: &DAG<G>,
+    tasks: &HashMap<VertexId, Task>,
+    makespan: Duration,
+) -> Vec<VertexId> {
+    let graph = dag.as_graph();
+    let mut current = tasks
+        .iter()
+        .filter(|(_, t)| t.is_critical() && t.earliest_finish == makespan)
+        .map(|(v, _)| *v)
+        .min();
+    let mut res = vec![];
+    while let Some(vertex) = current {
+        res.push(vertex);
+        let start = tasks[&vertex].earliest_start;
+        current = graph
+            .inbound_edges(vertex)
+            .map(|Edge(src, _)| src)
+            .filter(|src| tasks[src].is_critical() && tasks[src].earliest_finish == start)
+            .min();
+    }
+    res.reverse();
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn schedule_should_find_the_critical_path_and_slacks() {
+        let (g, durationfn) = build_test_project();
+        let dag = topo_sort::try_dag(&g).unwrap();
+        let schedule = schedule(&dag, durationfn).unwrap();
+
+        assert_eq!(schedule.makespan, 11);
+        assert_eq!(schedule.critical_path, vertices(vec![1, 2, 4, 6]));
+        assert_eq!(
+            schedule.tasks[&VertexId(3)],
+            Task {
+                earliest_start: 3,
+                earliest_finish: 5,
+                latest_start: 5,
+                latest_finish: 7,
+            }
+        );
+        assert_eq!(schedule.tasks[&VertexId(3)].slack(), 2);
+        assert_eq!(schedule.tasks[&VertexId(5)].slack(), 4);
+        assert!(schedule.tasks[&VertexId(4)].is_critical());
+    }
+
+    #[test]
+    fn schedule_should_run_independent_tasks_in_parallel() {
+        let mut g = DirectedGraph::new();
+        g.add_vertex(VertexId(1));
+        g.add_vertex(VertexId(2));
+        let dag = topo_sort::try_dag(&g).unwrap();
+        let schedule = schedule(&dag, |v: &VertexId| v.0 as Duration * 10).unwrap();
+
+        assert_eq!(schedule.makespan, 20);
+        assert_eq!(schedule.critical_path, vertices(vec![2]));
+        assert_eq!(schedule.tasks[&VertexId(1)].slack(), 10);
+    }
+
+    #[test]
+    fn schedule_of_an_empty_graph_is_empty() {
+        let g = DirectedGraph::new();
+        let dag = topo_sort::try_dag(&g).unwrap();
+        let schedule = schedule(&dag, |_: &VertexId| 1).unwrap();
+
+        assert_eq!(schedule.makespan, 0);
+        assert!(schedule.critical_path.is_empty());
+        assert!(schedule.tasks.is_empty());
+    }
+
+    #[test]
+    fn schedule_should_be_none_when_the_durations_overflow() {
+        let (g, _) = build_test_project();
+        let dag = topo_sort::try_dag(&g).unwrap();
+        assert_eq!(schedule(&dag, |_: &VertexId| Duration::MAX / 2), None);
+    }
+
+    #[test]
+    fn schedule_should_be_none_when_the_latest_times_overflow() {
+        let mut g = DirectedGraph::new();
+        g.add_vertex(VertexId(1));
+        let dag = topo_sort::try_dag(&g).unwrap();
+        assert_eq!(schedule(&dag, |_: &VertexId| Duration::MIN), None);
+    }
+
+    // Helpers
+
+    // 1 (3) -> 2 (4) -> 4 (3) -> 6 (1)
+    // 1 (3) -> 3 (2) -> 5 (1) -> 6 (1)
+    // 3 (2) -> 4 (3)
+    fn build_test_project() -> (DirectedGraph, impl Fn(&VertexId) -> Duration) {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(1, 3));
+        g.add_edge(edge(2, 4));
+        g.add_edge(edge(3, 4));
+        g.add_edge(edge(3, 5));
+        g.add_edge(edge(4, 6));
+        g.add_edge(edge(5, 6));
+        let durations: HashMap<VertexId, Duration> =
+            vec![(1, 3), (2, 4), (3, 2), (4, 3), (5, 1), (6, 1)]
+                .into_iter()
+                .map(|(v, d)| (VertexId(v), d))
+                .collect();
+        (g, move |v: &VertexId| durations[v])
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
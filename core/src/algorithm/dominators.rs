@@ -0,0 +1,371 @@
+//! Dominator trees and dominance frontiers
+use crate::directed_graph::DirectedGraph;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use std::collections::{HashMap, HashSet};
+
+/// Dominator tree of the vertices reachable from a root
+/// A vertex d dominates a vertex v if every path from the root to v goes through d.
+/// For a post-dominator tree, paths are followed backward: d post-dominates v if every path
+/// from v to the root (ie the exit) goes through d
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DominatorTree {
+    root: VertexId,
+    post: bool,
+    idoms: HashMap<VertexId, VertexId>,
+}
+
+impl DominatorTree {
+    pub fn root(&self) -> VertexId {
+        self.root
+    }
+
+    /// Tells if this is a post-dominator tree
+    pub fn is_post_dominator_tree(&self) -> bool {
+        self.post
+    }
+
+    /// Tests if the vertex is reachable from the root, and hence part of the tree
+    pub fn contains(&self, vertex: VertexId) -> bool {
+        vertex == self.root || self.idoms.contains_key(&vertex)
+    }
+
+    /// Closest strict dominator of a vertex, ie its parent in the tree
+    /// Returns None for the root and for vertices which are not in the tree
+    pub fn immediate_dominator(&self, vertex: VertexId) -> Option<VertexId> {
+        self.idoms.get(&vertex).copied()
+    }
+
+    /// All dominators of a vertex, from the vertex itself up to the root
+    /// Returns None for vertices which are not in the tree
+    pub fn dominators(&self, vertex: VertexId) -> Option<Vec<VertexId>> {
+        if !self.contains(vertex) {
+            return None;
+        }
+        let mut res = vec![vertex];
+        let mut current = vertex;
+        while let Some(idom) = self.immediate_dominator(current) {
+            res.push(idom);
+            current = idom;
+        }
+        Some(res)
+    }
+
+    /// Tests if a dominates b. Every vertex of the tree dominates itself
+    pub fn dominates(&self, a: VertexId, b: VertexId) -> bool {
+        self.dominators(b).is_some_and(|doms| doms.contains(&a))
+    }
+
+    /// Vertices immediately dominated by a vertex, sorted
+    pub fn children(&self, vertex: VertexId) -> Vec<VertexId> {
+        let mut res: Vec<VertexId> = self
+            .idoms
+            .iter()
+            .filter(|(_, idom)| **idom == vertex)
+            .map(|(v, _)| *v)
+            .collect();
+        res.sort();
+        res
+    }
+
+    /// Builds the tree as a graph, with an edge from each immediate dominator to its children
+    pub fn as_graph(&self) -> DirectedGraph {
+        let mut graph = DirectedGraph::new();
+        graph.add_vertex(self.root);
+        for (vertex, idom) in &self.idoms {
+            graph.add_edge(Edge(*idom, *vertex));
+        }
+        graph
+    }
+}
+
+/// Dominator tree of the vertices reachable from the root
+/// Returns None if the root is not in the graph
+/// Implementation of Cooper, Harvey and Kennedy iterative algorithm
+/// See "A Simple, Fast Dominance Algorithm" (2001)
+pub fn dominator_tree<G: GraphView>(graph: &G, root: VertexId) -> Option<DominatorTree> {
+    compute_tree(graph, root, false)
+}
+
+/// Post-dominator tree of the vertices that can reach the exit
+/// Returns None if the exit is not in the graph
+pub fn post_dominator_tree<G: GraphView>(graph: &G, exit: VertexId) -> Option<DominatorTree> {
+    compute_tree(graph, exit, true)
+}
+
+/// Dominance frontier of each vertex of the tree: the vertices where its dominance stops
+/// A vertex w is in the frontier of v if v dominates a predecessor of w, but does not
+/// strictly dominate w. For a post-dominator tree, the reverse graph is used
+pub fn dominance_frontiers<G: GraphView>(
+    graph: &G,
+    tree: &DominatorTree,
+) -> HashMap<VertexId, HashSet<VertexId>> {
+    let mut frontiers: HashMap<VertexId, HashSet<VertexId>> = HashMap::new();
+    frontiers.insert(tree.root, HashSet::new());
+    for vertex in tree.idoms.keys() {
+        frontiers.insert(*vertex, HashSet::new());
+    }
+    for (vertex, idom) in &tree.idoms {
+        add_to_frontiers(&mut frontiers, graph, tree, *vertex, Some(*idom));
+    }
+    add_to_frontiers(&mut frontiers, graph, tree, tree.root, None);
+    frontiers
+}
+
+// Walks up the tree from each predecessor of the vertex until its immediate dominator
+fn add_to_frontiers<G: GraphView>(
+    frontiers: &mut HashMap<VertexId, HashSet<VertexId>>,
+    graph: &G,
+    tree: &DominatorTree,
+    vertex: VertexId,
+    idom: Option<VertexId>,
+) {
+    let predecessors = predecessors(graph, vertex, tree.post).filter(|p| tree.contains(*p));
+    let predecessors: Vec<VertexId> = predecessors.collect();
+    if predecessors.len() < 2 && idom.is_some() {
+        return;
+    }
+    for predecessor in predecessors {
+        let mut runner = Some(predecessor);
+        while let Some(current) = runner {
+            if Some(current) == idom {
+                break;
+            }
+            frontiers
+                .get_mut(&current)
+                .expect("Vertices of the tree have a frontier")
+                .insert(vertex);
+            runner = tree.immediate_dominator(current);
+        }
+    }
+}
+
+fn compute_tree<G: GraphView>(graph: &G, root: VertexId, post: bool) -> Option<DominatorTree> {
+    if !graph.contains_vertex(root) {
+        return None;
+    }
+    let order = reverse_postorder(graph, root, post);
+    let postorder: HashMap<VertexId, usize> = order
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, v)| (*v, index))
+        .collect();
+    let mut idoms: HashMap<VertexId, VertexId> = HashMap::new();
+    idoms.insert(root, root);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for vertex in order.iter().skip(1) {
+            let mut new_idom: Option<VertexId> = None;
+            for predecessor in predecessors(graph, *vertex, post) {
+                if !idoms.contains_key(&predecessor) {
+                    continue;
+                }
+                new_idom = Some(match new_idom {
+                    None => predecessor,
+                    Some(current) => intersect(&idoms, &postorder, predecessor, current),
+                });
+            }
+            let new_idom = new_idom.expect("A reachable vertex has a processed predecessor");
+            if idoms.get(vertex) != Some(&new_idom) {
+                idoms.insert(*vertex, new_idom);
+                changed = true;
+            }
+        }
+    }
+    idoms.remove(&root);
+    Some(DominatorTree { root, post, idoms })
+}
+
+// Finds the closest common dominator by walking up the tree from both vertices
+fn intersect(
+    idoms: &HashMap<VertexId, VertexId>,
+    postorder: &HashMap<VertexId, usize>,
+    a: VertexId,
+    b: VertexId,
+) -> VertexId {
+    let (mut a, mut b) = (a, b);
+    while a != b {
+        while postorder[&a] < postorder[&b] {
+            a = idoms[&a];
+        }
+        while postorder[&b] < postorder[&a] {
+            b = idoms[&b];
+        }
+    }
+    a
+}
+
+// Iterative depth-first search, returning the vertices in reverse postorder
+fn reverse_postorder<G: GraphView>(graph: &G, root: VertexId, post: bool) -> Vec<VertexId> {
+    let mut order = vec![];
+    let mut visited: HashSet<VertexId> = HashSet::new();
+    let mut stack: Vec<(VertexId, Vec<VertexId>)> = vec![(root, successors(graph, root, post))];
+    visited.insert(root);
+    while let Some((vertex, pending)) = stack.last_mut() {
+        match pending.pop() {
+            Some(next) if visited.insert(next) => {
+                let next_successors = successors(graph, next, post);
+                stack.push((next, next_successors));
+            }
+            Some(_) => {}
+            None => {
+                order.push(*vertex);
+                stack.pop();
+            }
+        }
+    }
+    order.reverse();
+    order
+}
+
+// Successors in the graph, or in the reverse graph for post-dominators
+// Sorted in reverse order, so that they are visited in increasing order
+fn successors<G: GraphView>(graph: &G, vertex: VertexId, post: bool) -> Vec<VertexId> {
+    let mut res: Vec<VertexId> = if post {
+        graph
+            .inbound_edges(vertex)
+            .map(|Edge(src, _)| src)
+            .collect()
+    } else {
+        graph
+            .outbound_edges(vertex)
+            .map(|Edge(_, dst)| dst)
+            .collect()
+    };
+    res.sort_by(|a, b| b.cmp(a));
+    res
+}
+
+fn predecessors<'a, G: GraphView>(
+    graph: &'a G,
+    vertex: VertexId,
+    post: bool,
+) -> Box<dyn Iterator<Item = VertexId> + 'a> {
+    if post {
+        Box::new(graph.outbound_edges(vertex).map(|Edge(_, dst)| dst))
+    } else {
+        Box::new(graph.inbound_edges(vertex).map(|Edge(src, _)| src))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn dominator_tree_should_compute_immediate_dominators() {
+        let g = build_test_cfg();
+        let tree = dominator_tree(&g, VertexId(1)).unwrap();
+        assert_eq![tree.immediate_dominator(VertexId(1)), None];
+        assert_eq![tree.immediate_dominator(VertexId(2)), Some(VertexId(1))];
+        assert_eq![tree.immediate_dominator(VertexId(3)), Some(VertexId(2))];
+        assert_eq![tree.immediate_dominator(VertexId(4)), Some(VertexId(2))];
+        assert_eq![tree.immediate_dominator(VertexId(5)), Some(VertexId(2))];
+        assert_eq![tree.immediate_dominator(VertexId(6)), Some(VertexId(5))];
+        assert_eq![tree.children(VertexId(2)), vertices(vec![3, 4, 5])];
+    }
+
+    #[test]
+    fn dominator_tree_should_ignore_unreachable_vertices() {
+        let mut g = build_test_cfg();
+        g.add_edge(edge(8, 5));
+        let tree = dominator_tree(&g, VertexId(1)).unwrap();
+        assert![!tree.contains(VertexId(8))];
+        assert_eq![tree.immediate_dominator(VertexId(8)), None];
+        assert_eq![tree.immediate_dominator(VertexId(5)), Some(VertexId(2))];
+        assert_eq![tree.dominators(VertexId(8)), None];
+    }
+
+    #[test]
+    fn dominator_tree_should_be_none_when_the_root_is_not_in_the_graph() {
+        assert_eq![dominator_tree(&build_test_cfg(), VertexId(42)), None];
+    }
+
+    #[test]
+    fn dominators_should_list_the_dominators_up_to_the_root() {
+        let tree = dominator_tree(&build_test_cfg(), VertexId(1)).unwrap();
+        assert_eq![
+            tree.dominators(VertexId(6)),
+            Some(vertices(vec![6, 5, 2, 1]))
+        ];
+        assert![tree.dominates(VertexId(2), VertexId(6))];
+        assert![tree.dominates(VertexId(6), VertexId(6))];
+        assert![!tree.dominates(VertexId(3), VertexId(5))];
+    }
+
+    #[test]
+    fn post_dominator_tree_should_follow_edges_backward() {
+        let tree = post_dominator_tree(&build_test_cfg(), VertexId(6)).unwrap();
+        assert![tree.is_post_dominator_tree()];
+        assert_eq![tree.immediate_dominator(VertexId(5)), Some(VertexId(6))];
+        assert_eq![tree.immediate_dominator(VertexId(3)), Some(VertexId(5))];
+        assert_eq![tree.immediate_dominator(VertexId(4)), Some(VertexId(5))];
+        assert_eq![tree.immediate_dominator(VertexId(2)), Some(VertexId(5))];
+        assert_eq![tree.immediate_dominator(VertexId(1)), Some(VertexId(2))];
+    }
+
+    #[test]
+    fn dominance_frontiers_should_contain_the_join_points() {
+        let g = build_test_cfg();
+        let tree = dominator_tree(&g, VertexId(1)).unwrap();
+        let frontiers = dominance_frontiers(&g, &tree);
+        assert_eq![frontiers[&VertexId(1)], set(vec![])];
+        assert_eq![frontiers[&VertexId(2)], set(vec![2])];
+        assert_eq![frontiers[&VertexId(3)], set(vec![5])];
+        assert_eq![frontiers[&VertexId(4)], set(vec![5])];
+        assert_eq![frontiers[&VertexId(5)], set(vec![2])];
+        assert_eq![frontiers[&VertexId(6)], set(vec![])];
+    }
+
+    #[test]
+    fn post_dominance_frontiers_should_contain_the_branch_points() {
+        let g = build_test_cfg();
+        let tree = post_dominator_tree(&g, VertexId(6)).unwrap();
+        let frontiers = dominance_frontiers(&g, &tree);
+        assert_eq![frontiers[&VertexId(3)], set(vec![2])];
+        assert_eq![frontiers[&VertexId(4)], set(vec![2])];
+        assert_eq![frontiers[&VertexId(2)], set(vec![5])];
+        assert_eq![frontiers[&VertexId(5)], set(vec![5])];
+        assert_eq![frontiers[&VertexId(6)], set(vec![])];
+    }
+
+    #[test]
+    fn dominance_frontiers_should_contain_the_root_when_it_is_in_a_loop() {
+        let g = graph_of(vec![(1, 2), (2, 1)]);
+        let tree = dominator_tree(&g, VertexId(1)).unwrap();
+        let frontiers = dominance_frontiers(&g, &tree);
+        assert_eq![frontiers[&VertexId(1)], set(vec![1])];
+        assert_eq![frontiers[&VertexId(2)], set(vec![1])];
+    }
+
+    // Helpers
+
+    // 1 -> 2 -> {3, 4} -> 5 -> 6, with a loop back from 5 to 2
+    fn build_test_cfg() -> DirectedGraph {
+        graph_of(vec![(1, 2), (2, 3), (2, 4), (3, 5), (4, 5), (5, 2), (5, 6)])
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn set(ids: Vec<u64>) -> HashSet<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
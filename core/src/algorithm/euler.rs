@@ -0,0 +1,243 @@
+//! Eulerian paths and circuits: walks using every edge of the graph exactly once
+use crate::algorithm::max_flow::{Capacity, Flow};
+use crate::algorithm::min_cost_flow;
+use crate::graph::{Edge, GraphView, VertexId};
+use crate::path::Path;
+use std::collections::HashMap;
+
+/// Closed walk traversing every edge at least once, see chinese_postman
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct PostmanRoute {
+    /// Circuit going through every edge, its first and last vertices being the same
+    pub circuit: Path,
+    /// Number of times each edge is traversed more than once
+    pub extra_traversals: HashMap<Edge, Flow>,
+}
+
+impl PostmanRoute {
+    /// Minimum number of edges to add to the graph to make it Eulerian
+    pub fn extra_edge_count(&self) -> Flow {
+        self.extra_traversals.values().sum()
+    }
+}
+
+/// Checks if the graph has an Eulerian circuit
+/// All vertices must have as many inbound edges as outbound edges, and all the edges must
+/// be in the same connected component
+pub fn has_eulerian_circuit<G: GraphView>(graph: &G) -> bool {
+    eulerian_circuit(graph).is_some()
+}
+
+/// Checks if the graph has an Eulerian path
+/// All vertices must have as many inbound edges as outbound edges, except the start with one more
+/// outbound edge and the end with one more inbound edge, and all the edges must be in the same
+/// connected component
+pub fn has_eulerian_path<G: GraphView>(graph: &G) -> bool {
+    eulerian_path(graph).is_some()
+}
+
+/// Finds an Eulerian circuit, starting from the smallest vertex with edges
+/// A graph without edges has an empty circuit
+/// Implementation of Hierholzer algorithm, running in O(V + E)
+pub fn eulerian_circuit<G: GraphView>(graph: &G) -> Option<Path> {
+    if graph
+        .vertices()
+        .any(|v| graph.degree_in(v) != graph.degree_out(v))
+    {
+        return None;
+    }
+    let start = graph.vertices().filter(|v| graph.degree_out(*v) > 0).min();
+    hierholzer(graph, |_| 1, start)
+}
+
+/// Finds an Eulerian path
+/// It starts from the vertex with one more outbound edge if there is one, or else is a circuit
+/// A graph without edges has an empty path
+/// Implementation of Hierholzer algorithm, running in O(V + E)
+pub fn eulerian_path<G: GraphView>(graph: &G) -> Option<Path> {
+    let mut starts = vec![];
+    let mut ends = vec![];
+    for vertex in graph.vertices() {
+        let (degree_in, degree_out) = (graph.degree_in(vertex), graph.degree_out(vertex));
+        if degree_out == degree_in + 1 {
+            starts.push(vertex);
+        } else if degree_in == degree_out + 1 {
+            ends.push(vertex);
+        } else if degree_in != degree_out {
+            return None;
+        }
+    }
+    match (starts.len(), ends.len()) {
+        (0, 0) => eulerian_circuit(graph),
+        (1, 1) => hierholzer(graph, |_| 1, Some(starts[0])),
+        _ => None,
+    }
+}
+
+/// Directed Chinese postman problem: shortest circuit traversing every edge at least once
+/// Edges are traversed again along the shortest paths from vertices with more inbound edges
+/// to vertices with more outbound edges, computed as a minimum cost flow.
+/// Returns None if the graph has edges and the vertices with edges are not strongly connected
+pub fn chinese_postman<G: GraphView>(graph: &G) -> Option<PostmanRoute> {
+    let mut supplies: Vec<(VertexId, Flow)> = vec![];
+    let mut demands: Vec<(VertexId, Flow)> = vec![];
+    for vertex in graph.vertices() {
+        let (degree_in, degree_out) = (graph.degree_in(vertex), graph.degree_out(vertex));
+        if degree_in > degree_out {
+            supplies.push((vertex, (degree_in - degree_out) as Flow));
+        } else if degree_out > degree_in {
+            demands.push((vertex, (degree_out - degree_in) as Flow));
+        }
+    }
+    let total: Flow = demands.iter().map(|(_, d)| d).sum();
+    let flow =
+        min_cost_flow::transportation(graph, |_| total as Capacity, |_| 1, &supplies, &demands)?;
+    if flow.value != total {
+        return None;
+    }
+    let extra_traversals: HashMap<Edge, Flow> = flow
+        .flows
+        .into_iter()
+        .filter(|(_, (f, _))| *f > 0)
+        .map(|(e, (f, _))| (e, f))
+        .collect();
+    let start = graph.vertices().filter(|v| graph.degree_out(*v) > 0).min();
+    let circuit = hierholzer(
+        graph,
+        |e| 1 + extra_traversals.get(e).copied().unwrap_or(0),
+        start,
+    )?;
+    Some(PostmanRoute {
+        circuit,
+        extra_traversals,
+    })
+}
+
+// Walks from the start until stuck, which can only happen on the end of the path, then
+// inserts circuits from the vertices of the walk that still have unused edges.
+// Each edge is used as many times as its count. Returns None if some edges are not reached
+fn hierholzer<G, NFn>(graph: &G, count: NFn, start: Option<VertexId>) -> Option<Path>
+where
+    G: GraphView,
+    NFn: Fn(&Edge) -> Flow,
+{
+    let start = match start {
+        Some(start) => start,
+        None => return Some(Path::empty()),
+    };
+    let mut total: Flow = 0;
+    // Unused edges of each vertex, the smallest destination being used first
+    let mut unused: HashMap<VertexId, Vec<VertexId>> = HashMap::new();
+    for vertex in graph.vertices() {
+        let mut edges: Vec<Edge> = graph.outbound_edges(vertex).collect();
+        edges.sort_by(|a, b| b.cmp(a));
+        let mut destinations = vec![];
+        for edge in edges {
+            let n = count(&edge);
+            total += n;
+            destinations.extend(std::iter::repeat_n(edge.1, n as usize));
+        }
+        unused.insert(vertex, destinations);
+    }
+
+    let mut stack = vec![start];
+    let mut res = vec![];
+    while let Some(vertex) = stack.last() {
+        match unused.get_mut(vertex).and_then(|d| d.pop()) {
+            Some(next) => stack.push(next),
+            None => res.push(stack.pop().unwrap()),
+        }
+    }
+    if res.len() as Flow != total + 1 {
+        return None;
+    }
+    res.reverse();
+    Some(Path::from(&res))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn eulerian_circuit_should_use_every_edge_once() {
+        let g = build_test_graph();
+        let circuit = eulerian_circuit(&g).unwrap();
+        assert_eq![circuit, path_of(vec![1, 2, 3, 1, 4, 3, 4, 1])];
+        assert![has_eulerian_circuit(&g)];
+        assert![has_eulerian_path(&g)];
+    }
+
+    #[test]
+    fn eulerian_path_should_go_from_start_to_end() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1), (1, 4)]);
+        assert_eq![eulerian_circuit(&g), None];
+        let path = eulerian_path(&g).unwrap();
+        assert_eq![path, path_of(vec![1, 2, 3, 1, 4])];
+    }
+
+    #[test]
+    fn eulerian_path_should_be_none_for_unbalanced_or_disconnected_graphs() {
+        let g = graph_of(vec![(1, 2), (1, 3)]);
+        assert_eq![eulerian_path(&g), None];
+        let g = graph_of(vec![(1, 2), (2, 1), (3, 4), (4, 3)]);
+        assert![!has_eulerian_circuit(&g)];
+        assert![!has_eulerian_path(&g)];
+    }
+
+    #[test]
+    fn eulerian_circuit_should_handle_self_loops_and_empty_graphs() {
+        let g = graph_of(vec![(1, 1), (1, 2), (2, 1)]);
+        let circuit = eulerian_circuit(&g).unwrap();
+        assert_eq![circuit, path_of(vec![1, 1, 2, 1])];
+        assert_eq![eulerian_circuit(&DirectedGraph::new()), Some(Path::empty())];
+    }
+
+    #[test]
+    fn chinese_postman_should_add_minimum_extra_edges() {
+        // 1 -> 2 has to be traversed twice to come back to 1 after 1 -> 3
+        let g = graph_of(vec![(1, 2), (2, 1), (2, 3), (3, 1)]);
+        let route = chinese_postman(&g).unwrap();
+        assert_eq![route.extra_edge_count(), 1];
+        assert_eq![route.extra_traversals.get(&edge(1, 2)), Some(&1)];
+        assert_eq![route.circuit, path_of(vec![1, 2, 1, 2, 3, 1])];
+    }
+
+    #[test]
+    fn chinese_postman_should_not_add_edges_to_eulerian_graphs() {
+        let g = build_test_graph();
+        let route = chinese_postman(&g).unwrap();
+        assert_eq![route.extra_edge_count(), 0];
+        assert_eq![Some(route.circuit), eulerian_circuit(&g)];
+    }
+
+    #[test]
+    fn chinese_postman_should_be_none_if_not_strongly_connected() {
+        let g = graph_of(vec![(1, 2), (2, 3)]);
+        assert_eq![chinese_postman(&g), None];
+    }
+
+    // Helpers
+
+    // Balanced graph: 1 -> 2 -> 3 -> 1, 1 -> 4 -> 1 and 4 <-> 3
+    fn build_test_graph() -> DirectedGraph {
+        graph_of(vec![(1, 2), (2, 3), (3, 1), (1, 4), (4, 1), (4, 3), (3, 4)])
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn path_of(ids: Vec<u64>) -> Path {
+        Path::from(&ids.into_iter().map(VertexId).collect())
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
@@ -0,0 +1,293 @@
+//! Feedback arc sets: edges to remove to make a graph acyclic
+use crate::algorithm::connectivity;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use std::collections::HashMap;
+
+/// Strongly connected components up to this size are solved exactly
+/// The exact algorithm runs in O(2^n * n^2) for a component of n vertices
+pub const EXACT_COMPONENT_LIMIT: usize = 16;
+
+/// Feedback arc set: removing these edges makes the graph acyclic
+/// The set is minimum when all the strongly connected components of the graph have at most
+/// EXACT_COMPONENT_LIMIT vertices. Bigger components use Eades-Lin-Smyth heuristic.
+/// Returned edges are sorted
+pub fn feedback_arc_set<G: GraphView>(graph: &G) -> Vec<Edge> {
+    by_component(graph, |component| {
+        if component.len() <= EXACT_COMPONENT_LIMIT {
+            component.exact_order()
+        } else {
+            component.eades_lin_smyth_order()
+        }
+    })
+}
+
+/// Minimum feedback arc set
+/// Returns None if a strongly connected component has more than EXACT_COMPONENT_LIMIT vertices
+pub fn min_feedback_arc_set<G: GraphView>(graph: &G) -> Option<Vec<Edge>> {
+    if connectivity::strongly_connected_components(graph)
+        .iter()
+        .any(|c| c.len() > EXACT_COMPONENT_LIMIT)
+    {
+        None
+    } else {
+        Some(by_component(graph, |component| component.exact_order()))
+    }
+}
+
+/// Approximate feedback arc set using Eades-Lin-Smyth heuristic, running in O(V^2 + E)
+/// Vertices are ordered by repeatedly moving sinks to the end and sources to the start,
+/// or else the vertex with the biggest outdegree - indegree to the start.
+/// Edges going backward in this order form the feedback arc set.
+/// See Eades, Lin and Smyth, "A fast and effective heuristic for the feedback arc set problem" (1993)
+pub fn eades_lin_smyth<G: GraphView>(graph: &G) -> Vec<Edge> {
+    by_component(graph, |component| component.eades_lin_smyth_order())
+}
+
+// Cycles never span several strongly connected components, so each component is ordered
+// separately. Self loops always belong to the feedback arc set
+fn by_component<G, OFn>(graph: &G, order: OFn) -> Vec<Edge>
+where
+    G: GraphView,
+    OFn: Fn(&Component) -> Vec<usize>,
+{
+    let mut res: Vec<Edge> = graph.edges().filter(|e| e.0 == e.1).collect();
+    for vertices in connectivity::strongly_connected_components(graph) {
+        if vertices.len() < 2 {
+            continue;
+        }
+        let component = Component::new(graph, vertices);
+        res.extend(component.backward_edges(&order(&component)));
+    }
+    res.sort();
+    res
+}
+
+// Strongly connected component, with indexed vertices and without self loops
+struct Component {
+    vertices: Vec<VertexId>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl Component {
+    fn new<G: GraphView>(graph: &G, vertices: Vec<VertexId>) -> Component {
+        let indices: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut successors = vec![vec![]; vertices.len()];
+        let mut predecessors = vec![vec![]; vertices.len()];
+        for (index, vertex) in vertices.iter().enumerate() {
+            for Edge(_, dst) in graph.outbound_edges(*vertex) {
+                match indices.get(&dst) {
+                    Some(next) if *next != index => {
+                        successors[index].push(*next);
+                        predecessors[*next].push(index);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Component {
+            vertices,
+            successors,
+            predecessors,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn backward_edges(&self, order: &[usize]) -> Vec<Edge> {
+        let mut position = vec![0; order.len()];
+        for (rank, vertex) in order.iter().enumerate() {
+            position[*vertex] = rank;
+        }
+        let mut res = vec![];
+        for (vertex, successors) in self.successors.iter().enumerate() {
+            for next in successors {
+                if position[*next] < position[vertex] {
+                    res.push(Edge(self.vertices[vertex], self.vertices[*next]));
+                }
+            }
+        }
+        res
+    }
+
+    fn eades_lin_smyth_order(&self) -> Vec<usize> {
+        let size = self.vertices.len();
+        let mut removed = vec![false; size];
+        let mut degree_in: Vec<usize> = self.predecessors.iter().map(|p| p.len()).collect();
+        let mut degree_out: Vec<usize> = self.successors.iter().map(|s| s.len()).collect();
+        let mut start: Vec<usize> = vec![];
+        let mut end: Vec<usize> = vec![];
+        let mut remaining = size;
+        while remaining > 0 {
+            let vertex = (0..size)
+                .filter(|v| !removed[*v])
+                .find(|v| degree_out[*v] == 0)
+                .map(|v| (v, false))
+                .or_else(|| {
+                    (0..size)
+                        .filter(|v| !removed[*v])
+                        .find(|v| degree_in[*v] == 0)
+                        .map(|v| (v, true))
+                })
+                .or_else(|| {
+                    (0..size)
+                        .filter(|v| !removed[*v])
+                        .max_by_key(|v| {
+                            (degree_out[*v] as i64 - degree_in[*v] as i64, -(*v as i64))
+                        })
+                        .map(|v| (v, true))
+                });
+            let (vertex, at_start) = vertex.expect("There are remaining vertices");
+            if at_start {
+                start.push(vertex);
+            } else {
+                end.push(vertex);
+            }
+            removed[vertex] = true;
+            remaining -= 1;
+            for next in &self.successors[vertex] {
+                degree_in[*next] -= 1;
+            }
+            for previous in &self.predecessors[vertex] {
+                degree_out[*previous] -= 1;
+            }
+        }
+        // Sinks were found from the end of the order
+        start.extend(end.iter().rev());
+        start
+    }
+
+    // Dynamic programming over the subsets of vertices: the best order of a subset is the best
+    // order of the subset without its last vertex, followed by that vertex
+    fn exact_order(&self) -> Vec<usize> {
+        let size = self.vertices.len();
+        let mut successor_masks = vec![0usize; size];
+        for (vertex, successors) in self.successors.iter().enumerate() {
+            for next in successors {
+                successor_masks[vertex] |= 1 << next;
+            }
+        }
+        let full = (1usize << size) - 1;
+        let mut cost: Vec<usize> = vec![usize::MAX; full + 1];
+        let mut last: Vec<usize> = vec![0; full + 1];
+        cost[0] = 0;
+        for subset in 1..=full {
+            for vertex in (0..size).filter(|v| subset & (1 << v) != 0) {
+                let before = subset & !(1 << vertex);
+                // Edges from the last vertex back to the vertices placed before it
+                let backward = (successor_masks[vertex] & before).count_ones() as usize;
+                if cost[before] + backward < cost[subset] {
+                    cost[subset] = cost[before] + backward;
+                    last[subset] = vertex;
+                }
+            }
+        }
+        let mut order = vec![];
+        let mut subset = full;
+        while subset != 0 {
+            order.push(last[subset]);
+            subset &= !(1 << last[subset]);
+        }
+        order.reverse();
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::topo_sort;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn feedback_arc_set_should_be_empty_for_a_dag() {
+        let g = graph_of(vec![(1, 2), (2, 3), (1, 3)]);
+        assert_eq![feedback_arc_set(&g), vec![]];
+        assert_eq![eades_lin_smyth(&g), vec![]];
+    }
+
+    #[test]
+    fn feedback_arc_set_should_break_every_cycle() {
+        let g = build_test_graph();
+        for arcs in [feedback_arc_set(&g), eades_lin_smyth(&g)] {
+            assert![is_dag_without(&g, &arcs), "{:?} leaves a cycle", arcs];
+        }
+    }
+
+    #[test]
+    fn min_feedback_arc_set_should_remove_the_edge_shared_by_all_cycles() {
+        // Cycles 1 -> 2 -> 3 -> 1 and 1 -> 2 -> 4 -> 1 share the edge 1 -> 2
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1), (2, 4), (4, 1)]);
+        assert_eq![min_feedback_arc_set(&g), Some(vec![edge(1, 2)])];
+    }
+
+    #[test]
+    fn min_feedback_arc_set_should_find_the_minimum_on_a_tournament() {
+        let g = build_test_graph();
+        let exact = min_feedback_arc_set(&g).unwrap();
+        assert_eq![exact.len(), 2];
+        assert![is_dag_without(&g, &exact)];
+        assert![eades_lin_smyth(&g).len() >= exact.len()];
+    }
+
+    #[test]
+    fn feedback_arc_set_should_include_self_loops() {
+        let g = graph_of(vec![(1, 1), (1, 2), (2, 1)]);
+        let arcs = feedback_arc_set(&g);
+        assert_eq![arcs.len(), 2];
+        assert![arcs.contains(&edge(1, 1))];
+        assert![is_dag_without(&g, &arcs)];
+    }
+
+    #[test]
+    fn min_feedback_arc_set_should_be_none_for_big_components() {
+        let size = EXACT_COMPONENT_LIMIT as u64 + 1;
+        let g = graph_of((0..size).map(|i| (i, (i + 1) % size)).collect());
+        assert_eq![min_feedback_arc_set(&g), None];
+        assert_eq![feedback_arc_set(&g).len(), 1];
+    }
+
+    // Helpers
+
+    // Tournament on 5 vertices: 1 -> 2 -> 3 -> 4 -> 5, and reversed edges otherwise,
+    // except 1 -> 3 and 2 -> 4
+    fn build_test_graph() -> DirectedGraph {
+        graph_of(vec![
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (1, 3),
+            (2, 4),
+            (4, 1),
+            (5, 1),
+            (5, 2),
+            (5, 3),
+        ])
+    }
+
+    fn is_dag_without(g: &DirectedGraph, arcs: &[Edge]) -> bool {
+        let mut g2 = DirectedGraph::new();
+        for e in g.edges().filter(|e| !arcs.contains(e)) {
+            g2.add_edge(*e);
+        }
+        topo_sort::is_dag(&g2)
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
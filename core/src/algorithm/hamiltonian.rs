@@ -1,42 +1,362 @@
 //! Find Hamiltonian paths in a directed graph
 
-use crate::directed_graph::DirectedGraph;
+use crate::algorithm::connectivity;
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::iter;
+use crate::iter::iter_cycle::Cycle;
 use crate::path::Path;
+use crate::weight::Overflow;
+use std::collections::HashMap;
+
+/// Graphs up to this number of vertices are searched with Held-Karp dynamic programming
+/// It uses O(2^n) memory, beyond that the pruned backtracking search is used
+pub const HELD_KARP_LIMIT: usize = 25;
+
+/// Maximum number of vertices for the weighted search, which uses O(2^n * n) memory
+pub const WEIGHTED_HELD_KARP_LIMIT: usize = 18;
 
 /// Checks if the graph is Hamiltonian (ie contains an hamiltonian path)
 /// By convention an empty graph is hamiltonian
-pub fn is_hamiltonian(graph: &DirectedGraph) -> bool {
+pub fn is_hamiltonian<G: GraphView>(graph: &G) -> bool {
     graph.is_empty() || first_path(graph).is_some()
 }
 
 /// Iterates on the hamiltonian paths of the graph if it exists
 /// Naive algorithm implementation. Will do a DFS for each vertex of the graph,
-pub fn iter_hamiltonian_paths(graph: &DirectedGraph) -> impl Iterator<Item = Path> + '_ {
-    // FIXME check if path is connected first, else return empty iterator straght away
+/// unless the graph is not weakly connected, in which case there is no hamiltonian path
+pub fn iter_hamiltonian_paths<G: GraphView>(graph: &G) -> impl Iterator<Item = Path> + '_ {
+    let connected = connectivity::is_weakly_connected(graph);
     let count = graph.vertex_count();
     graph
         .vertices()
-        .flat_map(move |vid| iter::iter_depth::dfs_iter_path_from(graph, *vid))
+        .filter(move |_| connected)
+        .flat_map(move |vid| iter::iter_depth::dfs_iter_path_from(graph, vid))
         .filter(move |p| p.size() == count)
 }
 
 /// Finds an hamiltonian path of the graph if it exists
-pub fn first_path(graph: &DirectedGraph) -> Option<Path> {
-    iter_hamiltonian_paths(graph).next()
+/// Uses Held-Karp for small graphs, and the pruned backtracking search otherwise
+pub fn first_path<G: GraphView>(graph: &G) -> Option<Path> {
+    if graph.vertex_count() <= HELD_KARP_LIMIT {
+        held_karp_path(graph)
+    } else {
+        backtracking_path(graph)
+    }
+}
+
+/// Finds an hamiltonian cycle of the graph if it exists
+/// Uses Held-Karp for small graphs, and the pruned backtracking search otherwise
+pub fn first_cycle<G: GraphView>(graph: &G) -> Option<Cycle> {
+    if graph.vertex_count() <= HELD_KARP_LIMIT {
+        held_karp_cycle(graph)
+    } else {
+        backtracking_cycle(graph)
+    }
+}
+
+/// Finds an hamiltonian path with Held-Karp dynamic programming, running in O(2^n * n)
+/// For each subset of vertices, it computes the set of vertices a path covering this subset can end on.
+/// Returns None if there is no path, or if the graph has more than HELD_KARP_LIMIT vertices
+pub fn held_karp_path<G: GraphView>(graph: &G) -> Option<Path> {
+    let indexed = IndexedGraph::new(graph);
+    let size = indexed.len();
+    if size == 0 || size > HELD_KARP_LIMIT {
+        return None;
+    }
+    let mut ends = vec![0u32; 1 << size];
+    for vertex in 0..size {
+        ends[1 << vertex] = 1 << vertex;
+    }
+    indexed.extend_ends(&mut ends, |_| true);
+    let full = (1 << size) - 1;
+    let last = lowest_bit(ends[full])?;
+    Some(Path::from(
+        &indexed.vertices_of(indexed.backtrack(&ends, full, last)),
+    ))
+}
+
+/// Finds an hamiltonian cycle with Held-Karp dynamic programming, running in O(2^n * n)
+/// Returns None if there is no cycle, or if the graph has more than HELD_KARP_LIMIT vertices
+pub fn held_karp_cycle<G: GraphView>(graph: &G) -> Option<Cycle> {
+    let indexed = IndexedGraph::new(graph);
+    let size = indexed.len();
+    if !(2..=HELD_KARP_LIMIT).contains(&size) {
+        return None;
+    }
+    // Paths start from the smallest vertex, so the cycle is canonical
+    let mut ends = vec![0u32; 1 << size];
+    ends[1] = 1;
+    indexed.extend_ends(&mut ends, |subset| subset & 1 != 0);
+    let full = (1 << size) - 1;
+    let last = lowest_bit(ends[full] & indexed.predecessors[0])?;
+    Cycle::from_vertices(&indexed.vertices_of(indexed.backtrack(&ends, full, last)))
+}
+
+/// Finds the hamiltonian cycle with the minimum total weight (directed travelling salesman)
+/// Held-Karp dynamic programming, running in O(2^n * n^2)
+/// Returns None if there is no cycle, or if the graph has more than WEIGHTED_HELD_KARP_LIMIT vertices,
+/// and an error if the weight of a path overflows
+pub fn min_hamiltonian_cycle<G, WFn>(
+    graph: &G,
+    weight: WFn,
+) -> Result<Option<(Cycle, i64)>, Overflow>
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> i64,
+{
+    let indexed = IndexedGraph::new(graph);
+    let size = indexed.len();
+    if !(2..=WEIGHTED_HELD_KARP_LIMIT).contains(&size) {
+        return Ok(None);
+    }
+    let weights: HashMap<(usize, usize), i64> = graph
+        .edges()
+        .filter(|Edge(src, dst)| src != dst)
+        .map(|e| ((indexed.index[&e.0], indexed.index[&e.1]), weight(&e)))
+        .collect();
+    // costs[subset * size + v]: cheapest path from the smallest vertex covering subset, ending on v
+    let mut costs: Vec<Option<i64>> = vec![None; (1 << size) * size];
+    let mut previous: Vec<u8> = vec![0; (1 << size) * size];
+    costs[size] = Some(0);
+    for subset in (1..1usize << size).filter(|s| s & 1 != 0) {
+        for last in (0..size).filter(|v| subset & (1 << v) != 0) {
+            let cost = match costs[subset * size + last] {
+                Some(cost) => cost,
+                None => continue,
+            };
+            for next in indexed.successors[last]
+                .iter()
+                .filter(|v| subset & (1 << *v) == 0)
+            {
+                let extended = (subset | (1 << next)) * size + next;
+                let candidate = cost.checked_add(weights[&(last, *next)]).ok_or(Overflow)?;
+                if costs[extended].is_none_or(|c| candidate < c) {
+                    costs[extended] = Some(candidate);
+                    previous[extended] = last as u8;
+                }
+            }
+        }
+    }
+    let full = (1 << size) - 1;
+    let tours = (1..size)
+        .filter_map(|v| Some((costs[full * size + v]?, *weights.get(&(v, 0))?, v)))
+        .map(|(cost, closing, v)| cost.checked_add(closing).map(|total| (total, v)))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Overflow)?;
+    let (total, mut last) = match tours.into_iter().min() {
+        Some(tour) => tour,
+        None => return Ok(None),
+    };
+    let mut order = vec![];
+    let mut subset = full;
+    while subset != 1 {
+        order.push(last);
+        let before = previous[subset * size + last] as usize;
+        subset &= !(1 << last);
+        last = before;
+    }
+    order.push(0);
+    order.reverse();
+    Ok(Cycle::from_vertices(&indexed.vertices_of(order)).map(|c| (c, total)))
+}
+
+/// Finds an hamiltonian path with a backtracking search
+/// Branches are pruned when some vertices cannot be reached anymore from the end of the path.
+/// There can be only one vertex without inbound edges, and the path has to start on it.
+/// Likewise there can be only one vertex without outbound edges.
+pub fn backtracking_path<G: GraphView>(graph: &G) -> Option<Path> {
+    let indexed = IndexedGraph::new(graph);
+    if indexed.len() == 0 || !connectivity::is_weakly_connected(graph) {
+        return None;
+    }
+    let sources: Vec<usize> = (0..indexed.len())
+        .filter(|v| indexed.in_degree(*v) == 0)
+        .collect();
+    let sinks = (0..indexed.len())
+        .filter(|v| indexed.successors[*v].is_empty())
+        .count();
+    if sinks > 1 {
+        return None;
+    }
+    let starts: Vec<usize> = match sources.len() {
+        0 => (0..indexed.len()).collect(),
+        1 => sources,
+        _ => return None,
+    };
+    starts
+        .into_iter()
+        .find_map(|start| indexed.backtrack_from(start, false))
+        .map(|order| Path::from(&indexed.vertices_of(order)))
+}
+
+/// Finds an hamiltonian cycle with a backtracking search, pruned as backtracking_path
+pub fn backtracking_cycle<G: GraphView>(graph: &G) -> Option<Cycle> {
+    let indexed = IndexedGraph::new(graph);
+    if indexed.len() < 2 || (0..indexed.len()).any(|v| indexed.in_degree(v) == 0) {
+        return None;
+    }
+    indexed
+        .backtrack_from(0, true)
+        .and_then(|order| Cycle::from_vertices(&indexed.vertices_of(order)))
 }
 
 /// Checks if the path is an hamiltonian for the given graph
 /// Assumption : the path is coming from the given graph (ie this is a valid path with respect to the graph)
-pub fn is_path_hamiltonian(path: &Path, graph: &DirectedGraph) -> bool {
+pub fn is_path_hamiltonian<G: GraphView>(path: &Path, graph: &G) -> bool {
     !path.contains_cycle() && path.size() == graph.vertex_count()
 }
 
+// Graph with vertices indexed in increasing order, without self loops.
+// Predecessors are kept as bitsets for Held-Karp, which only runs on small graphs
+struct IndexedGraph {
+    vertices: Vec<VertexId>,
+    index: HashMap<VertexId, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessor_lists: Vec<Vec<usize>>,
+    predecessors: Vec<u32>,
+}
+
+impl IndexedGraph {
+    fn new<G: GraphView>(graph: &G) -> IndexedGraph {
+        let mut vertices: Vec<VertexId> = graph.vertices().collect();
+        vertices.sort();
+        let index: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut successors = vec![vec![]; vertices.len()];
+        let mut predecessor_lists = vec![vec![]; vertices.len()];
+        let mut predecessors = vec![0u32; vertices.len()];
+        let mut edges: Vec<Edge> = graph.edges().filter(|Edge(src, dst)| src != dst).collect();
+        edges.sort();
+        for Edge(src, dst) in edges {
+            let (src, dst) = (index[&src], index[&dst]);
+            successors[src].push(dst);
+            predecessor_lists[dst].push(src);
+            if src < 32 {
+                predecessors[dst] |= 1 << src;
+            }
+        }
+        IndexedGraph {
+            vertices,
+            index,
+            successors,
+            predecessor_lists,
+            predecessors,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn in_degree(&self, vertex: usize) -> usize {
+        self.predecessor_lists[vertex].len()
+    }
+
+    fn vertices_of(&self, order: Vec<usize>) -> Vec<VertexId> {
+        order.into_iter().map(|v| self.vertices[v]).collect()
+    }
+
+    // ends[subset] is the set of vertices a path covering the subset can end on
+    fn extend_ends<SFn: Fn(usize) -> bool>(&self, ends: &mut [u32], kept: SFn) {
+        for subset in 1..ends.len() {
+            if ends[subset] == 0 || !kept(subset) {
+                continue;
+            }
+            for next in (0..self.len()).filter(|v| subset & (1 << v) == 0) {
+                if self.predecessors[next] & ends[subset] != 0 {
+                    ends[subset | (1 << next)] |= 1 << next;
+                }
+            }
+        }
+    }
+
+    // Rebuilds a path covering the subset and ending on the last vertex
+    fn backtrack(&self, ends: &[u32], subset: usize, last: usize) -> Vec<usize> {
+        let mut order = vec![last];
+        let (mut subset, mut last) = (subset, last);
+        while subset.count_ones() > 1 {
+            subset &= !(1 << last);
+            last = lowest_bit(ends[subset] & self.predecessors[last])
+                .expect("A vertex of the path ends on a predecessor");
+            order.push(last);
+        }
+        order.reverse();
+        order
+    }
+
+    // Depth first search of a path covering all the vertices, closing on the start if needed
+    fn backtrack_from(&self, start: usize, closing: bool) -> Option<Vec<usize>> {
+        let mut visited = vec![false; self.len()];
+        visited[start] = true;
+        let mut order = vec![start];
+        // Next successor to try for each vertex of the path
+        let mut tried: Vec<usize> = vec![0];
+        while let Some(position) = tried.last_mut() {
+            let current = *order.last().unwrap();
+            if order.len() == self.len() && (!closing || self.successors[current].contains(&start))
+            {
+                return Some(order);
+            }
+            let next = self.successors[current]
+                .iter()
+                .skip(*position)
+                .position(|v| !visited[*v])
+                .map(|offset| *position + offset);
+            match next {
+                Some(index) if order.len() < self.len() => {
+                    *position = index + 1;
+                    let vertex = self.successors[current][index];
+                    visited[vertex] = true;
+                    order.push(vertex);
+                    tried.push(0);
+                    if !self.can_complete(&visited, vertex, start, closing) {
+                        visited[vertex] = false;
+                        order.pop();
+                        tried.pop();
+                    }
+                }
+                _ => {
+                    visited[current] = false;
+                    order.pop();
+                    tried.pop();
+                }
+            }
+        }
+        None
+    }
+
+    // All the unvisited vertices must be reachable from the end of the path through unvisited
+    // vertices, and the start must be reachable back when looking for a cycle
+    fn can_complete(&self, visited: &[bool], current: usize, start: usize, closing: bool) -> bool {
+        let mut reached = visited.to_vec();
+        let mut to_visit = vec![current];
+        let mut closes = !closing;
+        while let Some(vertex) = to_visit.pop() {
+            for next in &self.successors[vertex] {
+                closes |= *next == start;
+                if !reached[*next] {
+                    reached[*next] = true;
+                    to_visit.push(*next);
+                }
+            }
+        }
+        closes && reached.iter().all(|r| *r)
+    }
+}
+
+fn lowest_bit(bits: u32) -> Option<usize> {
+    if bits == 0 {
+        None
+    } else {
+        Some(bits.trailing_zeros() as usize)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::Edge;
-    use crate::graph::VertexId;
+    use crate::directed_graph::DirectedGraph;
+    use crate::iter::iter_cycle;
     use std::iter::Iterator;
 
     #[test]
@@ -63,6 +383,19 @@ mod tests {
         ]
     }
 
+    #[test]
+    fn first_path_should_return_none_if_the_graph_is_not_connected() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        g.add_vertex(VertexId(4));
+
+        assert![
+            first_path(&g).is_none(),
+            "A disconnected graph has no Hamiltonian path"
+        ]
+    }
+
     #[test]
     fn first_path_should_return_a_path_if_graph_has_one_hamiltonian_cycle() {
         let mut g = DirectedGraph::new();
@@ -161,8 +494,111 @@ mod tests {
         ];
     }
 
+    #[test]
+    fn held_karp_and_backtracking_should_agree_with_the_exhaustive_search() {
+        // Pseudo random sparse graphs
+        let mut seed: u64 = 11;
+        for _ in 0..40 {
+            let mut g = DirectedGraph::new();
+            for src in 0..7 {
+                g.add_vertex(VertexId(src));
+                for dst in 0..7 {
+                    seed = (seed * 1103 + 12345) % 1009;
+                    if src != dst && seed.is_multiple_of(4) {
+                        g.add_edge(edge(src, dst));
+                    }
+                }
+            }
+            let has_path = iter_hamiltonian_paths(&g).next().is_some();
+            for path in [held_karp_path(&g), backtracking_path(&g)] {
+                assert_eq![path.is_some(), has_path];
+                assert![path.is_none_or(|p| is_valid_path(&p, &g))];
+            }
+            let has_cycle = iter_cycle::circuit_iter(&g).any(|c| c.len() == 7);
+            for cycle in [held_karp_cycle(&g), backtracking_cycle(&g)] {
+                assert_eq![cycle.is_some(), has_cycle];
+                assert![cycle.is_none_or(|c| is_valid_path(&c.as_path(), &g))];
+            }
+        }
+    }
+
+    #[test]
+    fn backtracking_should_find_paths_and_cycles_in_big_graphs() {
+        let mut g = DirectedGraph::new();
+        for i in 0..999 {
+            g.add_edge(edge(i, i + 1));
+            g.add_edge(edge(i + 1, i / 2));
+        }
+        let path = backtracking_path(&g).unwrap();
+        assert![is_path_hamiltonian(&path, &g)];
+        assert_eq![first_path(&g), Some(path)];
+
+        g.add_edge(edge(999, 0));
+        let cycle = first_cycle(&g).unwrap();
+        assert_eq![cycle.len(), 1000];
+        assert![is_valid_path(&cycle.as_path(), &g)];
+    }
+
+    #[test]
+    fn min_hamiltonian_cycle_should_find_the_cheapest_tour() {
+        let mut g = DirectedGraph::new();
+        let mut weights = HashMap::new();
+        for (src, dst, w) in vec![
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 4, 1),
+            (4, 1, 10),
+            (1, 3, 2),
+            (3, 2, 2),
+            (2, 4, 2),
+            (4, 2, 1),
+            (2, 1, 3),
+        ] {
+            g.add_edge(edge(src, dst));
+            weights.insert(edge(src, dst), w);
+        }
+        let (cycle, weight) = min_hamiltonian_cycle(&g, |e| weights[e]).unwrap().unwrap();
+        // 1 -> 3 -> 4 -> 2 -> 1 costs 7, 1 -> 2 -> 3 -> 4 -> 1 costs 13
+        assert_eq![
+            cycle,
+            Cycle::from_vertices(&vertices(vec![1, 3, 4, 2])).unwrap()
+        ];
+        assert_eq![weight, 7];
+    }
+
+    #[test]
+    fn min_hamiltonian_cycle_should_return_none_without_hamiltonian_cycle() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        assert_eq![min_hamiltonian_cycle(&g, |_| 1), Ok(None)];
+    }
+
+    #[test]
+    fn min_hamiltonian_cycle_should_detect_overflows() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        g.add_edge(edge(3, 1));
+        let weight = |e: &Edge| if *e == edge(3, 1) { i64::MAX } else { 1 };
+        assert_eq![min_hamiltonian_cycle(&g, weight), Err(Overflow)];
+    }
+
     // Helpers
 
+    fn is_valid_path(path: &Path, g: &DirectedGraph) -> bool {
+        path.to_edge_list().all(|e| g.contains_edge(e))
+            && path
+                .to_vertex_list()
+                .filter(|v| g.contains_vertex(**v))
+                .count()
+                >= g.vertex_count()
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
     fn edge(src: u64, dst: u64) -> Edge {
         Edge(VertexId(src), VertexId(dst))
     }
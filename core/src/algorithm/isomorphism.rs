@@ -0,0 +1,486 @@
+//! Graph isomorphism and subgraph matching, using VF2 algorithm
+//! See Cordella, Foggia, Sansone and Vento, "A (sub)graph isomorphism algorithm for matching
+//! large graphs" (2004)
+use crate::graph::{Edge, GraphView, VertexId};
+use std::collections::{HashMap, HashSet};
+
+/// Mapping from the vertices of the pattern to the vertices of the target graph
+pub type Mapping = HashMap<VertexId, VertexId>;
+
+/// Kind of matching of a pattern in a target graph
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatchKind {
+    /// Bijection preserving edges and non edges between both graphs
+    Isomorphism,
+    /// Injection preserving edges and non edges: the pattern is an induced subgraph of the target
+    InducedSubgraph,
+    /// Injection preserving edges only: the target can have more edges between matched vertices
+    Monomorphism,
+}
+
+/// Checks if both graphs are isomorphic
+pub fn is_isomorphic<G: GraphView, H: GraphView>(graph: &G, other: &H) -> bool {
+    isomorphism(graph, other).is_some()
+}
+
+/// Finds an isomorphism from the first graph to the second one if it exists
+pub fn isomorphism<G: GraphView, H: GraphView>(graph: &G, other: &H) -> Option<Mapping> {
+    iter_matches(graph, other, MatchKind::Isomorphism, any_vertex, any_edge).next()
+}
+
+/// Iterates on the occurrences of the pattern as an induced subgraph of the target
+pub fn subgraph_isomorphisms<G: GraphView, H: GraphView>(
+    pattern: &G,
+    target: &H,
+) -> impl Iterator<Item = Mapping> {
+    iter_matches(
+        pattern,
+        target,
+        MatchKind::InducedSubgraph,
+        any_vertex,
+        any_edge,
+    )
+}
+
+/// Iterates on the occurrences of the pattern as a subgraph, not necessarily induced, of the target
+pub fn subgraph_monomorphisms<G: GraphView, H: GraphView>(
+    pattern: &G,
+    target: &H,
+) -> impl Iterator<Item = Mapping> {
+    iter_matches(
+        pattern,
+        target,
+        MatchKind::Monomorphism,
+        any_vertex,
+        any_edge,
+    )
+}
+
+/// Iterates on the matches of the pattern in the target, computed lazily
+/// Both graphs are copied into the iterator, which doesn't borrow them
+/// Matched vertices and edges must also satisfy the given predicates, taking the pattern
+/// vertex or edge first. A symmetric pattern matches the same vertices several times.
+pub fn iter_matches<G, H, VFn, EFn>(
+    pattern: &G,
+    target: &H,
+    kind: MatchKind,
+    vertex_match: VFn,
+    edge_match: EFn,
+) -> MatchIter<VFn, EFn>
+where
+    G: GraphView,
+    H: GraphView,
+    VFn: Fn(&VertexId, &VertexId) -> bool,
+    EFn: Fn(&Edge, &Edge) -> bool,
+{
+    let pattern = IndexedGraph::new(pattern);
+    let target = IndexedGraph::new(target);
+    let possible = match kind {
+        MatchKind::Isomorphism => {
+            pattern.len() == target.len() && pattern.edge_count == target.edge_count
+        }
+        _ => pattern.len() <= target.len() && pattern.edge_count <= target.edge_count,
+    };
+    let order = pattern.matching_order();
+    MatchIter {
+        core_pattern: vec![None; pattern.len()],
+        core_target: vec![None; target.len()],
+        pattern,
+        target,
+        kind,
+        vertex_match,
+        edge_match,
+        order,
+        candidates: vec![],
+        started: !possible,
+    }
+}
+
+/// Lazy iterator on the matches of a pattern in a target graph
+pub struct MatchIter<VFn, EFn> {
+    pattern: IndexedGraph,
+    target: IndexedGraph,
+    kind: MatchKind,
+    vertex_match: VFn,
+    edge_match: EFn,
+    // Pattern vertices in the order they are matched
+    order: Vec<usize>,
+    core_pattern: Vec<Option<usize>>,
+    core_target: Vec<Option<usize>>,
+    // For each matched pattern vertex, the target candidates and the next one to try
+    candidates: Vec<(Vec<usize>, usize)>,
+    started: bool,
+}
+
+impl<VFn, EFn> Iterator for MatchIter<VFn, EFn>
+where
+    VFn: Fn(&VertexId, &VertexId) -> bool,
+    EFn: Fn(&Edge, &Edge) -> bool,
+{
+    type Item = Mapping;
+
+    fn next(&mut self) -> Option<Mapping> {
+        if !self.started {
+            self.started = true;
+            if self.order.is_empty() {
+                return Some(Mapping::new());
+            }
+            let first = self.candidates_of(self.order[0]);
+            self.candidates.push((first, 0));
+        }
+        while let Some((candidates, next)) = self.candidates.last() {
+            let depth = self.candidates.len() - 1;
+            let vertex = self.order[depth];
+            let candidate = candidates.get(*next).copied();
+            // The candidate previously tried for this vertex is taken back
+            if let Some(previous) = self.core_pattern[vertex].take() {
+                self.core_target[previous] = None;
+            }
+            match candidate {
+                Some(image) => {
+                    self.candidates.last_mut().unwrap().1 += 1;
+                    if !self.is_feasible(vertex, image) {
+                        continue;
+                    }
+                    self.core_pattern[vertex] = Some(image);
+                    self.core_target[image] = Some(vertex);
+                    if depth + 1 == self.order.len() {
+                        return Some(self.mapping());
+                    }
+                    let next_candidates = self.candidates_of(self.order[depth + 1]);
+                    self.candidates.push((next_candidates, 0));
+                }
+                None => {
+                    self.candidates.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<VFn, EFn> MatchIter<VFn, EFn>
+where
+    VFn: Fn(&VertexId, &VertexId) -> bool,
+    EFn: Fn(&Edge, &Edge) -> bool,
+{
+    fn mapping(&self) -> Mapping {
+        self.core_pattern
+            .iter()
+            .enumerate()
+            .map(|(p, t)| (self.pattern.vertices[p], self.target.vertices[t.unwrap()]))
+            .collect()
+    }
+
+    // Images of a matched neighbour restrict the candidates to its neighbours
+    fn candidates_of(&self, vertex: usize) -> Vec<usize> {
+        let from_successor = self.pattern.predecessors[vertex]
+            .iter()
+            .find_map(|p| self.core_pattern[*p])
+            .map(|image| &self.target.successors[image]);
+        let from_predecessor = self.pattern.successors[vertex]
+            .iter()
+            .find_map(|s| self.core_pattern[*s])
+            .map(|image| &self.target.predecessors[image]);
+        let mut res: Vec<usize> = match from_successor.or(from_predecessor) {
+            Some(neighbours) => neighbours.iter().copied().collect(),
+            None => (0..self.target.len()).collect(),
+        };
+        res.retain(|t| self.core_target[*t].is_none());
+        res.sort();
+        res
+    }
+
+    fn is_feasible(&self, vertex: usize, image: usize) -> bool {
+        let (pattern, target) = (&self.pattern, &self.target);
+        let exact = self.kind == MatchKind::Isomorphism;
+        let degrees_fit = |p: usize, t: usize| {
+            if exact {
+                p == t
+            } else {
+                p <= t
+            }
+        };
+        if !degrees_fit(
+            pattern.successors[vertex].len(),
+            target.successors[image].len(),
+        ) || !degrees_fit(
+            pattern.predecessors[vertex].len(),
+            target.predecessors[image].len(),
+        ) || !(self.vertex_match)(&pattern.vertices[vertex], &target.vertices[image])
+        {
+            return false;
+        }
+        let pattern_loop = pattern.has_edge(vertex, vertex);
+        let target_loop = target.has_edge(image, image);
+        if pattern_loop && !target_loop
+            || target_loop && !pattern_loop && self.kind != MatchKind::Monomorphism
+            || pattern_loop
+                && !(self.edge_match)(&pattern.edge(vertex, vertex), &target.edge(image, image))
+        {
+            return false;
+        }
+
+        // Edges with the matched vertices must be preserved, and non edges unless monomorphism
+        for (other, other_image) in self.matched_pairs() {
+            for (from, to, from_image, to_image) in [
+                (vertex, other, image, other_image),
+                (other, vertex, other_image, image),
+            ] {
+                let in_pattern = pattern.has_edge(from, to);
+                let in_target = target.has_edge(from_image, to_image);
+                if in_pattern && !in_target
+                    || in_target && !in_pattern && self.kind != MatchKind::Monomorphism
+                {
+                    return false;
+                }
+                if in_pattern
+                    && !(self.edge_match)(
+                        &pattern.edge(from, to),
+                        &target.edge(from_image, to_image),
+                    )
+                {
+                    return false;
+                }
+            }
+        }
+
+        // Look ahead: unmatched neighbours of the vertex need distinct unmatched neighbours of the image
+        let unmatched_pattern = |neighbours: &HashSet<usize>| {
+            neighbours
+                .iter()
+                .filter(|n| **n != vertex && self.core_pattern[**n].is_none())
+                .count()
+        };
+        let unmatched_target = |neighbours: &HashSet<usize>| {
+            neighbours
+                .iter()
+                .filter(|n| **n != image && self.core_target[**n].is_none())
+                .count()
+        };
+        degrees_fit(
+            unmatched_pattern(&pattern.successors[vertex]),
+            unmatched_target(&target.successors[image]),
+        ) && degrees_fit(
+            unmatched_pattern(&pattern.predecessors[vertex]),
+            unmatched_target(&target.predecessors[image]),
+        )
+    }
+
+    fn matched_pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.core_pattern
+            .iter()
+            .enumerate()
+            .filter_map(|(p, t)| t.map(|t| (p, t)))
+    }
+}
+
+// Graph with indexed vertices, in increasing order
+struct IndexedGraph {
+    vertices: Vec<VertexId>,
+    successors: Vec<HashSet<usize>>,
+    predecessors: Vec<HashSet<usize>>,
+    edge_count: usize,
+}
+
+impl IndexedGraph {
+    fn new<G: GraphView>(graph: &G) -> IndexedGraph {
+        let mut vertices: Vec<VertexId> = graph.vertices().collect();
+        vertices.sort();
+        let index: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut successors = vec![HashSet::new(); vertices.len()];
+        let mut predecessors = vec![HashSet::new(); vertices.len()];
+        for Edge(src, dst) in graph.edges() {
+            successors[index[&src]].insert(index[&dst]);
+            predecessors[index[&dst]].insert(index[&src]);
+        }
+        IndexedGraph {
+            vertices,
+            successors,
+            predecessors,
+            edge_count: graph.edge_count(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn has_edge(&self, src: usize, dst: usize) -> bool {
+        self.successors[src].contains(&dst)
+    }
+
+    fn edge(&self, src: usize, dst: usize) -> Edge {
+        Edge(self.vertices[src], self.vertices[dst])
+    }
+
+    fn degree(&self, vertex: usize) -> usize {
+        self.successors[vertex].len() + self.predecessors[vertex].len()
+    }
+
+    // Vertices connected to the already ordered ones first, the most connected first,
+    // so that candidates are restricted to the neighbours of matched vertices
+    fn matching_order(&self) -> Vec<usize> {
+        let mut ordered = vec![false; self.len()];
+        let mut res = vec![];
+        while res.len() < self.len() {
+            let next = (0..self.len())
+                .filter(|v| !ordered[*v])
+                .max_by_key(|v| {
+                    let links = self.successors[*v]
+                        .iter()
+                        .chain(self.predecessors[*v].iter())
+                        .filter(|n| ordered[**n])
+                        .count();
+                    (links, self.degree(*v), std::cmp::Reverse(*v))
+                })
+                .unwrap();
+            ordered[next] = true;
+            res.push(next);
+        }
+        res
+    }
+}
+
+fn any_vertex(_: &VertexId, _: &VertexId) -> bool {
+    true
+}
+
+fn any_edge(_: &Edge, _: &Edge) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn is_isomorphic_should_ignore_vertex_ids() {
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1), (3, 4)]);
+        let relabeled = graph_of(vec![(30, 10), (10, 40), (40, 30), (40, 20)]);
+        let mapping = isomorphism(&g, &relabeled).unwrap();
+        assert_eq!(mapping[&VertexId(3)], VertexId(40));
+        assert_eq!(mapping[&VertexId(4)], VertexId(20));
+        for Edge(src, dst) in g.edges() {
+            assert!(relabeled.contains_edge(Edge(mapping[src], mapping[dst])));
+        }
+    }
+
+    #[test]
+    fn is_isomorphic_should_detect_different_structures() {
+        // Same number of vertices, edges and degrees, but different orientations
+        let g = graph_of(vec![(1, 2), (2, 3), (3, 1), (3, 4)]);
+        let other = graph_of(vec![(1, 2), (2, 3), (3, 1), (4, 3)]);
+        assert!(!is_isomorphic(&g, &other));
+        assert!(!is_isomorphic(&g, &graph_of(vec![(1, 2), (2, 3), (3, 1)])));
+        assert!(is_isomorphic(&DirectedGraph::new(), &DirectedGraph::new()));
+    }
+
+    #[test]
+    fn subgraph_monomorphisms_should_find_all_the_diamonds() {
+        let pattern = build_diamond();
+        // Diamond 1 -> {2, 3} -> 4 with an extra edge 2 -> 3, and diamond 4 -> {5, 6} -> 7
+        let target = graph_of(vec![
+            (1, 2),
+            (1, 3),
+            (2, 4),
+            (3, 4),
+            (2, 3),
+            (4, 5),
+            (4, 6),
+            (5, 7),
+            (6, 7),
+        ]);
+        let mut tops: Vec<VertexId> = subgraph_monomorphisms(&pattern, &target)
+            .map(|m| m[&VertexId(1)])
+            .collect();
+        tops.sort();
+        // Each diamond matches twice, as the pattern is symmetric
+        assert_eq!(tops, vertices(vec![1, 1, 4, 4]));
+    }
+
+    #[test]
+    fn subgraph_isomorphisms_should_reject_extra_edges_between_matched_vertices() {
+        let pattern = build_diamond();
+        let target = graph_of(vec![
+            (1, 2),
+            (1, 3),
+            (2, 4),
+            (3, 4),
+            (2, 3),
+            (4, 5),
+            (4, 6),
+            (5, 7),
+            (6, 7),
+        ]);
+        let mut tops: Vec<VertexId> = subgraph_isomorphisms(&pattern, &target)
+            .map(|m| m[&VertexId(1)])
+            .collect();
+        tops.sort();
+        assert_eq!(tops, vertices(vec![4, 4]));
+    }
+
+    #[test]
+    fn iter_matches_should_apply_the_predicates() {
+        let pattern = graph_of(vec![(1, 2)]);
+        let target = graph_of(vec![(10, 11), (11, 12), (12, 13)]);
+        let even_source = |_: &VertexId, t: &VertexId| t.0.is_multiple_of(2);
+        let matches: Vec<Mapping> = iter_matches(
+            &pattern,
+            &target,
+            MatchKind::Monomorphism,
+            |p, t| p.0 != 1 || even_source(p, t),
+            |_, t| t.1 != VertexId(11),
+        )
+        .collect();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0][&VertexId(1)], VertexId(12));
+    }
+
+    #[test]
+    fn self_loops_should_only_match_self_loops() {
+        let pattern = graph_of(vec![(1, 1), (1, 2)]);
+        let target = graph_of(vec![(1, 2), (2, 2), (2, 3)]);
+        let matches: Vec<Mapping> = subgraph_monomorphisms(&pattern, &target).collect();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0][&VertexId(1)], VertexId(2));
+        // Both edges of the target go through the self loop on 2
+        let edge_pattern = graph_of(vec![(1, 2)]);
+        assert_eq!(subgraph_monomorphisms(&edge_pattern, &target).count(), 2);
+        assert_eq!(subgraph_isomorphisms(&edge_pattern, &target).count(), 0);
+    }
+
+    #[test]
+    fn empty_pattern_should_match_once() {
+        let target = build_diamond();
+        assert_eq!(
+            subgraph_monomorphisms(&DirectedGraph::new(), &target).count(),
+            1
+        );
+    }
+
+    // Helpers
+
+    // 1 -> 2 -> 4 and 1 -> 3 -> 4
+    fn build_diamond() -> DirectedGraph {
+        graph_of(vec![(1, 2), (1, 3), (2, 4), (3, 4)])
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
@@ -2,38 +2,57 @@
 use crate::algorithm::shortest_path::dag_shortest_paths;
 use crate::algorithm::topo_sort::DAG;
 
-use crate::graph::{Edge, VertexId};
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::path::ScoredPath;
+use crate::weight::{Overflow, SignedWeight};
 use std::collections::hash_map::HashMap;
 
 /// Finds the longest path from a source to a target vertex in a DAG
-pub fn dag_longest_path<F>(
-    dag: DAG,
+/// Returns None if the target is not reachable, and an error if a score overflows
+pub fn dag_longest_path<G, F, W>(
+    dag: DAG<G>,
     scorefn: F,
     start: VertexId,
     end: VertexId,
-) -> Option<ScoredPath>
+) -> Result<Option<ScoredPath<W>>, Overflow>
 where
-    F: Fn(&Edge) -> i64,
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: SignedWeight,
 {
-    dag_longest_paths(dag, scorefn, start).remove(&end)
+    dag_longest_paths(dag, scorefn, start).map(|mut scores| scores.remove(&end))
 }
 
 /// Finds the longest path from a source to all reachable vertices in a DAG
-/// This reusing dag_shortest_paths and works by negating all scores.
-pub fn dag_longest_paths<F>(dag: DAG, scorefn: F, start: VertexId) -> HashMap<VertexId, ScoredPath>
+/// This reusing dag_shortest_paths and works by negating all scores, so weights must be signed.
+/// Returns an error if a score or its negation overflows the Weight type
+pub fn dag_longest_paths<G, F, W>(
+    dag: DAG<G>,
+    scorefn: F,
+    start: VertexId,
+) -> Result<HashMap<VertexId, ScoredPath<W>>, Overflow>
 where
-    F: Fn(&Edge) -> i64,
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: SignedWeight,
 {
-    let negated_score_fn = |e: &Edge| -1 * scorefn(e);
-    negate_scores(dag_shortest_paths(dag, negated_score_fn, start))
+    let negated_scores = dag
+        .as_graph()
+        .edges()
+        .map(|edge| scorefn(&edge).checked_neg().map(|score| (edge, score)))
+        .collect::<Option<HashMap<Edge, W>>>()
+        .ok_or(Overflow)?;
+    let negated_score_fn = |e: &Edge| negated_scores[e];
+    negate_scores(dag_shortest_paths(dag, negated_score_fn, start)?)
 }
 
-fn negate_scores(mut scores: HashMap<VertexId, ScoredPath>) -> HashMap<VertexId, ScoredPath> {
+fn negate_scores<W: SignedWeight>(
+    mut scores: HashMap<VertexId, ScoredPath<W>>,
+) -> Result<HashMap<VertexId, ScoredPath<W>>, Overflow> {
     for (_, sp) in &mut scores {
-        sp.score = -1 * sp.score;
+        sp.score = sp.score.checked_neg().ok_or(Overflow)?;
     }
-    scores
+    Ok(scores)
 }
 
 #[cfg(test)]
@@ -52,7 +71,10 @@ mod tests {
             VertexId(1),
             VertexId(8),
         );
-        assert_eq!(shortest_path, Some(scored_path_of(23, vec![1, 2, 5, 8])));
+        assert_eq!(
+            shortest_path,
+            Ok(Some(scored_path_of(23, vec![1, 2, 5, 8])))
+        );
     }
 
     #[test]
@@ -60,7 +82,7 @@ mod tests {
         let (g, scorefn) = build_test_weighted_dag();
 
         let all_longest_paths_from_1 =
-            dag_longest_paths(topo_sort::try_dag(&g).unwrap(), scorefn, VertexId(1));
+            dag_longest_paths(topo_sort::try_dag(&g).unwrap(), scorefn, VertexId(1)).unwrap();
 
         assert_eq!(all_longest_paths_from_1.len(), 8);
         assert_eq!(
@@ -97,6 +119,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dag_longest_paths_should_detect_overflows() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(Edge(VertexId(1), VertexId(2)));
+        g.add_edge(Edge(VertexId(2), VertexId(3)));
+        let dag = topo_sort::try_dag(&g).unwrap();
+        assert_eq!(
+            dag_longest_paths(dag, |_| i64::MAX, VertexId(1)),
+            Err(Overflow)
+        );
+        let dag = topo_sort::try_dag(&g).unwrap();
+        assert_eq!(
+            dag_longest_paths(dag, |_| i64::MIN, VertexId(1)),
+            Err(Overflow)
+        );
+    }
+
     // Helpers
 
     // Graph taken from https://www.youtube.com/watch?v=TXkDpqjDMHA
@@ -0,0 +1,457 @@
+//! Bipartite matching and weighted assignment
+use crate::algorithm::max_flow::Cost;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use crate::iter::iter_datastructure::{Queue, SearchQueue};
+use std::collections::HashMap;
+
+/// Partition of the vertices of a bipartite graph
+/// All the edges are going from a left vertex to a right vertex.
+/// Isolated vertices are on the left side
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Bipartition {
+    pub left: Vec<VertexId>,
+    pub right: Vec<VertexId>,
+}
+
+/// Result of a weighted assignment
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Assignment {
+    /// Edges of the matching, sorted
+    pub edges: Vec<Edge>,
+    /// Total cost of the matching edges
+    pub cost: Cost,
+}
+
+/// Checks that all edges are going from a left vertex to a right vertex
+pub fn is_bipartite<G: GraphView>(graph: &G) -> bool {
+    bipartition(graph).is_some()
+}
+
+/// Splits the vertices into the sources (left) and the targets (right) of the edges
+/// Returns None if a vertex has both inbound and outbound edges
+pub fn bipartition<G: GraphView>(graph: &G) -> Option<Bipartition> {
+    let mut left = vec![];
+    let mut right = vec![];
+    for vid in graph.vertices() {
+        match (graph.degree_in(vid), graph.degree_out(vid)) {
+            (0, _) => left.push(vid),
+            (_, 0) => right.push(vid),
+            _ => return None,
+        }
+    }
+    left.sort();
+    right.sort();
+    Some(Bipartition { left, right })
+}
+
+/// Maximum cardinality matching using Hopcroft-Karp algorithm
+/// Returns None if the graph is not bipartite
+/// Runs in O(E * sqrt(V))
+/// See https://en.wikipedia.org/wiki/Hopcroft%E2%80%93Karp_algorithm
+pub fn hopcroft_karp<G: GraphView>(graph: &G) -> Option<Vec<Edge>> {
+    let bipartition = bipartition(graph)?;
+    let mut matcher = HopcroftKarp::new(graph, &bipartition);
+    matcher.run();
+    let mut edges: Vec<Edge> = matcher
+        .pair_left
+        .iter()
+        .enumerate()
+        .filter_map(|(u, v)| v.map(|v| Edge(bipartition.left[u], bipartition.right[v])))
+        .collect();
+    edges.sort();
+    Some(edges)
+}
+
+/// Minimum cost matching among the matchings of maximum cardinality, using the Hungarian algorithm
+/// Costs may be negative. To maximize a score, use the opposite of the score as the cost.
+/// Returns None if the graph is not bipartite, or if the costs are too large to be summed
+/// Runs in O(L^2 * R) where L is the size of the smallest side
+/// See https://en.wikipedia.org/wiki/Hungarian_algorithm
+pub fn hungarian<G, WFn>(graph: &G, cost: WFn) -> Option<Assignment>
+where
+    G: GraphView,
+    WFn: Fn(&Edge) -> Cost,
+{
+    let Bipartition { left, right } = bipartition(graph)?;
+    // The algorithm assigns each row to a distinct column, so rows are the smallest side
+    let transposed = left.len() > right.len();
+    let (rows, columns) = if transposed {
+        (&right, &left)
+    } else {
+        (&left, &right)
+    };
+    let edge_of = |row: usize, column: usize| {
+        if transposed {
+            Edge(columns[column], rows[row])
+        } else {
+            Edge(rows[row], columns[column])
+        }
+    };
+    // Missing edges get a cost high enough to only be used when no other choice is left
+    let forbidden: Cost = graph.edges().try_fold(1 as Cost, |total, e| {
+        cost(&e).checked_abs().and_then(|c| total.checked_add(c))
+    })?;
+    let mut costs: Vec<Vec<Option<Cost>>> = vec![vec![None; columns.len()]; rows.len()];
+    let row_index: HashMap<VertexId, usize> =
+        rows.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let column_index: HashMap<VertexId, usize> =
+        columns.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    for edge in graph.edges() {
+        let Edge(src, dst) = edge;
+        let (row, column) = if transposed {
+            (row_index[&dst], column_index[&src])
+        } else {
+            (row_index[&src], column_index[&dst])
+        };
+        costs[row][column] = Some(cost(&edge));
+    }
+    let matrix: Vec<Vec<Cost>> = costs
+        .iter()
+        .map(|row| row.iter().map(|c| c.unwrap_or(forbidden)).collect())
+        .collect();
+
+    let mut edges = vec![];
+    let mut total: Cost = 0;
+    for (row, column) in assign(&matrix, columns.len()).into_iter().enumerate() {
+        if let Some(cost) = costs[row][column] {
+            edges.push(edge_of(row, column));
+            total = total.checked_add(cost)?;
+        }
+    }
+    edges.sort();
+    Some(Assignment { edges, cost: total })
+}
+
+// Hopcroft-Karp
+
+struct HopcroftKarp {
+    adjacency: Vec<Vec<usize>>,
+    pair_left: Vec<Option<usize>>,
+    pair_right: Vec<Option<usize>>,
+    // Layer of each left vertex in the last breadth-first search
+    layers: Vec<Option<usize>>,
+}
+
+impl HopcroftKarp {
+    fn new<G: GraphView>(graph: &G, bipartition: &Bipartition) -> HopcroftKarp {
+        let right_index: HashMap<VertexId, usize> = bipartition
+            .right
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (*v, i))
+            .collect();
+        let adjacency = bipartition
+            .left
+            .iter()
+            .map(|u| {
+                let mut targets: Vec<usize> = graph
+                    .outbound_edges(*u)
+                    .map(|Edge(_, v)| right_index[&v])
+                    .collect();
+                targets.sort_unstable();
+                targets
+            })
+            .collect();
+        HopcroftKarp {
+            adjacency,
+            pair_left: vec![None; bipartition.left.len()],
+            pair_right: vec![None; bipartition.right.len()],
+            layers: vec![None; bipartition.left.len()],
+        }
+    }
+
+    fn run(&mut self) {
+        while let Some(free_layer) = self.layers() {
+            let mut next_arc = vec![0; self.adjacency.len()];
+            for u in 0..self.adjacency.len() {
+                if self.pair_left[u].is_none() {
+                    self.augment_from(u, free_layer, &mut next_arc);
+                }
+            }
+        }
+    }
+
+    // Breadth-first search from the free left vertices, alternating unmatched and matched edges
+    // Returns the layer at which a free right vertex is reached, if any
+    fn layers(&mut self) -> Option<usize> {
+        let mut queue: Queue<usize> = Queue::<usize>::new();
+        for u in 0..self.adjacency.len() {
+            self.layers[u] = match self.pair_left[u] {
+                None => Some(0),
+                Some(_) => None,
+            };
+            if self.pair_left[u].is_none() {
+                queue.push(u);
+            }
+        }
+        let mut free_layer = None;
+        while let Some(u) = queue.pop() {
+            let layer = self.layers[u].expect("Queued vertices have a layer");
+            if free_layer.is_some_and(|free| layer >= free) {
+                continue;
+            }
+            for v in &self.adjacency[u] {
+                match self.pair_right[*v] {
+                    None => free_layer = free_layer.or(Some(layer + 1)),
+                    Some(w) if self.layers[w].is_none() => {
+                        self.layers[w] = Some(layer + 1);
+                        queue.push(w);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        free_layer
+    }
+
+    // Iterative depth-first search of an augmenting path in the layered graph
+    fn augment_from(&mut self, root: usize, free_layer: usize, next_arc: &mut [usize]) -> bool {
+        let mut stack: Vec<usize> = vec![root];
+        let mut chosen: Vec<usize> = vec![];
+        while let Some(&u) = stack.last() {
+            if next_arc[u] == self.adjacency[u].len() {
+                // Dead end: u is removed from the layered graph
+                self.layers[u] = None;
+                stack.pop();
+                chosen.pop();
+                continue;
+            }
+            let v = self.adjacency[u][next_arc[u]];
+            next_arc[u] += 1;
+            let layer = self.layers[u].map(|l| l + 1);
+            match self.pair_right[v] {
+                None if layer == Some(free_layer) => {
+                    chosen.push(v);
+                    for (u, v) in stack.iter().zip(chosen.iter()) {
+                        self.pair_left[*u] = Some(*v);
+                        self.pair_right[*v] = Some(*u);
+                    }
+                    return true;
+                }
+                Some(w) if self.layers[w] == layer => {
+                    chosen.push(v);
+                    stack.push(w);
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+}
+
+// Hungarian algorithm
+
+// Assigns each row of the matrix to a distinct column minimizing the total cost
+// The matrix must not have more rows than columns. Returns the column of each row
+fn assign(matrix: &[Vec<Cost>], columns: usize) -> Vec<usize> {
+    let rows = matrix.len();
+    // Potentials of rows and columns, plus a virtual column 0 holding the row being assigned
+    let mut row_potential: Vec<Cost> = vec![0; rows + 1];
+    let mut column_potential: Vec<Cost> = vec![0; columns + 1];
+    let mut row_of_column: Vec<usize> = vec![0; columns + 1];
+    let mut way: Vec<usize> = vec![0; columns + 1];
+    for row in 1..=rows {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut min_slack: Vec<Option<Cost>> = vec![None; columns + 1];
+        let mut used = vec![false; columns + 1];
+        loop {
+            used[column] = true;
+            let current_row = row_of_column[column];
+            let mut delta: Option<Cost> = None;
+            let mut next_column = 0;
+            for j in 1..=columns {
+                if used[j] {
+                    continue;
+                }
+                let slack = matrix[current_row - 1][j - 1]
+                    - row_potential[current_row]
+                    - column_potential[j];
+                if min_slack[j].is_none_or(|min| slack < min) {
+                    min_slack[j] = Some(slack);
+                    way[j] = column;
+                }
+                if delta.is_none_or(|d| min_slack[j].unwrap() < d) {
+                    delta = min_slack[j];
+                    next_column = j;
+                }
+            }
+            let delta = delta.expect("There are at least as many columns as rows");
+            for j in 0..=columns {
+                if used[j] {
+                    row_potential[row_of_column[j]] += delta;
+                    column_potential[j] -= delta;
+                } else if let Some(slack) = min_slack[j].as_mut() {
+                    *slack -= delta;
+                }
+            }
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+        // Flips the alternating path ending on the free column
+        while column != 0 {
+            let previous = way[column];
+            row_of_column[column] = row_of_column[previous];
+            column = previous;
+        }
+    }
+    let mut column_of_row = vec![0; rows];
+    for j in 1..=columns {
+        if row_of_column[j] != 0 {
+            column_of_row[row_of_column[j] - 1] = j - 1;
+        }
+    }
+    column_of_row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn bipartition_should_split_sources_and_targets_of_the_edges() {
+        let mut g = graph_of(vec![(1, 10), (1, 11), (2, 11)]);
+        g.add_vertex(VertexId(3));
+        assert_eq![
+            bipartition(&g),
+            Some(Bipartition {
+                left: vertices(vec![1, 2, 3]),
+                right: vertices(vec![10, 11]),
+            })
+        ];
+    }
+
+    #[test]
+    fn bipartition_should_fail_when_a_vertex_has_inbound_and_outbound_edges() {
+        let g = graph_of(vec![(1, 10), (10, 2)]);
+        assert_eq![bipartition(&g), None];
+        assert![!is_bipartite(&g)];
+        assert_eq![hopcroft_karp(&g), None];
+    }
+
+    #[test]
+    fn hopcroft_karp_should_find_a_maximum_matching() {
+        // A greedy matching 1-10, 2-11 would prevent 3 from being matched
+        let g = graph_of(vec![(1, 10), (1, 11), (2, 11), (2, 12), (3, 10)]);
+        let matching = hopcroft_karp(&g).unwrap();
+        assert_eq![matching, vec![edge(1, 11), edge(2, 12), edge(3, 10)]];
+    }
+
+    #[test]
+    fn hopcroft_karp_should_leave_vertices_unmatched_when_there_are_not_enough_partners() {
+        let g = graph_of(vec![(1, 10), (2, 10), (3, 10), (3, 11)]);
+        let matching = hopcroft_karp(&g).unwrap();
+        assert_eq![matching.len(), 2];
+        assert_valid_matching(&g, &matching);
+    }
+
+    #[test]
+    fn hopcroft_karp_should_work_on_a_big_number_of_vertices() {
+        // Each left vertex i can be matched to right vertices i and i + 1
+        let n = 10000;
+        let mut edges = vec![];
+        for i in 0..n {
+            edges.push((i, n + i));
+            edges.push((i, n + i + 1));
+        }
+        let g = graph_of(edges);
+        let matching = hopcroft_karp(&g).unwrap();
+        assert_eq![matching.len(), n as usize];
+        assert_valid_matching(&g, &matching);
+    }
+
+    #[test]
+    fn hungarian_should_find_the_assignment_of_minimum_cost() {
+        let (g, costfn) = weighted_graph_of(vec![
+            (1, 10, 4),
+            (1, 11, 1),
+            (1, 12, 3),
+            (2, 10, 2),
+            (2, 11, 0),
+            (2, 12, 5),
+            (3, 10, 3),
+            (3, 11, 2),
+            (3, 12, 2),
+        ]);
+        let assignment = hungarian(&g, costfn).unwrap();
+        assert_eq![assignment.cost, 5];
+        assert_eq![
+            assignment.edges,
+            vec![edge(1, 11), edge(2, 10), edge(3, 12)]
+        ];
+    }
+
+    #[test]
+    fn hungarian_should_prefer_a_bigger_matching_over_a_cheaper_one() {
+        // Matching 1-10 alone is cheaper, but leaves 2 unmatched
+        let (g, costfn) = weighted_graph_of(vec![(1, 10, 1), (1, 11, 5), (2, 10, 5)]);
+        let assignment = hungarian(&g, costfn).unwrap();
+        assert_eq![assignment.cost, 10];
+        assert_eq![assignment.edges, vec![edge(1, 11), edge(2, 10)]];
+    }
+
+    #[test]
+    fn hungarian_should_handle_more_left_vertices_than_right_vertices() {
+        let (g, costfn) = weighted_graph_of(vec![(1, 10, 7), (2, 10, 3), (3, 10, -2), (3, 11, 4)]);
+        let assignment = hungarian(&g, costfn).unwrap();
+        assert_eq![assignment.cost, 7];
+        assert_eq![assignment.edges, vec![edge(2, 10), edge(3, 11)]];
+    }
+
+    #[test]
+    fn hungarian_should_maximize_with_opposite_costs() {
+        let (g, costfn) = weighted_graph_of(vec![(1, 10, 4), (1, 11, 1), (2, 10, 2), (2, 11, 0)]);
+        let assignment = hungarian(&g, |e| -costfn(e)).unwrap();
+        assert_eq![assignment.cost, -4];
+        assert_eq![assignment.edges, vec![edge(1, 10), edge(2, 11)]];
+    }
+
+    #[test]
+    fn hungarian_should_be_none_when_the_costs_overflow() {
+        let (g, costfn) = weighted_graph_of(vec![(1, 10, Cost::MAX), (2, 11, 1)]);
+        assert_eq![hungarian(&g, costfn), None];
+        let (g, costfn) = weighted_graph_of(vec![(1, 10, Cost::MIN)]);
+        assert_eq![hungarian(&g, costfn), None];
+    }
+
+    // Helpers
+
+    fn assert_valid_matching(g: &DirectedGraph, matching: &[Edge]) {
+        let mut matched = std::collections::HashSet::new();
+        for Edge(src, dst) in matching {
+            assert![g.contains_edge(Edge(*src, *dst))];
+            assert![matched.insert(*src), "{:?} is matched twice", src];
+            assert![matched.insert(*dst), "{:?} is matched twice", dst];
+        }
+    }
+
+    fn weighted_graph_of(edges: Vec<(u64, u64, Cost)>) -> (DirectedGraph, impl Fn(&Edge) -> Cost) {
+        let g = graph_of(edges.iter().map(|(src, dst, _)| (*src, *dst)).collect());
+        let costs: HashMap<Edge, Cost> = edges
+            .iter()
+            .map(|(src, dst, c)| (edge(*src, *dst), *c))
+            .collect();
+        (g, move |e: &Edge| costs[e])
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
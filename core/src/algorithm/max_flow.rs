@@ -1,228 +1,434 @@
-use crate::directed_graph::DirectedGraph;
+//! Maximum flow and minimum cut
 use crate::graph::Edge;
+use crate::graph::GraphView;
 use crate::graph::VertexId;
 use crate::iter::iter_datastructure::{Queue, SearchQueue};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-type Flow = u64;
-type Capacity = u64;
+pub type Flow = u64;
+pub type Capacity = u64;
+pub type Cost = i64;
 type ResidualCapacity = u64;
 
-/// Direction of an edge in an Augmenting path
-#[derive(Copy, PartialEq, Eq, Hash, Clone, Debug)]
-enum Direction {
-    Forward,
-    Backward,
+/// Result of a maximum flow computation
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MaxFlow {
+    /// Value of the maximum flow
+    pub value: Flow,
+    /// Flow and capacity of each edge of the graph
+    pub flows: HashMap<Edge, (Flow, Capacity)>,
+    /// Minimum cut matching the maximum flow
+    pub cut: MinCut,
 }
 
-/// An augmenting path is a path that have a residual capacity
-/// It cannot be empty
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
-struct AugmentingPath {
-    path: Vec<(VertexId, Direction)>,
+/// Minimum cut separating the sources from the sinks
+/// The total capacity of the cut edges is equal to the value of the maximum flow
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MinCut {
+    /// Edges going from the source side to the sink side. They are all saturated
+    pub edges: Vec<Edge>,
+    /// Vertices still reachable from the sources in the residual graph
+    pub source_side: HashSet<VertexId>,
+    /// All the other vertices of the graph
+    pub sink_side: HashSet<VertexId>,
 }
 
-impl AugmentingPath {
-    fn new(start: VertexId) -> AugmentingPath {
-        AugmentingPath {
-            path: vec![(start, Direction::Forward)],
-        }
-    }
-
-    fn edges(&self) -> impl Iterator<Item = (Edge, Direction)> + '_ {
-        let tail = self.path.iter().skip(1);
-        self.path
-            .iter()
-            .zip(tail)
-            .map(|((src, _), (dst, dir))| (Edge(*src, *dst), *dir))
-    }
-
-    fn append(&self, vid: VertexId, direction: Direction) -> AugmentingPath {
-        let mut cloned = self.path.clone();
-        cloned.push((vid, direction));
-        AugmentingPath { path: cloned }
-    }
+/// Maximum flow
+/// Returns the value of the flow along with the flow and capacity of each edge
+/// Implementation of Edmonds-Karp algorithm (Ford-Fulkerson using shortest augmenting paths)
+pub fn max_flow<G, CFn>(
+    graph: &G,
+    capacity: CFn,
+    start: VertexId,
+    end: VertexId,
+) -> (Flow, HashMap<Edge, (Flow, Capacity)>)
+where
+    G: GraphView,
+    CFn: Fn(&Edge) -> Capacity,
+{
+    let MaxFlow { value, flows, .. } = edmonds_karp(graph, capacity, start, end);
+    (value, flows)
+}
 
-    fn contains_vertex(&self, vid: VertexId) -> bool {
-        self.path.iter().find(|(v, _)| vid == *v).is_some()
-    }
+/// Maximum flow and minimum cut using Edmonds-Karp algorithm
+/// Runs in O(VE^2)
+/// See https://en.wikipedia.org/wiki/Edmonds%E2%80%93Karp_algorithm
+pub fn edmonds_karp<G, CFn>(graph: &G, capacity: CFn, start: VertexId, end: VertexId) -> MaxFlow
+where
+    G: GraphView,
+    CFn: Fn(&Edge) -> Capacity,
+{
+    let mut residual = ResidualGraph::new(graph, capacity);
+    let source = residual.index_of(start);
+    let value = match (source, residual.index_of(end)) {
+        (Some(source), Some(sink)) if source != sink => residual.edmonds_karp(source, sink),
+        _ => 0,
+    };
+    residual.max_flow(value, source)
+}
 
-    fn last(&self) -> Option<&(VertexId, Direction)> {
-        self.path.last()
+/// Maximum flow and minimum cut using Dinic algorithm
+/// Runs in O(V^2E), and much faster in practice on unit capacity networks
+/// See https://en.wikipedia.org/wiki/Dinic%27s_algorithm
+pub fn dinic<G, CFn>(graph: &G, capacity: CFn, start: VertexId, end: VertexId) -> MaxFlow
+where
+    G: GraphView,
+    CFn: Fn(&Edge) -> Capacity,
+{
+    if start == end {
+        ResidualGraph::new(graph, capacity).max_flow(0, None)
+    } else {
+        multi_source_max_flow(graph, capacity, &[start], &[end])
+            .expect("Start and end are different vertices")
     }
 }
 
-/// Maximum flow
-/// Implementation of Ford-Fulkerson algorithm
-pub fn max_flow<CFn>(
-    graph: &DirectedGraph,
+/// Maximum flow and minimum cut between several sources and several sinks
+/// A super-source connected to all sources and a super-sink connected from all sinks are added to
+/// the residual graph, and the flow between them is computed using Dinic algorithm.
+/// Returns None if a vertex is both a source and a sink, as the flow would be unbounded
+pub fn multi_source_max_flow<G, CFn>(
+    graph: &G,
     capacity: CFn,
-    start: VertexId,
-    end: VertexId,
-) -> (Flow, HashMap<Edge, (Flow, Capacity)>)
+    sources: &[VertexId],
+    sinks: &[VertexId],
+) -> Option<MaxFlow>
 where
+    G: GraphView,
     CFn: Fn(&Edge) -> Capacity,
 {
-    // Initialising current flow to 0 everywhere
-    let mut current_flow: HashMap<Edge, (Flow, Capacity)> = HashMap::new();
-    for edge in graph.edges() {
-        current_flow.insert(*edge, (0, capacity(edge)));
+    if sources.iter().any(|source| sinks.contains(source)) {
+        return None;
     }
-    let mut max_flow = 0;
-    // As long a we can find an augmenting path,
-    // we can update the flow along the path using the residual capacity
-    while let Some((path, residual_cap)) = find_augmenting_path(&graph, &current_flow, start, end) {
-        update_flow(&mut current_flow, path, residual_cap);
-        max_flow = max_flow + residual_cap;
+    let mut residual = ResidualGraph::new(graph, capacity);
+    let super_source = residual.add_vertex(None);
+    let super_sink = residual.add_vertex(None);
+    // Super arcs have an unbounded capacity, so they can never be part of the minimum cut
+    for source in sources {
+        if let Some(index) = residual.index_of(*source) {
+            residual.add_arc(super_source, index, Capacity::MAX, 0, None);
+        }
     }
-
-    (max_flow, current_flow)
+    for sink in sinks {
+        if let Some(index) = residual.index_of(*sink) {
+            residual.add_arc(index, super_sink, Capacity::MAX, 0, None);
+        }
+    }
+    let value = residual.dinic(super_source, super_sink);
+    Some(residual.max_flow(value, Some(super_source)))
 }
 
-/// Finds an Augmenting Path
-fn find_augmenting_path(
-    graph: &DirectedGraph,
-    current_flow: &HashMap<Edge, (Flow, Capacity)>,
-    start: VertexId,
-    target: VertexId,
-) -> Option<(AugmentingPath, ResidualCapacity)> {
-    // !!! Highly inefficent !!!
-    // But using the iterator directly would borrow the current flow as immuatble,
-    // when a mutable borrow would be needed to update the map afterwards ...
-    iter_augmenting_path(graph, &current_flow, start, target).next()
+/// An arc of the residual graph
+/// Each edge of the graph is represented by 2 arcs: a forward arc at an even index,
+/// and its backward arc stored right after it (ie at index ^ 1)
+#[derive(Clone, Debug)]
+pub(crate) struct Arc {
+    pub(crate) to: usize,
+    pub(crate) capacity: Capacity,
+    pub(crate) residual: ResidualCapacity,
+    // Cost of a unit of flow. A backward arc costs the opposite of its forward arc
+    pub(crate) cost: Cost,
+    // Edge of the graph this arc is the forward arc of
+    pub(crate) edge: Option<Edge>,
 }
 
-/// Iter on augmenting paths
-fn iter_augmenting_path<'a>(
-    graph: &'a DirectedGraph,
-    current_flow: &'a HashMap<Edge, (Flow, Capacity)>,
-    start: VertexId,
-    target: VertexId,
-) -> AugmentingPathIter<'a> {
-    AugmentingPathIter::new(graph, current_flow, start, target)
+/// Residual graph used by the flow algorithms
+/// Vertices are indexed to avoid hashing while looking for augmenting paths.
+/// Vertices added on top of the graph (super-source, super-sink) have no VertexId
+pub(crate) struct ResidualGraph {
+    pub(crate) vertices: Vec<Option<VertexId>>,
+    indices: HashMap<VertexId, usize>,
+    pub(crate) adjacency: Vec<Vec<usize>>,
+    pub(crate) arcs: Vec<Arc>,
 }
 
-fn update_flow(
-    current_flow: &mut HashMap<Edge, (Flow, Capacity)>,
-    path: AugmentingPath,
-    delta: ResidualCapacity,
-) {
-    use Direction::*;
-    for (edge, direction) in path.edges() {
-        match direction {
-            Forward => current_flow
-                .entry(edge)
-                .and_modify(|flow_cap| flow_cap.0 += delta),
-            Backward => current_flow
-                .entry(edge.reverse())
-                .and_modify(|flow_cap| flow_cap.0 -= delta),
+impl ResidualGraph {
+    pub(crate) fn new<G, CFn>(graph: &G, capacity: CFn) -> ResidualGraph
+    where
+        G: GraphView,
+        CFn: Fn(&Edge) -> Capacity,
+    {
+        ResidualGraph::with_costs(graph, capacity, |_: &Edge| 0)
+    }
+
+    pub(crate) fn with_costs<G, CFn, WFn>(graph: &G, capacity: CFn, cost: WFn) -> ResidualGraph
+    where
+        G: GraphView,
+        CFn: Fn(&Edge) -> Capacity,
+        WFn: Fn(&Edge) -> Cost,
+    {
+        let mut residual = ResidualGraph {
+            vertices: vec![],
+            indices: HashMap::new(),
+            adjacency: vec![],
+            arcs: vec![],
         };
+        for vid in graph.vertices() {
+            let index = residual.add_vertex(Some(vid));
+            residual.indices.insert(vid, index);
+        }
+        let mut arc_of_edge: HashMap<Edge, usize> = HashMap::new();
+        for edge in graph.edges() {
+            let arc = residual.arcs.len();
+            residual.push_arc_pair(
+                residual.indices[&edge.0],
+                residual.indices[&edge.1],
+                capacity(&edge),
+                cost(&edge),
+                Some(edge),
+            );
+            arc_of_edge.insert(edge, arc);
+        }
+        // Outbound edges are explored before inbound ones, following the graph adjacency order
+        for vid in graph.vertices() {
+            let index = residual.indices[&vid];
+            let forward = graph.outbound_edges(vid).map(|e| arc_of_edge[&e]);
+            let backward = graph.inbound_edges(vid).map(|e| arc_of_edge[&e] ^ 1);
+            residual.adjacency[index] = forward.chain(backward).collect();
+        }
+        residual
     }
-}
 
-/// Computes the residual capacity of an Augmenting Path
-fn residual_capacity(
-    path: &AugmentingPath,
-    current_flow: &HashMap<Edge, (Flow, Capacity)>,
-) -> ResidualCapacity {
-    path.edges()
-        .map(|(e, dir)| edge_residual_capacity(&e, &dir, current_flow))
-        .min()
-        .expect("An augmenting path cannot be empty")
-}
+    pub(crate) fn index_of(&self, vid: VertexId) -> Option<usize> {
+        self.indices.get(&vid).copied()
+    }
+
+    pub(crate) fn add_vertex(&mut self, vid: Option<VertexId>) -> usize {
+        self.vertices.push(vid);
+        self.adjacency.push(vec![]);
+        self.vertices.len() - 1
+    }
+
+    pub(crate) fn add_arc(
+        &mut self,
+        from: usize,
+        to: usize,
+        capacity: Capacity,
+        cost: Cost,
+        edge: Option<Edge>,
+    ) {
+        let arc = self.arcs.len();
+        self.push_arc_pair(from, to, capacity, cost, edge);
+        self.adjacency[from].push(arc);
+        self.adjacency[to].push(arc ^ 1);
+    }
+
+    fn push_arc_pair(
+        &mut self,
+        from: usize,
+        to: usize,
+        capacity: Capacity,
+        cost: Cost,
+        edge: Option<Edge>,
+    ) {
+        self.arcs.push(Arc {
+            to,
+            capacity,
+            residual: capacity,
+            cost,
+            edge,
+        });
+        self.arcs.push(Arc {
+            to: from,
+            capacity: 0,
+            residual: 0,
+            cost: -cost,
+            edge: None,
+        });
+    }
+
+    pub(crate) fn tail(&self, arc: usize) -> usize {
+        self.arcs[arc ^ 1].to
+    }
 
-/// Computes the residual capacity of an edge
-fn edge_residual_capacity(
-    edge: &Edge,
-    direction: &Direction,
-    current_flow: &HashMap<Edge, (Flow, Capacity)>,
-) -> ResidualCapacity {
-    use Direction::*;
-    match direction {
-        Forward => {
-            let (flow, cap) = current_flow
-                .get(edge)
-                .expect("All edges must have been initialized already");
-            *cap - *flow
+    fn push(&mut self, arc: usize, delta: Flow) {
+        self.arcs[arc].residual -= delta;
+        self.arcs[arc ^ 1].residual += delta;
+    }
+
+    // Pushes the bottleneck residual capacity along a path of arcs
+    pub(crate) fn augment(&mut self, path: &[usize]) -> Flow {
+        let delta = path
+            .iter()
+            .map(|arc| self.arcs[*arc].residual)
+            .min()
+            .expect("An augmenting path cannot be empty");
+        path.iter().for_each(|arc| self.push(*arc, delta));
+        delta
+    }
+
+    // Edmonds-Karp
+
+    fn edmonds_karp(&mut self, source: usize, sink: usize) -> Flow {
+        let mut value: Flow = 0;
+        while let Some(path) = self.shortest_augmenting_path(source, sink) {
+            value += self.augment(&path);
         }
-        Backward => {
-            let (flow, _) = current_flow
-                .get(&(*edge).reverse())
-                .expect("All edges must have been initialized already");
-            *flow
+        value
+    }
+
+    // Breadth-first search of the shortest path of arcs with a positive residual capacity
+    fn shortest_augmenting_path(&self, source: usize, sink: usize) -> Option<Vec<usize>> {
+        let mut parent_arc: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        let mut visited = vec![false; self.vertices.len()];
+        let mut queue: Queue<usize> = Queue::<usize>::new();
+        visited[source] = true;
+        queue.push(source);
+        while let Some(vertex) = queue.pop() {
+            if vertex == sink {
+                let mut path = vec![];
+                let mut current = sink;
+                while let Some(arc) = parent_arc[current] {
+                    path.push(arc);
+                    current = self.tail(arc);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for arc in &self.adjacency[vertex] {
+                let Arc { to, residual, .. } = self.arcs[*arc];
+                if residual > 0 && !visited[to] {
+                    visited[to] = true;
+                    parent_arc[to] = Some(*arc);
+                    queue.push(to);
+                }
+            }
         }
+        None
     }
-}
 
-/// Breadth-First Augmenting Path iterator
+    // Dinic
+
+    fn dinic(&mut self, source: usize, sink: usize) -> Flow {
+        let mut value: Flow = 0;
+        while let Some(levels) = self.levels(source, sink) {
+            // Index of the next arc to explore for each vertex, so dead-ends are never explored twice
+            let mut next_arc = vec![0; self.vertices.len()];
+            while let Some(delta) =
+                self.augment_in_level_graph(source, sink, &levels, &mut next_arc)
+            {
+                value = value.saturating_add(delta);
+            }
+        }
+        value
+    }
 
-struct AugmentingPathIter<'a> {
-    queue: Queue<AugmentingPath>,
-    graph: &'a DirectedGraph,
-    // FIXME: This map needs to be updated externally to the iterator during iteration
-    // This breaks borrowing rules
-    current_flow: &'a HashMap<Edge, (Flow, Capacity)>,
-    target: VertexId,
-}
+    // Breadth-first search computing the distance from the source in the residual graph
+    // Returns None when the sink cannot be reached anymore
+    fn levels(&self, source: usize, sink: usize) -> Option<Vec<Option<usize>>> {
+        let mut levels: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        let mut queue: Queue<usize> = Queue::<usize>::new();
+        levels[source] = Some(0);
+        queue.push(source);
+        while let Some(vertex) = queue.pop() {
+            let level = levels[vertex].map(|l| l + 1);
+            for arc in &self.adjacency[vertex] {
+                let Arc { to, residual, .. } = self.arcs[*arc];
+                if residual > 0 && levels[to].is_none() {
+                    levels[to] = level;
+                    queue.push(to);
+                }
+            }
+        }
+        levels[sink].map(|_| levels)
+    }
 
-impl<'a> AugmentingPathIter<'a> {
-    fn new(
-        graph: &'a DirectedGraph,
-        current_flow: &'a HashMap<Edge, (Flow, Capacity)>,
-        start: VertexId,
-        target: VertexId,
-    ) -> AugmentingPathIter<'a> {
-        let mut queue: Queue<AugmentingPath> = Queue::<Queue<AugmentingPath>>::new();
-        queue.push(AugmentingPath::new(start));
-        AugmentingPathIter {
-            queue,
-            graph,
-            current_flow,
-            target,
+    // Depth-first search of an augmenting path in the level graph
+    // The flow is pushed along the path found, and its value is returned
+    fn augment_in_level_graph(
+        &mut self,
+        source: usize,
+        sink: usize,
+        levels: &[Option<usize>],
+        next_arc: &mut [usize],
+    ) -> Option<Flow> {
+        let mut path: Vec<usize> = vec![];
+        let mut vertex = source;
+        while vertex != sink {
+            let admissible = self.adjacency[vertex][next_arc[vertex]..]
+                .iter()
+                .position(|arc| {
+                    let Arc { to, residual, .. } = self.arcs[*arc];
+                    residual > 0 && levels[to] == levels[vertex].map(|l| l + 1)
+                });
+            match admissible {
+                Some(offset) => {
+                    next_arc[vertex] += offset;
+                    let arc = self.adjacency[vertex][next_arc[vertex]];
+                    path.push(arc);
+                    vertex = self.arcs[arc].to;
+                }
+                None => {
+                    // Dead-end: retreating and skipping the arc leading here
+                    next_arc[vertex] = self.adjacency[vertex].len();
+                    let arc = path.pop()?;
+                    vertex = self.tail(arc);
+                    next_arc[vertex] += 1;
+                }
+            }
         }
+        Some(self.augment(&path))
     }
-}
 
-impl<'a> Iterator for AugmentingPathIter<'a> {
-    type Item = (AugmentingPath, ResidualCapacity);
-    fn next(&mut self) -> Option<Self::Item> {
-        use Direction::*;
-
-        // Looping until we have a complete path to the target
-        while let Some(path) = self.queue.pop() {
-            let (vid, _) = path
-                .last()
-                .expect("We shouldn't never have any empty path in the queue !");
-
-            let foward_edges = self
-                .graph
-                .outbound_edges(*vid)
-                // .filter(|e| !is_full(e))
-                .map(|Edge(_, v)| (v, Forward));
-            let backward_edges = self
-                .graph
-                .inbound_edges(*vid)
-                .map(|Edge(_, v)| (v, Backward));
-            // forward + backward edges
-            (foward_edges.chain(backward_edges))
-                .filter(|(v, _)| !path.contains_vertex(**v)) // avoiding cycles here
-                .for_each(|(v, dir)| self.queue.push(path.append(*v, dir)));
-
-            // Path has reach the target vertex
-            if *vid == self.target {
-                // If the residual capacity is positive => we return the path, as we can augment the flow along the path
-                // else we continue looking for other paths
-                let residual_cap = residual_capacity(&path, self.current_flow);
-                if residual_cap > 0 {
-                    return Some((path, residual_cap));
+    // Results
+
+    pub(crate) fn flows(&self) -> HashMap<Edge, (Flow, Capacity)> {
+        self.arcs
+            .iter()
+            .filter_map(|arc| {
+                arc.edge
+                    .map(|edge| (edge, (arc.capacity - arc.residual, arc.capacity)))
+            })
+            .collect()
+    }
+
+    // The source side of the cut is made of the vertices reachable from the source
+    fn min_cut(&self, source: Option<usize>) -> MinCut {
+        let mut reachable = vec![false; self.vertices.len()];
+        let mut queue: Queue<usize> = Queue::<usize>::new();
+        if let Some(source) = source {
+            reachable[source] = true;
+            queue.push(source);
+        }
+        while let Some(vertex) = queue.pop() {
+            for arc in &self.adjacency[vertex] {
+                let Arc { to, residual, .. } = self.arcs[*arc];
+                if residual > 0 && !reachable[to] {
+                    reachable[to] = true;
+                    queue.push(to);
                 }
             }
         }
+        let edges = self
+            .arcs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, arc)| match arc.edge {
+                Some(edge) if reachable[self.tail(index)] && !reachable[arc.to] => Some(edge),
+                _ => None,
+            })
+            .collect();
+        let (mut source_side, mut sink_side) = (HashSet::new(), HashSet::new());
+        for (index, vid) in self.vertices.iter().enumerate() {
+            match vid {
+                Some(vid) if reachable[index] => source_side.insert(*vid),
+                Some(vid) => sink_side.insert(*vid),
+                None => false,
+            };
+        }
+        MinCut {
+            edges,
+            source_side,
+            sink_side,
+        }
+    }
 
-        // FIXME:
-        // Here should we restart the search in case we can now find some more augmenting paths ?
-        // Or is it guaranteed that we have increased the flow to the max capacity here ?
-        None
+    fn max_flow(&self, value: Flow, source: Option<usize>) -> MaxFlow {
+        MaxFlow {
+            value,
+            flows: self.flows(),
+            cut: self.min_cut(source),
+        }
     }
 }
 
@@ -303,8 +509,157 @@ mod tests {
         assert_eq!(flow_for(6, 7), (10, 10));
     }
 
+    #[test]
+    fn edmonds_karp_should_return_a_min_cut_with_the_capacity_of_the_max_flow() {
+        let (g, capfn) = build_simple_test_flow_1();
+        let result = edmonds_karp(&g, &capfn, VertexId(0), VertexId(5));
+
+        assert_eq!(result.value, 23);
+        assert_eq!(cut_capacity(&result.cut, &capfn), 23);
+        assert_valid_flow(&g, &result, &[VertexId(0)], &[VertexId(5)]);
+    }
+
+    #[test]
+    fn dinic_should_compute_maximum_flow_in_simple_graphs() {
+        let (g1, capfn1) = build_simple_test_flow_1();
+        let result1 = dinic(&g1, &capfn1, VertexId(0), VertexId(5));
+        assert_eq!(result1.value, 23);
+        assert_valid_flow(&g1, &result1, &[VertexId(0)], &[VertexId(5)]);
+
+        let (g2, capfn2) = build_simple_test_flow_2();
+        let result2 = dinic(&g2, &capfn2, VertexId(0), VertexId(7));
+        assert_eq!(result2.value, 22);
+        assert_valid_flow(&g2, &result2, &[VertexId(0)], &[VertexId(7)]);
+
+        let (g3, capfn3) = build_simple_test_flow_3();
+        let result3 = dinic(&g3, &capfn3, VertexId(0), VertexId(7));
+        assert_eq!(result3.value, 28);
+        assert_valid_flow(&g3, &result3, &[VertexId(0)], &[VertexId(7)]);
+    }
+
+    #[test]
+    fn dinic_should_return_the_min_cut_edges_and_both_partitions() {
+        let (g, capfn) = build_simple_test_flow_1();
+        let result = dinic(&g, &capfn, VertexId(0), VertexId(5));
+
+        assert_eq!(cut_capacity(&result.cut, &capfn), 23);
+        assert_eq!(
+            result.cut.edges.iter().cloned().collect::<HashSet<Edge>>(),
+            vec![edge(1, 3), edge(4, 3), edge(4, 5)]
+                .into_iter()
+                .collect::<HashSet<Edge>>()
+        );
+        assert_eq!(result.cut.source_side, vertices(vec![0, 1, 2, 4]));
+        assert_eq!(result.cut.sink_side, vertices(vec![3, 5]));
+    }
+
+    #[test]
+    fn dinic_should_return_a_zero_flow_when_the_sink_is_not_reachable() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(0, 1));
+        g.add_edge(edge(2, 1));
+        let result = dinic(&g, |_: &Edge| 10, VertexId(0), VertexId(2));
+
+        assert_eq!(result.value, 0);
+        assert!(result.cut.edges.is_empty());
+        assert_eq!(result.cut.source_side, vertices(vec![0, 1]));
+        assert_eq!(result.cut.sink_side, vertices(vec![2]));
+    }
+
+    #[test]
+    fn dinic_should_return_a_zero_flow_when_source_and_sink_are_the_same() {
+        let (g, capfn) = build_simple_test_flow_1();
+        let result = dinic(&g, capfn, VertexId(0), VertexId(0));
+        assert_eq!(result.value, 0);
+        assert!(result.flows.values().all(|(flow, _)| *flow == 0));
+    }
+
+    #[test]
+    fn multi_source_max_flow_should_compute_the_flow_between_all_sources_and_sinks() {
+        // 2 independent networks sharing a bottleneck edge 2->3
+        let mut g = DirectedGraph::new();
+        let mut capacity: HashMap<Edge, Capacity> = HashMap::new();
+        cap_edge(&mut g, &mut capacity, 0, 2, 5);
+        cap_edge(&mut g, &mut capacity, 1, 2, 5);
+        cap_edge(&mut g, &mut capacity, 2, 3, 7);
+        cap_edge(&mut g, &mut capacity, 3, 4, 4);
+        cap_edge(&mut g, &mut capacity, 3, 5, 4);
+        cap_edge(&mut g, &mut capacity, 1, 6, 3);
+        cap_edge(&mut g, &mut capacity, 6, 5, 2);
+        let capfn = move |e: &Edge| -> Capacity { *capacity.get(e).unwrap_or(&0) };
+
+        let sources = [VertexId(0), VertexId(1)];
+        let sinks = [VertexId(4), VertexId(5)];
+        let result = multi_source_max_flow(&g, &capfn, &sources, &sinks).unwrap();
+
+        assert_eq!(result.value, 9);
+        assert_eq!(cut_capacity(&result.cut, &capfn), 9);
+        assert_valid_flow(&g, &result, &sources, &sinks);
+        assert!(result.cut.source_side.contains(&VertexId(0)));
+        assert!(result.cut.source_side.contains(&VertexId(1)));
+        assert!(result.cut.sink_side.contains(&VertexId(4)));
+        assert!(result.cut.sink_side.contains(&VertexId(5)));
+    }
+
+    #[test]
+    fn multi_source_max_flow_should_be_none_when_a_source_is_also_a_sink() {
+        let (g, capfn) = build_simple_test_flow_1();
+        let sources = [VertexId(0), VertexId(1)];
+        let sinks = [VertexId(1), VertexId(5)];
+        assert_eq!(multi_source_max_flow(&g, capfn, &sources, &sinks), None);
+    }
+
+    #[test]
+    fn dinic_should_work_on_a_big_number_of_chained_vertices() {
+        let mut g = DirectedGraph::new();
+        let n = 25000;
+        for i in 1..n {
+            g.add_edge(edge(i, i + 1));
+        }
+        let result = dinic(&g, |_: &Edge| 1, VertexId(1), VertexId(n));
+        assert_eq!(result.value, 1);
+        assert_eq!(result.cut.edges.len(), 1);
+    }
+
     // Helpers
 
+    fn cut_capacity<CFn: Fn(&Edge) -> Capacity>(cut: &MinCut, capfn: &CFn) -> Capacity {
+        cut.edges.iter().map(capfn).sum()
+    }
+
+    // Checks capacities are respected and flow is conserved everywhere but on sources and sinks
+    fn assert_valid_flow(
+        g: &DirectedGraph,
+        result: &MaxFlow,
+        sources: &[VertexId],
+        sinks: &[VertexId],
+    ) {
+        for (edge, (flow, cap)) in &result.flows {
+            assert!(flow <= cap, "Flow exceeds capacity on {:?}", edge);
+        }
+        let flow_of = |e: &Edge| result.flows.get(e).unwrap().0;
+        for vid in g.vertices() {
+            let inflow: Flow = g.inbound_edges(*vid).map(flow_of).sum();
+            let outflow: Flow = g.outbound_edges(*vid).map(flow_of).sum();
+            if !sources.contains(vid) && !sinks.contains(vid) {
+                assert_eq!(inflow, outflow, "Flow is not conserved on {:?}", vid);
+            }
+        }
+        let total_out: Flow = sources
+            .iter()
+            .map(|s| {
+                let outflow: Flow = g.outbound_edges(*s).map(flow_of).sum();
+                let inflow: Flow = g.inbound_edges(*s).map(flow_of).sum();
+                outflow - inflow
+            })
+            .sum();
+        assert_eq!(total_out, result.value);
+    }
+
+    fn vertices(ids: Vec<u64>) -> HashSet<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
     // Max flow in this test graph should be 23
     // Graph taken from https://www.geeksforgeeks.org/max-flow-problem-introduction/
     fn build_simple_test_flow_1() -> (DirectedGraph, impl Fn(&Edge) -> Capacity) {
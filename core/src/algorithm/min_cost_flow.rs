@@ -0,0 +1,395 @@
+//! Minimum cost maximum flow and transportation problems
+use crate::algorithm::max_flow::{Capacity, Cost, Flow, ResidualGraph};
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use crate::iter::iter_datastructure::{MinPriorityQueue, SearchQueue};
+use crate::weight::Overflow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Result of a minimum cost flow computation
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MinCostFlow {
+    /// Value of the flow
+    pub value: Flow,
+    /// Total cost of the flow, ie the sum of flow * cost over all edges
+    pub cost: Cost,
+    /// Flow and capacity of each edge of the graph
+    pub flows: HashMap<Edge, (Flow, Capacity)>,
+}
+
+/// Maximum flow of minimum cost between a source and a sink
+/// Costs may be negative, but the graph must not contain any cycle of negative cost
+/// Returns None if the total cost, or the cost of a path, overflows
+/// See https://en.wikipedia.org/wiki/Minimum-cost_flow_problem
+pub fn min_cost_max_flow<G, CFn, WFn>(
+    graph: &G,
+    capacity: CFn,
+    cost: WFn,
+    start: VertexId,
+    end: VertexId,
+) -> Option<MinCostFlow>
+where
+    G: GraphView,
+    CFn: Fn(&Edge) -> Capacity,
+    WFn: Fn(&Edge) -> Cost,
+{
+    let mut residual = ResidualGraph::with_costs(graph, capacity, cost);
+    let (value, cost) = match (residual.index_of(start), residual.index_of(end)) {
+        (Some(source), Some(sink)) if source != sink => {
+            successive_shortest_paths(&mut residual, source, sink)?
+        }
+        _ => (0, 0),
+    };
+    Some(MinCostFlow {
+        value,
+        cost,
+        flows: residual.flows(),
+    })
+}
+
+/// Maximum flow of minimum cost between several sources and several sinks
+/// Returns None if a vertex is both a source and a sink, as the flow would be unbounded,
+/// or if the total cost, or the cost of a path, overflows
+pub fn multi_source_min_cost_max_flow<G, CFn, WFn>(
+    graph: &G,
+    capacity: CFn,
+    cost: WFn,
+    sources: &[VertexId],
+    sinks: &[VertexId],
+) -> Option<MinCostFlow>
+where
+    G: GraphView,
+    CFn: Fn(&Edge) -> Capacity,
+    WFn: Fn(&Edge) -> Cost,
+{
+    if sources.iter().any(|source| sinks.contains(source)) {
+        return None;
+    }
+    let supplies: Vec<(VertexId, Flow)> = sources.iter().map(|s| (*s, Flow::MAX)).collect();
+    let demands: Vec<(VertexId, Flow)> = sinks.iter().map(|t| (*t, Flow::MAX)).collect();
+    transportation(graph, capacity, cost, &supplies, &demands)
+}
+
+/// Transportation problem: ships the supplies to the demands at the minimum cost
+/// Each supplier cannot send more than its supply, and each consumer cannot receive more
+/// than its demand. All the demands are met if and only if the value of the flow is
+/// equal to the total demand.
+/// Returns None if the total cost, or the cost of a path, overflows
+pub fn transportation<G, CFn, WFn>(
+    graph: &G,
+    capacity: CFn,
+    cost: WFn,
+    supplies: &[(VertexId, Flow)],
+    demands: &[(VertexId, Flow)],
+) -> Option<MinCostFlow>
+where
+    G: GraphView,
+    CFn: Fn(&Edge) -> Capacity,
+    WFn: Fn(&Edge) -> Cost,
+{
+    let mut residual = ResidualGraph::with_costs(graph, capacity, cost);
+    let super_source = residual.add_vertex(None);
+    let super_sink = residual.add_vertex(None);
+    for (source, supply) in supplies {
+        if let Some(index) = residual.index_of(*source) {
+            residual.add_arc(super_source, index, *supply, 0, None);
+        }
+    }
+    for (sink, demand) in demands {
+        if let Some(index) = residual.index_of(*sink) {
+            residual.add_arc(index, super_sink, *demand, 0, None);
+        }
+    }
+    let (value, cost) = successive_shortest_paths(&mut residual, super_source, super_sink)?;
+    Some(MinCostFlow {
+        value,
+        cost,
+        flows: residual.flows(),
+    })
+}
+
+// Successive shortest paths: augments the flow along the cheapest path until the sink is not
+// reachable anymore. Vertex potentials keep the reduced costs of the residual arcs non negative,
+// so that the cheapest path can be found with Dijkstra algorithm
+// Returns None if the total cost, or the cost of a path, overflows
+fn successive_shortest_paths(
+    residual: &mut ResidualGraph,
+    source: usize,
+    sink: usize,
+) -> Option<(Flow, Cost)> {
+    let mut potentials = initial_potentials(residual, source)?;
+    let (mut value, mut total_cost): (Flow, Cost) = (0, 0);
+    while let Some(path) = cheapest_augmenting_path(residual, source, sink, &mut potentials).ok()? {
+        let path_cost = path.iter().try_fold(0 as Cost, |total, arc| {
+            total.checked_add(residual.arcs[*arc].cost)
+        })?;
+        let delta = residual.augment(&path);
+        value = value.saturating_add(delta);
+        total_cost = Cost::try_from(delta)
+            .ok()
+            .and_then(|delta| delta.checked_mul(path_cost))
+            .and_then(|cost| total_cost.checked_add(cost))?;
+    }
+    Some((value, total_cost))
+}
+
+// Bellman-Ford on the arcs with a positive residual capacity. Only needed with negative costs
+// Returns None if a distance overflows
+fn initial_potentials(residual: &ResidualGraph, source: usize) -> Option<Vec<Cost>> {
+    let mut potentials: Vec<Cost> = vec![0; residual.vertices.len()];
+    // Backward arcs cost the opposite of their forward arc, but have no residual capacity yet
+    if residual
+        .arcs
+        .iter()
+        .all(|arc| arc.residual == 0 || arc.cost >= 0)
+    {
+        return Some(potentials);
+    }
+    let mut distances: Vec<Option<Cost>> = vec![None; residual.vertices.len()];
+    distances[source] = Some(0);
+    for _ in 1..residual.vertices.len() {
+        let mut updated = false;
+        for (index, arc) in residual.arcs.iter().enumerate() {
+            if arc.residual == 0 {
+                continue;
+            }
+            if let Some(distance) = distances[residual.tail(index)] {
+                let candidate = distance.checked_add(arc.cost)?;
+                if distances[arc.to].is_none_or(|current| candidate < current) {
+                    distances[arc.to] = Some(candidate);
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+    for (index, distance) in distances.iter().enumerate() {
+        potentials[index] = distance.unwrap_or(0);
+    }
+    Some(potentials)
+}
+
+// Dijkstra on the reduced costs cost(u, v) + potential(u) - potential(v)
+// Potentials of the reached vertices are updated with their distance to the source
+// Returns None if the sink is not reachable, and an error if a distance overflows
+fn cheapest_augmenting_path(
+    residual: &ResidualGraph,
+    source: usize,
+    sink: usize,
+    potentials: &mut [Cost],
+) -> Result<Option<Vec<usize>>, Overflow> {
+    let mut distances: Vec<Option<Cost>> = vec![None; residual.vertices.len()];
+    let mut parent_arc: Vec<Option<usize>> = vec![None; residual.vertices.len()];
+    let mut done = vec![false; residual.vertices.len()];
+    let mut queue: MinPriorityQueue<(Cost, usize)> = MinPriorityQueue::<(Cost, usize)>::new();
+    distances[source] = Some(0);
+    queue.push((0, source));
+    while let Some((distance, vertex)) = queue.pop() {
+        if done[vertex] {
+            continue;
+        }
+        done[vertex] = true;
+        for arc in &residual.adjacency[vertex] {
+            let next = &residual.arcs[*arc];
+            if next.residual == 0 || done[next.to] {
+                continue;
+            }
+            let candidate = distance
+                .checked_add(next.cost)
+                .and_then(|c| c.checked_add(potentials[vertex]))
+                .and_then(|c| c.checked_sub(potentials[next.to]))
+                .ok_or(Overflow)?;
+            if distances[next.to].is_none_or(|current| candidate < current) {
+                distances[next.to] = Some(candidate);
+                parent_arc[next.to] = Some(*arc);
+                queue.push((candidate, next.to));
+            }
+        }
+    }
+    if distances[sink].is_none() {
+        return Ok(None);
+    }
+    for (index, distance) in distances.iter().enumerate() {
+        if let Some(distance) = distance {
+            potentials[index] = potentials[index].checked_add(*distance).ok_or(Overflow)?;
+        }
+    }
+    let mut path = vec![];
+    let mut vertex = sink;
+    while let Some(arc) = parent_arc[vertex] {
+        path.push(arc);
+        vertex = residual.tail(arc);
+    }
+    path.reverse();
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn min_cost_max_flow_should_use_the_cheapest_routes_first() {
+        let (g, capfn, costfn) = build_test_network(vec![
+            (0, 1, 5, 1),
+            (1, 3, 5, 1),
+            (0, 2, 5, 3),
+            (2, 3, 5, 3),
+            (3, 4, 7, 0),
+        ]);
+        let result = min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(4)).unwrap();
+        assert_eq![result.value, 7];
+        assert_eq![result.cost, 22];
+        assert_eq![result.flows[&edge(0, 1)], (5, 5)];
+        assert_eq![result.flows[&edge(0, 2)], (2, 5)];
+        assert_eq![result.flows[&edge(3, 4)], (7, 7)];
+    }
+
+    #[test]
+    fn min_cost_max_flow_should_cancel_flow_on_expensive_edges() {
+        let (g, capfn, costfn) = build_test_network(vec![
+            (0, 1, 2, 1),
+            (0, 2, 1, 2),
+            (1, 2, 1, 1),
+            (1, 3, 1, 3),
+            (2, 3, 2, 1),
+        ]);
+        let result = min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(3)).unwrap();
+        assert_eq![result.value, 3];
+        assert_eq![result.cost, 10];
+        assert_eq![result.flows[&edge(1, 3)], (1, 1)];
+        assert_eq![result.flows[&edge(2, 3)], (2, 2)];
+    }
+
+    #[test]
+    fn min_cost_max_flow_should_handle_negative_costs() {
+        let (g, capfn, costfn) = build_test_network(vec![
+            (0, 1, 1, 4),
+            (0, 2, 1, 1),
+            (1, 3, 1, -5),
+            (2, 3, 1, 1),
+            (2, 1, 1, 1),
+        ]);
+        let result = min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(3)).unwrap();
+        assert_eq![result.value, 2];
+        assert_eq![result.cost, 1];
+        assert_eq![result.flows[&edge(2, 1)], (0, 1)];
+    }
+
+    #[test]
+    fn min_cost_max_flow_should_return_an_empty_flow_when_the_sink_is_not_reachable() {
+        let (g, capfn, costfn) = build_test_network(vec![(0, 1, 3, 1), (2, 1, 3, 1)]);
+        let result = min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(2)).unwrap();
+        assert_eq![result.value, 0];
+        assert_eq![result.cost, 0];
+    }
+
+    #[test]
+    fn transportation_should_meet_all_demands_at_the_minimum_cost() {
+        let (g, capfn, costfn) = build_test_network(vec![
+            (0, 2, 10, 4),
+            (0, 3, 10, 6),
+            (1, 2, 10, 5),
+            (1, 3, 10, 3),
+        ]);
+        let supplies = vec![(VertexId(0), 3), (VertexId(1), 4)];
+        let demands = vec![(VertexId(2), 5), (VertexId(3), 2)];
+        let result = transportation(&g, capfn, costfn, &supplies, &demands).unwrap();
+        assert_eq![result.value, 7];
+        assert_eq![result.cost, 28];
+        assert_eq![result.flows[&edge(0, 2)], (3, 10)];
+        assert_eq![result.flows[&edge(1, 2)], (2, 10)];
+        assert_eq![result.flows[&edge(1, 3)], (2, 10)];
+    }
+
+    #[test]
+    fn multi_source_min_cost_max_flow_should_compute_the_flow_between_all_sources_and_sinks() {
+        let (g, capfn, costfn) = build_test_network(vec![
+            (0, 2, 2, 1),
+            (1, 2, 2, 5),
+            (1, 3, 1, 1),
+            (2, 4, 3, 1),
+            (3, 4, 1, 1),
+        ]);
+        let result = multi_source_min_cost_max_flow(
+            &g,
+            capfn,
+            costfn,
+            &[VertexId(0), VertexId(1)],
+            &[VertexId(4)],
+        )
+        .unwrap();
+        assert_eq![result.value, 4];
+        assert_eq![result.cost, 12];
+    }
+
+    #[test]
+    fn min_cost_max_flow_should_be_none_when_the_cost_overflows() {
+        let (g, capfn, costfn) =
+            build_test_network(vec![(0, 1, 1 << 40, 1 << 30), (1, 2, 1 << 40, 1)]);
+        assert_eq![
+            min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(2)),
+            None
+        ];
+    }
+
+    #[test]
+    fn min_cost_max_flow_should_be_none_when_the_cost_of_a_path_overflows() {
+        let (g, capfn, costfn) =
+            build_test_network(vec![(0, 1, 1, Cost::MAX), (1, 2, 1, Cost::MAX)]);
+        assert_eq![
+            min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(2)),
+            None
+        ];
+        let (g, capfn, costfn) = build_test_network(vec![(0, 1, 1, -Cost::MAX), (1, 2, 1, -2)]);
+        assert_eq![
+            min_cost_max_flow(&g, capfn, costfn, VertexId(0), VertexId(2)),
+            None
+        ];
+    }
+
+    #[test]
+    fn multi_source_min_cost_max_flow_should_be_none_when_a_source_is_also_a_sink() {
+        let (g, capfn, costfn) = build_test_network(vec![(0, 1, 2, 1), (1, 2, 2, 1)]);
+        let result = multi_source_min_cost_max_flow(
+            &g,
+            capfn,
+            costfn,
+            &[VertexId(0), VertexId(1)],
+            &[VertexId(1)],
+        );
+        assert_eq![result, None];
+    }
+
+    // Helpers
+
+    #[allow(clippy::type_complexity)]
+    fn build_test_network(
+        edges: Vec<(u64, u64, Capacity, Cost)>,
+    ) -> (
+        DirectedGraph,
+        impl Fn(&Edge) -> Capacity,
+        impl Fn(&Edge) -> Cost,
+    ) {
+        let mut g = DirectedGraph::new();
+        let mut capacity: HashMap<Edge, Capacity> = HashMap::new();
+        let mut cost: HashMap<Edge, Cost> = HashMap::new();
+        for (src, dst, cap, c) in edges {
+            g.add_edge(edge(src, dst));
+            capacity.insert(edge(src, dst), cap);
+            cost.insert(edge(src, dst), c);
+        }
+        let capfn = move |e: &Edge| -> Capacity { *capacity.get(e).unwrap_or(&0) };
+        let costfn = move |e: &Edge| -> Cost { *cost.get(e).unwrap_or(&0) };
+        (g, capfn, costfn)
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
@@ -1,6 +1,22 @@
+pub mod arborescence;
+pub mod centrality;
+pub mod cliques;
+pub mod coloring;
+pub mod community;
+pub mod connectivity;
+pub mod critical_path;
 pub mod cycle;
+pub mod dominators;
+pub mod euler;
+pub mod feedback_arc_set;
 pub mod hamiltonian;
+pub mod isomorphism;
 pub mod longest_path;
+pub mod matching;
 pub mod max_flow;
+pub mod min_cost_flow;
+pub mod reachability;
 pub mod shortest_path;
+pub mod temporal;
 pub mod topo_sort;
+pub mod transitive;
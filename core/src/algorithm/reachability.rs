@@ -0,0 +1,220 @@
+//! Reachability queries: ancestors, descendants and a reachability index for DAGs
+use crate::algorithm::topo_sort;
+use crate::algorithm::topo_sort::DAG;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use crate::iter::iter_breadth;
+use crate::view::Reversed;
+use std::collections::{HashMap, HashSet};
+
+/// Vertices reachable from a vertex, the vertex itself excluded
+pub fn descendants<G: GraphView>(graph: &G, vertex: VertexId) -> HashSet<VertexId> {
+    iter_breadth::bfs_iter_from(graph, vertex)
+        .filter(|v| *v != vertex)
+        .collect()
+}
+
+/// Vertices from which a vertex is reachable, the vertex itself excluded
+pub fn ancestors<G: GraphView>(graph: &G, vertex: VertexId) -> HashSet<VertexId> {
+    descendants(&Reversed::new(graph), vertex)
+}
+
+/// Tests if there is a path from a vertex to another one. A vertex can always reach itself
+/// Stops the breadth-first search as soon as the target is found
+pub fn is_reachable<G: GraphView>(graph: &G, from: VertexId, to: VertexId) -> bool {
+    graph.contains_vertex(from)
+        && graph.contains_vertex(to)
+        && iter_breadth::bfs_iter_from(graph, from).any(|v| v == to)
+}
+
+/// Precomputed index answering reachability queries on a DAG in O(log V)
+/// Implements interval labeling: vertices are numbered in postorder of a depth-first spanning
+/// forest, and each vertex is labeled with the intervals of numbers of all its descendants.
+/// See Agrawal, Borgida and Jagadish, "Efficient management of transitive relationships in
+/// large data and knowledge bases" (1989)
+#[derive(Clone, Debug)]
+pub struct ReachabilityIndex {
+    postorder: HashMap<VertexId, usize>,
+    // Sorted and disjoint intervals of postorder numbers reachable from each vertex
+    intervals: HashMap<VertexId, Vec<(usize, usize)>>,
+}
+
+impl ReachabilityIndex {
+    pub fn new<G: GraphView>(dag: &DAG<G>) -> ReachabilityIndex {
+        let graph = dag.as_graph();
+        let (postorder, lowest) = spanning_forest_postorder(graph);
+        let order =
+            topo_sort::topological_sort(graph).expect("A DAG should have a topological order !");
+        let mut intervals: HashMap<VertexId, Vec<(usize, usize)>> = HashMap::new();
+        // Successors come after a vertex in the topological order, so they are already labeled
+        for vertex in order.iter().rev() {
+            let mut labels = vec![(lowest[vertex], postorder[vertex])];
+            for Edge(_, next) in graph.outbound_edges(*vertex) {
+                labels.extend(intervals[&next].iter());
+            }
+            intervals.insert(*vertex, merge(labels));
+        }
+        ReachabilityIndex {
+            postorder,
+            intervals,
+        }
+    }
+
+    /// Tests if there is a path from a vertex to another one. A vertex can always reach itself
+    pub fn is_reachable(&self, from: VertexId, to: VertexId) -> bool {
+        match (self.intervals.get(&from), self.postorder.get(&to)) {
+            (Some(intervals), Some(number)) => {
+                // Last interval starting before the number
+                let index = intervals.partition_point(|(start, _)| start <= number);
+                index > 0 && intervals[index - 1].1 >= *number
+            }
+            _ => false,
+        }
+    }
+}
+
+// Postorder number of each vertex in a depth-first spanning forest, along with the lowest
+// number of its subtree. Trees are rooted at the vertices without inbound edges
+fn spanning_forest_postorder<G: GraphView>(
+    graph: &G,
+) -> (HashMap<VertexId, usize>, HashMap<VertexId, usize>) {
+    let mut roots: Vec<VertexId> = graph
+        .vertices()
+        .filter(|v| graph.degree_in(*v) == 0)
+        .collect();
+    roots.sort();
+    let mut postorder: HashMap<VertexId, usize> = HashMap::new();
+    let mut lowest: HashMap<VertexId, usize> = HashMap::new();
+    let mut visited: HashSet<VertexId> = HashSet::new();
+    for root in roots {
+        visited.insert(root);
+        lowest.insert(root, postorder.len());
+        let mut stack: Vec<(VertexId, Vec<VertexId>)> = vec![(root, successors(graph, root))];
+        while let Some((vertex, pending)) = stack.last_mut() {
+            match pending.pop() {
+                Some(next) if visited.insert(next) => {
+                    // All vertices of the subtree will be numbered from now on
+                    lowest.insert(next, postorder.len());
+                    let next_successors = successors(graph, next);
+                    stack.push((next, next_successors));
+                }
+                Some(_) => {}
+                None => {
+                    postorder.insert(*vertex, postorder.len());
+                    stack.pop();
+                }
+            }
+        }
+    }
+    (postorder, lowest)
+}
+
+// Sorted in reverse order, so that they are visited in increasing order
+fn successors<G: GraphView>(graph: &G, vertex: VertexId) -> Vec<VertexId> {
+    let mut res: Vec<VertexId> = graph.outbound_edges(vertex).map(|e| e.1).collect();
+    res.sort_by(|a, b| b.cmp(a));
+    res
+}
+
+// Sorts the intervals and merges the overlapping or contiguous ones
+fn merge(mut intervals: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    intervals.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = vec![];
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn descendants_should_return_all_reachable_vertices() {
+        let g = build_test_dag();
+        assert_eq![descendants(&g, VertexId(2)), set(vec![4, 5, 6])];
+        assert_eq![descendants(&g, VertexId(6)), set(vec![])];
+    }
+
+    #[test]
+    fn ancestors_should_return_all_vertices_reaching_the_vertex() {
+        let g = build_test_dag();
+        assert_eq![ancestors(&g, VertexId(5)), set(vec![1, 2, 3, 4])];
+        assert_eq![ancestors(&g, VertexId(1)), set(vec![])];
+    }
+
+    #[test]
+    fn ancestors_and_descendants_should_exclude_the_vertex_on_a_cycle() {
+        let g = graph_of(vec![(1, 2), (2, 1)]);
+        assert_eq![ancestors(&g, VertexId(1)), set(vec![2])];
+        assert_eq![descendants(&g, VertexId(1)), set(vec![2])];
+    }
+
+    #[test]
+    fn is_reachable_should_follow_the_direction_of_edges() {
+        let g = build_test_dag();
+        assert![is_reachable(&g, VertexId(1), VertexId(6))];
+        assert![is_reachable(&g, VertexId(3), VertexId(3))];
+        assert![!is_reachable(&g, VertexId(6), VertexId(1))];
+        assert![!is_reachable(&g, VertexId(2), VertexId(3))];
+        assert![!is_reachable(&g, VertexId(1), VertexId(42))];
+    }
+
+    #[test]
+    fn reachability_index_should_answer_like_a_graph_search() {
+        let g = build_test_dag();
+        let index = ReachabilityIndex::new(&topo_sort::try_dag(&g).unwrap());
+        for from in g.vertices() {
+            for to in g.vertices() {
+                assert_eq![
+                    index.is_reachable(*from, *to),
+                    is_reachable(&g, *from, *to),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                ];
+            }
+        }
+        assert![!index.is_reachable(VertexId(1), VertexId(42))];
+    }
+
+    #[test]
+    fn reachability_index_should_handle_vertices_reachable_through_non_tree_edges() {
+        // 4 is reached from 1 through 2 in the spanning tree, and from 3 through a non tree edge
+        let g = graph_of(vec![(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 3)]);
+        let index = ReachabilityIndex::new(&topo_sort::try_dag(&g).unwrap());
+        assert![index.is_reachable(VertexId(3), VertexId(5))];
+        assert![index.is_reachable(VertexId(6), VertexId(4))];
+        assert![!index.is_reachable(VertexId(6), VertexId(2))];
+        assert![!index.is_reachable(VertexId(3), VertexId(2))];
+    }
+
+    // Helpers
+
+    // 1 -> 2 -> 4 -> 5 -> 6, 1 -> 3 -> 4, 2 -> 5
+    fn build_test_dag() -> DirectedGraph {
+        graph_of(vec![(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (5, 6), (2, 5)])
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn set(ids: Vec<u64>) -> HashSet<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
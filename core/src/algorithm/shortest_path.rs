@@ -1,115 +1,152 @@
 use crate::algorithm::topo_sort;
 use crate::algorithm::topo_sort::DAG;
-use crate::directed_graph::DirectedGraph;
 use crate::graph::Edge;
+use crate::graph::GraphView;
 use crate::graph::VertexId;
+use crate::iter::iter_cycle::Cycle;
 use crate::iter::iter_datastructure::{Queue, SearchQueue};
 use crate::path::{Path, ScoredPath};
+use crate::weight::{Overflow, Weight};
 use std::collections::hash_map::HashMap;
+use std::collections::HashSet;
 
 /// Finds the sortest path from a source to a target vertex in a DAG
-pub fn dag_shortest_path<F>(
-    dag: DAG,
+/// Returns None if the target is not reachable, and an error if a score overflows
+pub fn dag_shortest_path<G, F, W>(
+    dag: DAG<G>,
     scorefn: F,
     start: VertexId,
     end: VertexId,
-) -> Option<ScoredPath>
+) -> Result<Option<ScoredPath<W>>, Overflow>
 where
-    F: Fn(&Edge) -> i64,
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: Weight,
 {
-    dag_shortest_paths(dag, scorefn, start).remove(&end)
+    dag_shortest_paths(dag, scorefn, start).map(|mut scores| scores.remove(&end))
 }
 
 /// Finds the sortest path from a source to all reachable vertices in a DAG
-pub fn dag_shortest_paths<F>(dag: DAG, scorefn: F, start: VertexId) -> HashMap<VertexId, ScoredPath>
+/// Returns an error if the score of a path overflows the Weight type
+pub fn dag_shortest_paths<G, F, W>(
+    dag: DAG<G>,
+    scorefn: F,
+    start: VertexId,
+) -> Result<HashMap<VertexId, ScoredPath<W>>, Overflow>
 where
-    F: Fn(&Edge) -> i64,
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: Weight,
 {
     let graph = dag.as_graph();
     let topo_order =
         topo_sort::topological_sort(graph).expect("A DAG should have a topological order !");
-    let mut scores: HashMap<VertexId, ScoredPath> = HashMap::new();
-    scores.insert(start, scored_path_of_one(start, 0));
-
-    topo_order
-        .iter()
-        .skip_while(|v| **v != start)
-        .for_each(|v| {
-            graph.outbound_edges(*v).for_each(|edge| {
-                // FIXME is it necessarily true here ...
-                let ScoredPath { path, score } = scores
-                    .get(v)
-                    .expect("The current score of the processed vertex should be in the map");
-                let Edge(_, w) = edge;
-                let new_score = score + scorefn(&edge);
-                if let Some(current_score) = scores.get(w).map(|x| x.score) {
-                    // we found a shorter path to w => we update
-                    if new_score < current_score {
-                        let new_path = path.append(*w);
-                        scores.insert(*w, scored_path_of(new_path, new_score));
-                    }
-                } else {
-                    let new_path = path.append(*w);
-                    scores.insert(*w, scored_path_of(new_path, new_score));
-                };
-            });
-        });
-    scores
+    let mut scores: HashMap<VertexId, ScoredPath<W>> = HashMap::new();
+    scores.insert(start, scored_path_of_one(start, W::zero()));
+
+    for v in topo_order.iter().skip_while(|v| **v != start) {
+        for edge in graph.outbound_edges(*v) {
+            // FIXME is it necessarily true here ...
+            let ScoredPath { path, score } = scores
+                .get(v)
+                .expect("The current score of the processed vertex should be in the map");
+            let Edge(_, w) = edge;
+            let new_score = score.checked_add(scorefn(&edge)).ok_or(Overflow)?;
+            if let Some(current_score) = scores.get(&w).map(|x| x.score) {
+                // we found a shorter path to w => we update
+                if new_score < current_score {
+                    let new_path = path.append(w);
+                    scores.insert(w, scored_path_of(new_path, new_score));
+                }
+            } else {
+                let new_path = path.append(w);
+                scores.insert(w, scored_path_of(new_path, new_score));
+            };
+        }
+    }
+    Ok(scores)
+}
+
+/// Reason why shortest paths with negative weights can't be computed
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ShortestPathError {
+    /// A cycle of negative weight is reachable from the start
+    NegativeCycle(Cycle),
+    /// The score of a path overflows the Weight type
+    Overflow,
+}
+
+impl From<Overflow> for ShortestPathError {
+    fn from(_: Overflow) -> ShortestPathError {
+        ShortestPathError::Overflow
+    }
 }
 
 /// Bellman-Ford implementation - can handle negative weights
-/// In case a negative cycle is detected, algorithm will return None
+/// In case a negative cycle is reachable from the start, it is returned as the error
+/// An error is also returned if the score of a path overflows the Weight type
 /// Implements algorithm as descrived in https://en.wikipedia.org/wiki/Bellman%E2%80%93Ford_algorithm
-pub fn bellman_ford<F>(
-    graph: &DirectedGraph,
+pub fn bellman_ford<G, F, W>(
+    graph: &G,
     scorefn: F,
     start: VertexId,
-) -> Option<HashMap<VertexId, ScoredPath>>
+) -> Result<HashMap<VertexId, ScoredPath<W>>, ShortestPathError>
 where
-    F: Fn(&Edge) -> i64,
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: Weight,
 {
-    let mut scores: HashMap<VertexId, ScoredPath> = HashMap::new();
-    scores.insert(start, scored_path_of_one(start, 0));
+    let mut scores: HashMap<VertexId, ScoredPath<W>> = HashMap::new();
+    scores.insert(start, scored_path_of_one(start, W::zero()));
+    let mut predecessors: HashMap<VertexId, VertexId> = HashMap::new();
 
     for _ in 1..graph.vertex_count() {
         for edge in graph.edges() {
-            let weight = scorefn(edge);
-            let Edge(u, v) = edge;
-            if let Some(ScoredPath { path, score }) = scores.get(&u) {
-                if *score + weight < current_score_of_vertex(&scores, v) {
-                    let (new_path, new_score) = (path.append(*v), *score + weight);
-                    scores.insert(*v, scored_path_of(new_path, new_score));
-                }
-            }
+            relax(&mut scores, &mut predecessors, &edge, scorefn(&edge))?;
         }
     }
 
     // Looking for cycle with negative weights.
     // Iterating a last time on all edges.
     // If a path can actually be improved after having iterated |V| -1, this means there is a negative cycle
+    // and following the predecessors from the last improved vertex leads to it
+    let mut last_relaxed = None;
     for edge in graph.edges() {
-        let weight = scorefn(edge);
-        let Edge(u, v) = edge;
-        if let Some(ScoredPath { score, .. }) = scores.get(&u) {
-            if *score + weight < current_score_of_vertex(&scores, v) {
-                return None;
-            }
+        if relax(&mut scores, &mut predecessors, &edge, scorefn(&edge))? {
+            last_relaxed = Some(edge.1);
         }
     }
 
-    // If we reached that point, no negative cycles have been detected
-    Some(scores)
+    match last_relaxed {
+        Some(vertex) => Err(ShortestPathError::NegativeCycle(
+            predecessor_cycle(&predecessors, vertex)
+                .expect("The predecessors of a vertex improved after |V| - 1 passes form a cycle"),
+        )),
+        // If we reached that point, no negative cycles have been detected
+        None => Ok(scores),
+    }
 }
 
 /// SPFA - Shortest Path Faster Algorithm
-/// This is an improvement to Bellman-Ford using a queue to avoid blind scanning of edges - but does not terminate on negative weight cycles
+/// This is an improvement to Bellman-Ford using a queue to avoid blind scanning of edges
+/// A vertex improved more than |V| times reveals a negative cycle reachable from the start,
+/// which is returned as the error
+/// An error is also returned if the score of a path overflows the Weight type
 /// Implements algorithm as descrived in https://en.wikipedia.org/wiki/Shortest_Path_Faster_Algorithm
-pub fn spfa<F>(graph: &DirectedGraph, scorefn: F, start: VertexId) -> HashMap<VertexId, ScoredPath>
+pub fn spfa<G, F, W>(
+    graph: &G,
+    scorefn: F,
+    start: VertexId,
+) -> Result<HashMap<VertexId, ScoredPath<W>>, ShortestPathError>
 where
-    F: Fn(&Edge) -> i64,
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: Weight,
 {
-    let mut scores: HashMap<VertexId, ScoredPath> = HashMap::new();
-    scores.insert(start, scored_path_of_one(start, 0));
+    let mut scores: HashMap<VertexId, ScoredPath<W>> = HashMap::new();
+    scores.insert(start, scored_path_of_one(start, W::zero()));
+    let mut predecessors: HashMap<VertexId, VertexId> = HashMap::new();
+    let mut relaxations: HashMap<VertexId, usize> = HashMap::new();
 
     // Using a standard FIFO queue
     let mut q: Queue<VertexId> = Queue::<VertexId>::new();
@@ -117,48 +154,102 @@ where
 
     while let Some(vid) = q.pop() {
         for edge in graph.outbound_edges(vid) {
-            let weight = scorefn(edge);
-            let Edge(u, v) = edge;
-            if let Some(ScoredPath { path, score }) = scores.get(&u) {
-                if *score + weight < current_score_of_vertex(&scores, v) {
-                    let (new_path, new_score) = (path.append(*v), *score + weight);
-                    scores.insert(*v, scored_path_of(new_path, new_score));
-                    q.push(*v);
+            if relax(&mut scores, &mut predecessors, &edge, scorefn(&edge))? {
+                let Edge(_, v) = edge;
+                let count = relaxations.entry(v).or_insert(0);
+                *count += 1;
+                if *count > graph.vertex_count() {
+                    // Any cycle of the predecessors has a negative weight, but they may not
+                    // have one yet: Bellman-Ford then finds the cycle
+                    return match predecessor_cycle(&predecessors, v) {
+                        Some(cycle) => Err(ShortestPathError::NegativeCycle(cycle)),
+                        None => bellman_ford(graph, scorefn, start),
+                    };
                 }
+                q.push(v);
             }
         }
     }
 
-    scores
+    Ok(scores)
 }
 
 // Helpers
 
-fn scored_path_of_one(v: VertexId, score: i64) -> ScoredPath {
+fn scored_path_of_one<W>(v: VertexId, score: W) -> ScoredPath<W> {
     ScoredPath {
         path: Path::from(&vec![v]),
         score: score,
     }
 }
 
-fn scored_path_of(path: Path, score: i64) -> ScoredPath {
+fn scored_path_of<W>(path: Path, score: W) -> ScoredPath<W> {
     ScoredPath {
         path: path,
         score: score,
     }
 }
 
-// Extract current score for a given vertex from a scored path map, or i64::MAX if there is no score yet
-fn current_score_of_vertex(scores: &HashMap<VertexId, ScoredPath>, vid: &VertexId) -> i64 {
+// Improves the score of the destination of the edge going through its source,
+// returns true if the score was improved, or an error if the new score overflows
+fn relax<W: Weight>(
+    scores: &mut HashMap<VertexId, ScoredPath<W>>,
+    predecessors: &mut HashMap<VertexId, VertexId>,
+    edge: &Edge,
+    weight: W,
+) -> Result<bool, Overflow> {
+    let Edge(u, v) = edge;
+    if let Some(ScoredPath { path, score }) = scores.get(&u) {
+        let new_score = score.checked_add(weight).ok_or(Overflow)?;
+        if new_score < current_score_of_vertex(scores, v) {
+            let new_path = path.append(*v);
+            scores.insert(*v, scored_path_of(new_path, new_score));
+            predecessors.insert(*v, *u);
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Follows the predecessors from a vertex until one of them is visited twice
+// Returns None if a vertex without predecessor is reached first
+fn predecessor_cycle(predecessors: &HashMap<VertexId, VertexId>, from: VertexId) -> Option<Cycle> {
+    let mut visited: HashSet<VertexId> = HashSet::new();
+    let mut current = from;
+    while visited.insert(current) {
+        current = *predecessors.get(&current)?;
+    }
+    // current is on the cycle, which is collected backward
+    let mut vertices = vec![current];
+    let mut previous = predecessors[&current];
+    while previous != current {
+        vertices.push(previous);
+        previous = predecessors[&previous];
+    }
+    vertices.reverse();
+    if vertices.len() == 1 {
+        Some(Cycle::self_loop(current))
+    } else {
+        Cycle::from_vertices(&vertices).map(|c| c.canonical())
+    }
+}
+
+// Extract current score for a given vertex from a scored path map, or the maximum weight if there is no score yet
+fn current_score_of_vertex<W: Weight>(
+    scores: &HashMap<VertexId, ScoredPath<W>>,
+    vid: &VertexId,
+) -> W {
     scores
         .get(vid)
         .map(|sp| sp.score)
-        .unwrap_or_else(|| std::i64::MAX)
+        .unwrap_or_else(W::max_value)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::directed_graph::DirectedGraph;
+    use crate::weight::Real;
 
     // Shortest-path in a DAG
 
@@ -171,7 +262,10 @@ mod tests {
             VertexId(1),
             VertexId(8),
         );
-        assert_eq!(shortest_path, Some(scored_path_of(11, vec![1, 2, 4, 7, 8])));
+        assert_eq!(
+            shortest_path,
+            Ok(Some(scored_path_of(11, vec![1, 2, 4, 7, 8])))
+        );
     }
 
     #[test]
@@ -179,7 +273,7 @@ mod tests {
         let (g, scorefn) = build_test_weighted_dag();
 
         let all_shortest_paths_from_1 =
-            dag_shortest_paths(topo_sort::try_dag(&g).unwrap(), scorefn, VertexId(1));
+            dag_shortest_paths(topo_sort::try_dag(&g).unwrap(), scorefn, VertexId(1)).unwrap();
 
         assert_eq!(all_shortest_paths_from_1.len(), 8);
         assert_eq!(
@@ -216,6 +310,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dag_shortest_path_should_support_fractional_weights() {
+        let (g, scorefn) = build_test_weighted_dag();
+        let halved = |e: &Edge| Real(scorefn(e) as f64 / 2.0);
+        let shortest_path = dag_shortest_path(
+            topo_sort::try_dag(&g).unwrap(),
+            halved,
+            VertexId(1),
+            VertexId(8),
+        );
+        assert_eq!(shortest_path.unwrap().map(|sp| sp.score), Some(Real(5.5)));
+    }
+
+    #[test]
+    fn dag_shortest_paths_should_detect_overflows() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        let dag = topo_sort::try_dag(&g).unwrap();
+        assert_eq!(
+            dag_shortest_paths(dag, |_| i64::MIN, VertexId(1)),
+            Err(Overflow)
+        );
+    }
+
     // Bellman-Ford-Moore
 
     #[test]
@@ -260,13 +379,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bfm_shortest_paths_should_return_the_negative_cycle() {
+        let (g, scorefn) = build_test_arbitrage_graph();
+        let cycle = bellman_ford(&g, scorefn, VertexId(1)).unwrap_err();
+        assert_eq!(
+            cycle,
+            ShortestPathError::NegativeCycle(cycle_of(vec![2, 3, 4]))
+        );
+    }
+
+    #[test]
+    fn bfm_shortest_paths_should_return_negative_self_loops() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 2));
+        let scorefn = |e: &Edge| if e.0 == e.1 { -1i64 } else { 1 };
+        let cycle = bellman_ford(&g, scorefn, VertexId(1)).unwrap_err();
+        assert_eq!(
+            cycle,
+            ShortestPathError::NegativeCycle(Cycle::self_loop(VertexId(2)))
+        );
+    }
+
+    #[test]
+    fn bfm_shortest_paths_should_ignore_unreachable_negative_cycles() {
+        let (g, scorefn) = build_test_arbitrage_graph();
+        let scores = bellman_ford(&g, scorefn, VertexId(5)).unwrap();
+        assert_eq!(scores.len(), 1);
+    }
+
+    #[test]
+    fn bfm_shortest_paths_should_detect_overflows() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        let scorefn = |_: &Edge| u64::MAX - 1;
+        assert_eq!(
+            bellman_ford(&g, scorefn, VertexId(1)),
+            Err(ShortestPathError::Overflow)
+        );
+        assert_eq!(
+            spfa(&g, scorefn, VertexId(1)),
+            Err(ShortestPathError::Overflow)
+        );
+    }
+
     // SPFA
 
     #[test]
     fn spfa_shortest_paths_should_find_all_shortest_paths_from_source_vertex_in_a_dag() {
         let (g, scorefn) = build_test_weighted_dag();
 
-        let all_shortest_paths_from_1 = spfa(&g, scorefn, VertexId(1));
+        let all_shortest_paths_from_1 =
+            spfa(&g, scorefn, VertexId(1)).expect("A DAG cannot have a negative weight cycle");
 
         assert_eq!(all_shortest_paths_from_1.len(), 8);
         assert_eq!(
@@ -303,6 +469,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn spfa_shortest_paths_should_return_the_negative_cycle() {
+        let (g, scorefn) = build_test_arbitrage_graph();
+        let cycle = spfa(&g, scorefn, VertexId(1)).unwrap_err();
+        assert_eq!(
+            cycle,
+            ShortestPathError::NegativeCycle(cycle_of(vec![2, 3, 4]))
+        );
+        assert!(spfa(&g, scorefn, VertexId(5)).is_ok());
+    }
+
     // Helpers
 
     // Graph taken from https://www.youtube.com/watch?v=TXkDpqjDMHA
@@ -327,6 +504,25 @@ mod tests {
         (g, scorefn)
     }
 
+    // Exchange rates as weights: going around 2 -> 3 -> 4 -> 2 earns 1, 5 is a dead end
+    fn build_test_arbitrage_graph() -> (DirectedGraph, impl Fn(&Edge) -> i64 + Copy) {
+        let scorefn = |e: &Edge| -> i64 {
+            match (e.0).0 {
+                4 => -4,
+                _ => 1,
+            }
+        };
+        let mut g = DirectedGraph::new();
+        for (src, dst) in [(1, 2), (2, 3), (3, 4), (4, 2), (3, 1), (1, 5)] {
+            g.add_edge(edge(src, dst));
+        }
+        (g, scorefn)
+    }
+
+    fn cycle_of(vertices: Vec<u64>) -> Cycle {
+        Cycle::from_vertices(&vertices.into_iter().map(VertexId).collect()).unwrap()
+    }
+
     fn edge(src: u64, dst: u64) -> Edge {
         Edge(VertexId(src), VertexId(dst))
     }
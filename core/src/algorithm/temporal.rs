@@ -0,0 +1,387 @@
+//! Temporal graphs: edges can only be taken during their validity intervals
+use crate::attribute::mapping::EdgeAttrMapping;
+use crate::graph::{Edge, GraphView, VertexId};
+use crate::iter::iter_datastructure::{MinPriorityQueue, SearchQueue};
+use crate::path::Path;
+use crate::view::EdgeFiltered;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+pub type Timestamp = i64;
+
+/// Half-open time interval: start is included, end is excluded
+/// Written like "10..20"
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Interval {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Interval {
+    pub fn contains(&self, time: Timestamp) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Interval, String> {
+        let mut bounds = s.trim().splitn(2, "..");
+        match (
+            bounds.next().and_then(|b| b.parse::<Timestamp>().ok()),
+            bounds.next().and_then(|b| b.parse::<Timestamp>().ok()),
+        ) {
+            (Some(start), Some(end)) => Ok(Interval { start, end }),
+            _ => Err(format!["Invalid interval '{}', expected <start>..<end>", s]),
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Validity of a temporal edge: the edge can only be taken during one of these intervals
+/// Written as comma separated intervals, like "10..20,30..40"
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Validity {
+    pub intervals: Vec<Interval>,
+}
+
+impl Validity {
+    pub fn contains(&self, time: Timestamp) -> bool {
+        self.intervals.iter().any(|i| i.contains(time))
+    }
+
+    /// Earliest time, at or after the given time, at which the edge can be taken
+    pub fn next_departure(&self, time: Timestamp) -> Option<Timestamp> {
+        self.intervals
+            .iter()
+            .filter(|i| i.start < i.end && time < i.end)
+            .map(|i| i.start.max(time))
+            .min()
+    }
+
+    /// Latest time, at or before the given time, at which the edge can be taken
+    pub fn last_departure(&self, time: Timestamp) -> Option<Timestamp> {
+        self.intervals
+            .iter()
+            .filter(|i| i.start < i.end && i.start <= time)
+            .map(|i| (i.end - 1).min(time))
+            .max()
+    }
+}
+
+impl FromStr for Validity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Validity, String> {
+        let intervals: Result<Vec<Interval>, String> = s.split(',').map(|i| i.parse()).collect();
+        intervals.map(|intervals| Validity { intervals })
+    }
+}
+
+impl fmt::Display for Validity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let intervals: Vec<String> = self.intervals.iter().map(|i| i.to_string()).collect();
+        write!(f, "{}", intervals.join(","))
+    }
+}
+
+/// Path through temporal edges, leaving its first vertex at departure
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TemporalPath {
+    pub path: Path,
+    pub departure: Timestamp,
+    pub arrival: Timestamp,
+}
+
+/// View of the edges valid at the given time, with all the vertices
+/// Edges without validity are always valid
+pub fn valid_at<'a, G: GraphView>(
+    graph: &'a G,
+    validity: &'a EdgeAttrMapping<Validity>,
+    time: Timestamp,
+) -> EdgeFiltered<'a, G, impl Fn(&Edge) -> bool + 'a> {
+    EdgeFiltered::new(graph, move |edge: &Edge| {
+        validity.get(edge).is_none_or(|v| v.contains(time))
+    })
+}
+
+/// Finds the path from start to end arriving the earliest, leaving start at or after departure
+/// An edge is taken during its validity and traversed in its duration, waiting on vertices is
+/// allowed. Edges without validity are always valid. Durations must not be negative.
+/// Implementation of Dijkstra algorithm on arrival times
+pub fn earliest_arrival<G, DFn>(
+    graph: &G,
+    validity: &EdgeAttrMapping<Validity>,
+    duration: DFn,
+    start: VertexId,
+    end: VertexId,
+    departure: Timestamp,
+) -> Option<TemporalPath>
+where
+    G: GraphView,
+    DFn: Fn(&Edge) -> Timestamp,
+{
+    if !graph.contains_vertex(start) {
+        return None;
+    }
+    let mut arrivals: HashMap<VertexId, Timestamp> = HashMap::new();
+    let mut predecessors: HashMap<VertexId, VertexId> = HashMap::new();
+    let mut queue = MinPriorityQueue::<(Timestamp, VertexId)>::new();
+    arrivals.insert(start, departure);
+    queue.push((departure, start));
+
+    while let Some((time, vertex)) = queue.pop() {
+        if time > arrivals[&vertex] {
+            continue;
+        }
+        if vertex == end {
+            break;
+        }
+        for edge in graph.outbound_edges(vertex) {
+            let arrival = match next_departure(validity, &edge, time) {
+                Some(leave) => leave.saturating_add(duration(&edge)),
+                None => continue,
+            };
+            if arrivals.get(&edge.1).is_none_or(|a| arrival < *a) {
+                arrivals.insert(edge.1, arrival);
+                predecessors.insert(edge.1, vertex);
+                queue.push((arrival, edge.1));
+            }
+        }
+    }
+
+    arrivals.get(&end)?;
+    let mut vertices = vec![end];
+    while let Some(previous) = predecessors.get(vertices.last().unwrap()) {
+        if *vertices.last().unwrap() == start {
+            break;
+        }
+        vertices.push(*previous);
+    }
+    vertices.reverse();
+    replay(Path::from(&vertices), validity, duration, departure)
+}
+
+/// Finds the path from start to end leaving start the latest, arriving at end at or before
+/// the deadline. An edge is taken during its validity and traversed in its duration, waiting on
+/// vertices is allowed. Edges without validity are always valid. Durations must not be negative.
+/// Implementation of Dijkstra algorithm on departure times, backward from end
+pub fn latest_departure<G, DFn>(
+    graph: &G,
+    validity: &EdgeAttrMapping<Validity>,
+    duration: DFn,
+    start: VertexId,
+    end: VertexId,
+    deadline: Timestamp,
+) -> Option<TemporalPath>
+where
+    G: GraphView,
+    DFn: Fn(&Edge) -> Timestamp,
+{
+    if !graph.contains_vertex(end) {
+        return None;
+    }
+    // Latest time to leave each vertex and still reach end in time
+    let mut departures: HashMap<VertexId, Timestamp> = HashMap::new();
+    let mut successors: HashMap<VertexId, VertexId> = HashMap::new();
+    let mut queue = MinPriorityQueue::<(Reverse<Timestamp>, VertexId)>::new();
+    departures.insert(end, deadline);
+    queue.push((Reverse(deadline), end));
+
+    while let Some((Reverse(time), vertex)) = queue.pop() {
+        if time < departures[&vertex] {
+            continue;
+        }
+        if vertex == start {
+            break;
+        }
+        for edge in graph.inbound_edges(vertex) {
+            let leave = match last_departure(validity, &edge, time.saturating_sub(duration(&edge)))
+            {
+                Some(leave) => leave,
+                None => continue,
+            };
+            if departures.get(&edge.0).is_none_or(|d| leave > *d) {
+                departures.insert(edge.0, leave);
+                successors.insert(edge.0, vertex);
+                queue.push((Reverse(leave), edge.0));
+            }
+        }
+    }
+
+    let departure = *departures.get(&start)?;
+    let mut vertices = vec![start];
+    while let Some(next) = successors.get(vertices.last().unwrap()) {
+        if *vertices.last().unwrap() == end {
+            break;
+        }
+        vertices.push(*next);
+    }
+    replay(Path::from(&vertices), validity, duration, departure)
+}
+
+// Follows the path leaving each vertex as soon as possible from the given time
+// Returns None if an edge cannot be taken anymore
+fn replay<DFn>(
+    path: Path,
+    validity: &EdgeAttrMapping<Validity>,
+    duration: DFn,
+    time: Timestamp,
+) -> Option<TemporalPath>
+where
+    DFn: Fn(&Edge) -> Timestamp,
+{
+    let mut departure = None;
+    let mut arrival = time;
+    for edge in path.to_edge_list() {
+        let leave = next_departure(validity, &edge, arrival)?;
+        departure = departure.or(Some(leave));
+        arrival = leave.saturating_add(duration(&edge));
+    }
+    Some(TemporalPath {
+        path,
+        departure: departure.unwrap_or(time),
+        arrival,
+    })
+}
+
+fn next_departure(
+    validity: &EdgeAttrMapping<Validity>,
+    edge: &Edge,
+    time: Timestamp,
+) -> Option<Timestamp> {
+    match validity.get(edge) {
+        Some(v) => v.next_departure(time),
+        None => Some(time),
+    }
+}
+
+fn last_departure(
+    validity: &EdgeAttrMapping<Validity>,
+    edge: &Edge,
+    time: Timestamp,
+) -> Option<Timestamp> {
+    match validity.get(edge) {
+        Some(v) => v.last_departure(time),
+        None => Some(time),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn validity_should_be_parsed_from_intervals() {
+        let validity: Validity = "10..20, 30..40".parse().unwrap();
+        assert_eq![validity.intervals.len(), 2];
+        assert![validity.contains(10) && validity.contains(35)];
+        assert![!validity.contains(20) && !validity.contains(5)];
+        assert_eq![validity.to_string(), "10..20,30..40"];
+        assert_eq![validity.next_departure(25), Some(30)];
+        assert_eq![validity.last_departure(25), Some(19)];
+        assert![Validity::from_str("10-20").is_err()];
+    }
+
+    #[test]
+    fn valid_at_should_keep_the_edges_valid_at_the_given_time() {
+        let (g, validity) = build_test_graph();
+        let g2 = valid_at(&g, &validity, 12);
+        assert_eq![g2.vertex_count(), g.vertex_count()];
+        assert![g2.contains_edge(edge(1, 2))];
+        assert![!g2.contains_edge(edge(2, 4))];
+        // Edges without validity are always valid
+        assert![g2.contains_edge(edge(1, 3))];
+    }
+
+    #[test]
+    fn earliest_arrival_should_wait_for_edges_to_be_valid() {
+        let (g, validity) = build_test_graph();
+        let path = earliest_arrival(&g, &validity, duration, vid(1), vid(4), 0).unwrap();
+        // 1 -> 2 -> 4 has to wait for 2 -> 4 until 30
+        assert_eq![path.path, path_of(vec![1, 3, 4])];
+        assert_eq![(path.departure, path.arrival), (0, 25)];
+        let path = earliest_arrival(&g, &validity, duration, vid(1), vid(4), 25).unwrap();
+        assert_eq![path.path, path_of(vec![1, 2, 4])];
+        assert_eq![(path.departure, path.arrival), (25, 32)];
+    }
+
+    #[test]
+    fn earliest_arrival_should_be_none_when_edges_are_not_valid_anymore() {
+        let (g, validity) = build_test_graph();
+        assert_eq![
+            earliest_arrival(&g, &validity, duration, vid(1), vid(4), 100),
+            None
+        ];
+        assert_eq![
+            earliest_arrival(&g, &validity, duration, vid(4), vid(1), 0),
+            None
+        ];
+    }
+
+    #[test]
+    fn latest_departure_should_arrive_before_the_deadline() {
+        let (g, validity) = build_test_graph();
+        let path = latest_departure(&g, &validity, duration, vid(1), vid(4), 40).unwrap();
+        assert_eq![path.path, path_of(vec![1, 2, 4])];
+        assert_eq![(path.departure, path.arrival), (37, 40)];
+        let path = latest_departure(&g, &validity, duration, vid(1), vid(4), 29).unwrap();
+        assert_eq![path.path, path_of(vec![1, 3, 4])];
+        assert_eq![(path.departure, path.arrival), (4, 29)];
+        assert_eq![
+            latest_departure(&g, &validity, duration, vid(1), vid(4), 10),
+            None
+        ];
+    }
+
+    // Helpers
+
+    // 1 -> 2 -> 4 is quick but 2 -> 4 is only valid from 30 to 40
+    // 1 -> 3 -> 4 is slow, 3 -> 4 is valid before 20
+    fn build_test_graph() -> (DirectedGraph, EdgeAttrMapping<Validity>) {
+        let mut g = DirectedGraph::new();
+        let mut validity = EdgeAttrMapping::<Validity>::new("validity");
+        for (src, dst, intervals) in [
+            (1, 2, "0..50"),
+            (2, 4, "30..40"),
+            (1, 3, ""),
+            (3, 4, "0..20"),
+        ] {
+            g.add_edge(edge(src, dst));
+            if !intervals.is_empty() {
+                validity.add(edge(src, dst), intervals.parse().unwrap());
+            }
+        }
+        (g, validity)
+    }
+
+    fn duration(e: &Edge) -> Timestamp {
+        match (((e.0).0), ((e.1).0)) {
+            (1, 2) => 1,
+            (2, 4) => 2,
+            (1, 3) => 10,
+            _ => 15,
+        }
+    }
+
+    fn path_of(vertices: Vec<u64>) -> Path {
+        Path::from(&vertices.into_iter().map(VertexId).collect())
+    }
+
+    fn vid(id: u64) -> VertexId {
+        VertexId(id)
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
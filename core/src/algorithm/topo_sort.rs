@@ -1,28 +1,31 @@
 use crate::directed_graph::DirectedGraph;
 use crate::graph::Edge;
+use crate::graph::GraphView;
 use crate::graph::VertexId;
+use crate::iter::iter_datastructure::{MinPriorityQueue, SearchQueue};
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::LinkedList;
 
 // DAG Marker
 
-pub struct DAG<'a> {
-    graph: &'a DirectedGraph,
+pub struct DAG<'a, G: GraphView = DirectedGraph> {
+    graph: &'a G,
 }
 
-impl<'a> DAG<'a> {
-    pub fn as_graph(&'a self) -> &'a DirectedGraph {
+impl<'a, G: GraphView> DAG<'a, G> {
+    pub fn as_graph(&self) -> &'a G {
         self.graph
     }
 }
 
 /// Tests if te graph is a Directed Acyclic Graph (DAG)
-pub fn is_dag(graph: &DirectedGraph) -> bool {
+pub fn is_dag<G: GraphView>(graph: &G) -> bool {
     topological_sort(graph).is_some()
 }
 
 /// Mark a graph as Dag if it is a DAG
-pub fn try_dag(graph: &DirectedGraph) -> Option<DAG> {
+pub fn try_dag<G: GraphView>(graph: &G) -> Option<DAG<G>> {
     if is_dag(graph) {
         Some(DAG { graph })
     } else {
@@ -32,7 +35,7 @@ pub fn try_dag(graph: &DirectedGraph) -> Option<DAG> {
 
 /// Computes a topological order for a Graph
 /// See Kahn's algorithm: https://en.wikipedia.org/wiki/Topological_sorting
-pub fn topological_sort(graph: &DirectedGraph) -> Option<Vec<VertexId>> {
+pub fn topological_sort<G: GraphView>(graph: &G) -> Option<Vec<VertexId>> {
     let mut res: Vec<VertexId> = vec![];
     let mut start_vertices: LinkedList<VertexId> = find_start_vertices(graph).collect();
     // Keeping track of removed edges  (ie visited, since we don't want to remove them from the graph)
@@ -40,13 +43,16 @@ pub fn topological_sort(graph: &DirectedGraph) -> Option<Vec<VertexId>> {
 
     while let Some(start) = start_vertices.pop_front() {
         res.push(start);
-        let mut children: Vec<Edge> = graph.outbound_edges(start).map(|x| *x).collect();
+        let mut children: Vec<Edge> = graph.outbound_edges(start).collect();
         children.retain(|x| !removed_edges.contains(x));
         for edge in children {
             removed_edges.insert(edge);
             let Edge(_, dest) = edge;
             // No other incoming edges (ie no other edges, or already visited edges)
-            if graph.inbound_edges(dest).all(|e| removed_edges.contains(e)) {
+            if graph
+                .inbound_edges(dest)
+                .all(|e| removed_edges.contains(&e))
+            {
                 start_vertices.push_back(dest);
             }
         }
@@ -59,16 +65,182 @@ pub fn topological_sort(graph: &DirectedGraph) -> Option<Vec<VertexId>> {
     }
 }
 
+/// Computes the lexicographically smallest topological order for a Graph
+/// Unlike topological_sort, the order does not depend on the iteration order of the graph
+/// Kahn's algorithm, always picking the smallest available vertex, running in O((V + E) log V)
+pub fn lexicographic_topological_sort<G: GraphView>(graph: &G) -> Option<Vec<VertexId>> {
+    let mut res: Vec<VertexId> = vec![];
+    let mut degree_in: HashMap<VertexId, usize> = HashMap::new();
+    let mut available = MinPriorityQueue::<VertexId>::new();
+    for vertex in graph.vertices() {
+        let degree = graph.degree_in(vertex);
+        if degree == 0 {
+            available.push(vertex);
+        }
+        degree_in.insert(vertex, degree);
+    }
+
+    while let Some(vertex) = available.pop() {
+        res.push(vertex);
+        for Edge(_, dst) in graph.outbound_edges(vertex) {
+            let degree = degree_in.get_mut(&dst).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                available.push(dst);
+            }
+        }
+    }
+
+    if res.len() != graph.vertex_count() {
+        None
+    } else {
+        Some(res)
+    }
+}
+
+/// Iterates over all the topological orders of a DAG, in lexicographic order
+/// Orders are built lazily by backtracking, so only the current order is kept in memory
+pub struct AllTopologicalSorts {
+    vertices: Vec<VertexId>,
+    successors: Vec<Vec<usize>>,
+    degree_in: Vec<usize>,
+    placed: Vec<bool>,
+    order: Vec<usize>,
+    // For each position of the order, the candidate vertices and the next one to try
+    candidates: Vec<(Vec<usize>, usize)>,
+    started: bool,
+}
+
+impl Iterator for AllTopologicalSorts {
+    type Item = Vec<VertexId>;
+
+    fn next(&mut self) -> Option<Vec<VertexId>> {
+        if !self.started {
+            self.started = true;
+            if self.vertices.is_empty() {
+                return Some(vec![]);
+            }
+            let first = self.available();
+            self.candidates.push((first, 0));
+        }
+        while let Some((candidates, next)) = self.candidates.last() {
+            let (candidate, depth) = (candidates.get(*next).copied(), self.candidates.len());
+            // The vertex previously tried at this position is taken back
+            if self.order.len() == depth {
+                self.unplace();
+            }
+            match candidate {
+                Some(vertex) => {
+                    self.candidates.last_mut().unwrap().1 += 1;
+                    self.place(vertex);
+                    if self.order.len() == self.vertices.len() {
+                        return Some(self.order.iter().map(|v| self.vertices[*v]).collect());
+                    }
+                    let available = self.available();
+                    self.candidates.push((available, 0));
+                }
+                None => {
+                    self.candidates.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+impl AllTopologicalSorts {
+    fn available(&self) -> Vec<usize> {
+        (0..self.vertices.len())
+            .filter(|v| !self.placed[*v] && self.degree_in[*v] == 0)
+            .collect()
+    }
+
+    fn place(&mut self, vertex: usize) {
+        self.placed[vertex] = true;
+        self.order.push(vertex);
+        for next in &self.successors[vertex] {
+            self.degree_in[*next] -= 1;
+        }
+    }
+
+    fn unplace(&mut self) {
+        let vertex = self.order.pop().unwrap();
+        self.placed[vertex] = false;
+        for next in &self.successors[vertex] {
+            self.degree_in[*next] += 1;
+        }
+    }
+}
+
+/// Returns an iterator over all the topological orders of a DAG
+pub fn all_topological_sorts<G: GraphView>(dag: &DAG<G>) -> AllTopologicalSorts {
+    let graph = dag.as_graph();
+    let mut vertices: Vec<VertexId> = graph.vertices().collect();
+    vertices.sort();
+    let indices: HashMap<VertexId, usize> =
+        vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let successors = vertices
+        .iter()
+        .map(|v| {
+            graph
+                .outbound_edges(*v)
+                .map(|Edge(_, dst)| indices[&dst])
+                .collect()
+        })
+        .collect();
+    let degree_in = vertices.iter().map(|v| graph.degree_in(*v)).collect();
+    AllTopologicalSorts {
+        placed: vec![false; vertices.len()],
+        vertices,
+        successors,
+        degree_in,
+        order: vec![],
+        candidates: vec![],
+        started: false,
+    }
+}
+
+/// Longest path layering: the level of a vertex is the length of the longest path ending on it
+/// Vertices without inbound edges are on level 0, and every edge goes to a higher level
+pub fn layering<G: GraphView>(dag: &DAG<G>) -> HashMap<VertexId, usize> {
+    let graph = dag.as_graph();
+    let order = lexicographic_topological_sort(graph).expect("A DAG has a topological order");
+    let mut levels: HashMap<VertexId, usize> = HashMap::new();
+    for vertex in order {
+        let level = graph
+            .inbound_edges(vertex)
+            .map(|Edge(src, _)| levels[&src] + 1)
+            .max()
+            .unwrap_or(0);
+        levels.insert(vertex, level);
+    }
+    levels
+}
+
+/// Groups the vertices of a DAG by level of the longest path layering
+/// All the vertices of a wave can be processed in parallel once the previous waves are done.
+/// Vertices of a wave are sorted
+pub fn waves<G: GraphView>(dag: &DAG<G>) -> Vec<Vec<VertexId>> {
+    let levels = layering(dag);
+    let mut res: Vec<Vec<VertexId>> = vec![vec![]; levels.values().max().map_or(0, |l| l + 1)];
+    for (vertex, level) in levels {
+        res[level].push(vertex);
+    }
+    for wave in res.iter_mut() {
+        wave.sort();
+    }
+    res
+}
+
 // Finds start nodes (ie with no inbound edges)
-fn find_start_vertices(graph: &DirectedGraph) -> impl Iterator<Item = VertexId> + '_ {
+fn find_start_vertices<G: GraphView>(graph: &G) -> impl Iterator<Item = VertexId> + '_ {
     graph
         .vertices()
-        .map(|x| *x)
         .filter(move |vid| is_start_vertex(graph, vid))
 }
 
 // A start vertex is a vertex that doesn't have any incoming edge
-fn is_start_vertex(graph: &DirectedGraph, vid: &VertexId) -> bool {
+fn is_start_vertex<G: GraphView>(graph: &G, vid: &VertexId) -> bool {
     graph.inbound_edges(*vid).count() == 0
 }
 
@@ -194,12 +366,96 @@ mod tests {
         assert!(topological_sort(&g).is_none());
     }
 
+    #[test]
+    fn lexicographic_topological_sort_should_pick_the_smallest_available_vertex() {
+        let g = build_wikipedia_dag();
+        assert_eq!(
+            lexicographic_topological_sort(&g),
+            Some(vertices(vec![3, 5, 7, 8, 11, 2, 9, 10]))
+        );
+    }
+
+    #[test]
+    fn lexicographic_topological_sort_on_a_graph_with_a_cycle_is_none() {
+        let mut g = build_wikipedia_dag();
+        g.add_edge(edge(10, 5));
+        assert!(lexicographic_topological_sort(&g).is_none());
+    }
+
+    #[test]
+    fn all_topological_sorts_should_enumerate_orders_in_lexicographic_order() {
+        // Diamond: 1 -> 2 -> 4, 1 -> 3 -> 4, and 5 isolated
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(1, 3));
+        g.add_edge(edge(2, 4));
+        g.add_edge(edge(3, 4));
+        g.add_vertex(VertexId(5));
+        let dag = try_dag(&g).unwrap();
+        let orders: Vec<Vec<VertexId>> = all_topological_sorts(&dag).collect();
+        assert_eq!(orders.len(), 10);
+        assert_eq!(orders[0], vertices(vec![1, 2, 3, 4, 5]));
+        assert_eq!(orders[1], vertices(vec![1, 2, 3, 5, 4]));
+        assert_eq!(orders[9], vertices(vec![5, 1, 3, 2, 4]));
+        let mut sorted = orders.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, orders);
+    }
+
+    #[test]
+    fn all_topological_sorts_on_an_empty_graph_is_one_empty_order() {
+        let g = DirectedGraph::new();
+        let dag = try_dag(&g).unwrap();
+        assert_eq!(
+            all_topological_sorts(&dag).collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+    }
+
+    #[test]
+    fn layering_should_use_the_longest_path_to_each_vertex() {
+        let g = build_wikipedia_dag();
+        let dag = try_dag(&g).unwrap();
+        let levels = layering(&dag);
+        assert_eq!(levels[&VertexId(7)], 0);
+        assert_eq!(levels[&VertexId(11)], 1);
+        assert_eq!(levels[&VertexId(9)], 2);
+        assert_eq!(
+            waves(&dag),
+            vec![
+                vertices(vec![3, 5, 7]),
+                vertices(vec![8, 11]),
+                vertices(vec![2, 9, 10])
+            ]
+        );
+    }
+
     // Helpers
 
     fn edge(src: u64, dst: u64) -> Edge {
         Edge(VertexId(src), VertexId(dst))
     }
 
+    fn vertices(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    // DAG taken from https://en.wikipedia.org/wiki/Topological_sorting
+    fn build_wikipedia_dag() -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(5, 11));
+        g.add_edge(edge(11, 2));
+        g.add_edge(edge(7, 11));
+        g.add_edge(edge(11, 9));
+        g.add_edge(edge(11, 10));
+        g.add_edge(edge(7, 8));
+        g.add_edge(edge(8, 9));
+        g.add_edge(edge(3, 8));
+        g.add_edge(edge(3, 10));
+        g
+    }
+
     fn set<T: Eq + Hash + Clone>(v: &[T]) -> HashSet<T> {
         v.iter().map(|x| x.clone()).collect::<HashSet<T>>()
     }
@@ -0,0 +1,241 @@
+//! Transitive closure and transitive reduction
+use crate::algorithm::topo_sort;
+use crate::algorithm::topo_sort::DAG;
+use crate::directed_graph::DirectedGraph;
+use crate::graph::Edge;
+use crate::graph::GraphView;
+use crate::graph::VertexId;
+use crate::iter::iter_datastructure::{Queue, SearchQueue};
+use std::collections::{HashMap, HashSet};
+
+/// Transitive closure of a graph: adds an edge u -> v for each vertex v reachable from u
+/// A vertex on a cycle can reach itself, and gets a self loop
+/// Runs a breadth-first search from each vertex, in O(V(V+E))
+pub fn transitive_closure<G: GraphView>(graph: &G) -> DirectedGraph {
+    let mut closure = DirectedGraph::new();
+    for vertex in graph.vertices() {
+        closure.add_vertex(vertex);
+        let mut visited: HashSet<VertexId> = HashSet::new();
+        let mut queue: Queue<VertexId> = Queue::<VertexId>::new();
+        queue.push(vertex);
+        while let Some(current) = queue.pop() {
+            for Edge(_, next) in graph.outbound_edges(current) {
+                if visited.insert(next) {
+                    closure.add_edge(Edge(vertex, next));
+                    queue.push(next);
+                }
+            }
+        }
+    }
+    closure
+}
+
+/// Transitive closure of a DAG
+/// Reachable vertices are stored as bitsets, and merged in reverse topological order
+/// Runs in O(VE / 64) plus the size of the closure
+pub fn dag_transitive_closure<G: GraphView>(dag: &DAG<G>) -> DirectedGraph {
+    let reachability = Reachability::new(dag);
+    let mut closure = DirectedGraph::new();
+    for (index, vertex) in reachability.vertices.iter().enumerate() {
+        closure.add_vertex(*vertex);
+        for reachable in reachability.reachable[index].iter() {
+            closure.add_edge(Edge(*vertex, reachability.vertices[reachable]));
+        }
+    }
+    closure
+}
+
+/// Edges of a DAG that can be removed without changing its reachability
+/// An edge u -> v is redundant if v can also be reached from another successor of u.
+/// Returned edges are sorted
+pub fn redundant_edges<G: GraphView>(dag: &DAG<G>) -> Vec<Edge> {
+    let graph = dag.as_graph();
+    let reachability = Reachability::new(dag);
+    let mut redundant: Vec<Edge> = graph
+        .edges()
+        .filter(|Edge(src, dst)| {
+            let target = reachability.indices[dst];
+            graph.outbound_edges(*src).any(|Edge(_, other)| {
+                reachability.reachable[reachability.indices[&other]].contains(target)
+            })
+        })
+        .collect();
+    redundant.sort();
+    redundant
+}
+
+/// Transitive reduction of a DAG: the smallest graph with the same reachability
+/// It is unique for a DAG, and is made of all the edges of the DAG which are not redundant
+/// See https://en.wikipedia.org/wiki/Transitive_reduction
+pub fn transitive_reduction<G: GraphView>(dag: &DAG<G>) -> DirectedGraph {
+    let graph = dag.as_graph();
+    let redundant: HashSet<Edge> = redundant_edges(dag).into_iter().collect();
+    let mut reduction = DirectedGraph::new();
+    for vertex in graph.vertices() {
+        reduction.add_vertex(vertex);
+    }
+    for edge in graph.edges().filter(|e| !redundant.contains(e)) {
+        reduction.add_edge(edge);
+    }
+    reduction
+}
+
+// Vertices reachable from each vertex of a DAG, through at least one edge
+struct Reachability {
+    vertices: Vec<VertexId>,
+    indices: HashMap<VertexId, usize>,
+    reachable: Vec<BitSet>,
+}
+
+impl Reachability {
+    fn new<G: GraphView>(dag: &DAG<G>) -> Reachability {
+        let graph = dag.as_graph();
+        let vertices =
+            topo_sort::topological_sort(graph).expect("A DAG should have a topological order !");
+        let indices: HashMap<VertexId, usize> =
+            vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut reachable = vec![BitSet::new(vertices.len()); vertices.len()];
+        // Successors come after a vertex in the topological order, so they are already done
+        for (index, vertex) in vertices.iter().enumerate().rev() {
+            let mut bits = BitSet::new(vertices.len());
+            for Edge(_, next) in graph.outbound_edges(*vertex) {
+                let next = indices[&next];
+                bits.insert(next);
+                bits.union_with(&reachable[next]);
+            }
+            reachable[index] = bits;
+        }
+        Reachability {
+            vertices,
+            indices,
+            reachable,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(size: usize) -> BitSet {
+        BitSet {
+            words: vec![0; size.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn union_with(&mut self, other: &BitSet) {
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word |= other;
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.words.len() * 64).filter(move |index| self.contains(*index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn transitive_closure_should_link_all_reachable_vertices() {
+        let g = graph_of(vec![(1, 2), (2, 3), (4, 3)]);
+        let closure = transitive_closure(&g);
+        assert_eq![
+            sorted_edges(&closure),
+            edges(vec![(1, 2), (1, 3), (2, 3), (4, 3)])
+        ];
+    }
+
+    #[test]
+    fn transitive_closure_should_add_self_loops_on_cycles() {
+        let g = graph_of(vec![(1, 2), (2, 1), (2, 3)]);
+        let closure = transitive_closure(&g);
+        assert_eq![
+            sorted_edges(&closure),
+            edges(vec![(1, 1), (1, 2), (1, 3), (2, 1), (2, 2), (2, 3)])
+        ];
+    }
+
+    #[test]
+    fn dag_transitive_closure_should_be_the_same_as_the_transitive_closure() {
+        let g = build_test_dag();
+        let dag = topo_sort::try_dag(&g).unwrap();
+        let closure = dag_transitive_closure(&dag);
+        assert_eq![
+            sorted_edges(&closure),
+            sorted_edges(&transitive_closure(&g))
+        ];
+        assert_eq![closure.vertex_count(), g.vertex_count()];
+    }
+
+    #[test]
+    fn dag_transitive_closure_should_work_with_more_than_64_vertices() {
+        let g = graph_of((0..100).map(|i| (i, i + 1)).collect());
+        let closure = dag_transitive_closure(&topo_sort::try_dag(&g).unwrap());
+        assert_eq![closure.edge_count(), 101 * 100 / 2];
+        assert![closure.contains_edge(edge(0, 100))];
+    }
+
+    #[test]
+    fn redundant_edges_should_find_edges_implied_by_longer_paths() {
+        let g = build_test_dag();
+        let redundant = redundant_edges(&topo_sort::try_dag(&g).unwrap());
+        assert_eq![redundant, edges(vec![(1, 3), (1, 4), (2, 4)])];
+    }
+
+    #[test]
+    fn transitive_reduction_should_keep_only_the_minimal_edges() {
+        let g = build_test_dag();
+        let reduction = transitive_reduction(&topo_sort::try_dag(&g).unwrap());
+        assert_eq![
+            sorted_edges(&reduction),
+            edges(vec![(1, 2), (2, 3), (3, 4), (5, 4)])
+        ];
+        assert_eq![
+            sorted_edges(&transitive_closure(&reduction)),
+            sorted_edges(&transitive_closure(&g))
+        ];
+    }
+
+    // Helpers
+
+    // 1 -> 2 -> 3 -> 4 <- 5, with shortcuts 1 -> 3, 1 -> 4 and 2 -> 4
+    fn build_test_dag() -> DirectedGraph {
+        graph_of(vec![(1, 2), (2, 3), (3, 4), (5, 4), (1, 3), (1, 4), (2, 4)])
+    }
+
+    fn sorted_edges(g: &DirectedGraph) -> Vec<Edge> {
+        let mut res: Vec<Edge> = g.edges().copied().collect();
+        res.sort();
+        res
+    }
+
+    fn edges(pairs: Vec<(u64, u64)>) -> Vec<Edge> {
+        pairs.into_iter().map(|(src, dst)| edge(src, dst)).collect()
+    }
+
+    fn graph_of(edges: Vec<(u64, u64)>) -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in edges {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
@@ -2,6 +2,7 @@
 use crate::attribute::mapping::*;
 use crate::graph::*;
 
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
 pub enum AttributeCommand<T> {
     AddVertexAttr(VertexId, T),
     RemoveVertexAttr(VertexId),
@@ -46,6 +46,22 @@ impl<K: Eq + Hash, V> AttributeMapping<K, V> {
         self.mapping.remove(e).is_some()
     }
 
+    pub fn get(&self, e: &K) -> Option<&V> {
+        self.mapping.get(e)
+    }
+
+    pub fn len(&self) -> usize {
+        self.mapping.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.mapping.iter()
+    }
+
     // Representing this mapping as closure
 
     pub fn as_closure<'a>(&'a self) -> impl Fn(&K) -> Option<&'a V> {
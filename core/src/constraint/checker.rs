@@ -2,6 +2,7 @@ use crate::constraint::constraint::Constraint;
 use crate::constraint::constraint::Constraint::*;
 use crate::graph::Edge;
 use crate::graph::VertexId;
+use crate::weight::Weight;
 
 use std::collections::HashSet;
 
@@ -18,7 +19,7 @@ use std::collections::HashSet;
 ///
 /// Note:
 /// This simple validation doesn't look into And/Or/Xor statement, nor dig recursively in a bunch of Not(Not(...)) definition
-pub fn validate(constraints: &Vec<Constraint>) -> Result<(), String> {
+pub fn validate<W: Weight>(constraints: &Vec<Constraint<W>>) -> Result<(), String> {
     check_vertex_inclusion_exclusion(constraints)?;
     check_edge_inclusion_exclusion(constraints)?;
     check_vertices_order(constraints)?;
@@ -28,7 +29,9 @@ pub fn validate(constraints: &Vec<Constraint>) -> Result<(), String> {
     ok()
 }
 
-fn check_vertex_inclusion_exclusion(constraints: &Vec<Constraint>) -> Result<(), String> {
+fn check_vertex_inclusion_exclusion<W: Weight>(
+    constraints: &Vec<Constraint<W>>,
+) -> Result<(), String> {
     let vertex_inclusions: HashSet<&VertexId> = constraints
         .iter()
         .filter_map(|c| match c {
@@ -60,7 +63,9 @@ fn check_vertex_inclusion_exclusion(constraints: &Vec<Constraint>) -> Result<(),
     }
 }
 
-fn check_edge_inclusion_exclusion(constraints: &Vec<Constraint>) -> Result<(), String> {
+fn check_edge_inclusion_exclusion<W: Weight>(
+    constraints: &Vec<Constraint<W>>,
+) -> Result<(), String> {
     let edge_inclusions: HashSet<&Edge> = constraints
         .iter()
         .filter_map(|c| match c {
@@ -88,12 +93,14 @@ fn check_edge_inclusion_exclusion(constraints: &Vec<Constraint>) -> Result<(), S
     }
 }
 
-fn check_vertices_order(_constraints: &Vec<Constraint>) -> Result<(), String> {
+fn check_vertices_order<W: Weight>(_constraints: &Vec<Constraint<W>>) -> Result<(), String> {
     // Nothing to check for now
     ok()
 }
 
-fn check_cycle_inclusion_exclusion(constraints: &Vec<Constraint>) -> Result<(), String> {
+fn check_cycle_inclusion_exclusion<W: Weight>(
+    constraints: &Vec<Constraint<W>>,
+) -> Result<(), String> {
     let contains_cycle = constraints.iter().find(|c| **c == ContainsCycle).is_some();
     let contains_no_cycle = negated_constraints(constraints)
         .find(|c| **c == ContainsCycle)
@@ -107,7 +114,7 @@ fn check_cycle_inclusion_exclusion(constraints: &Vec<Constraint>) -> Result<(),
     }
 }
 
-fn check_min_max_length(constraints: &Vec<Constraint>) -> Result<(), String> {
+fn check_min_max_length<W: Weight>(constraints: &Vec<Constraint<W>>) -> Result<(), String> {
     let min_length: Vec<usize> = constraints
         .iter()
         .filter_map(|c| match c {
@@ -140,15 +147,15 @@ fn check_min_max_length(constraints: &Vec<Constraint>) -> Result<(), String> {
     }
 }
 
-fn check_min_max_score(constraints: &Vec<Constraint>) -> Result<(), String> {
-    let min_score: Vec<i64> = constraints
+fn check_min_max_score<W: Weight>(constraints: &Vec<Constraint<W>>) -> Result<(), String> {
+    let min_score: Vec<W> = constraints
         .iter()
         .filter_map(|c| match c {
             MinScore(l) => Some(*l),
             _ => None,
         })
         .collect();
-    let max_score: Vec<i64> = constraints
+    let max_score: Vec<W> = constraints
         .iter()
         .filter_map(|c| match c {
             MaxScore(l) => Some(*l),
@@ -184,9 +191,9 @@ fn fail<T>(failure: T) -> Result<(), T> {
 }
 
 // Extract negated constraints
-fn negated_constraints<'a>(
-    constraints: impl IntoIterator<Item = &'a Constraint>,
-) -> impl Iterator<Item = &'a Constraint> {
+fn negated_constraints<'a, W: 'a>(
+    constraints: impl IntoIterator<Item = &'a Constraint<W>>,
+) -> impl Iterator<Item = &'a Constraint<W>> {
     constraints.into_iter().filter_map(|nc| match nc {
         Not(c) => Some(&**c),
         _ => None,
@@ -200,7 +207,7 @@ mod tests {
     #[test]
     fn check_vertex_inclusion_exclusion_should_accept_when_vertices_inclusion_and_exclusion_are_compatible(
     ) {
-        let constraints = vec![
+        let constraints: Vec<Constraint> = vec![
             ContainsVertex(vertex(4)),
             ContainsVertex(vertex(5)),
             ContainsVertex(vertex(6)),
@@ -213,7 +220,7 @@ mod tests {
     #[test]
     fn check_vertex_inclusion_exclusion_should_reject_when_a_vertex_is_included_and_excluded_at_the_same_time_1(
     ) {
-        let constraints = vec![
+        let constraints: Vec<Constraint> = vec![
             ContainsVertex(vertex(3)),
             Not(Box::new(ContainsVertex(vertex(3)))),
         ];
@@ -227,13 +234,13 @@ mod tests {
 
     #[test]
     fn check_min_max_length_should_accept_compatible_min_and_max_length_constraint() {
-        let constraints = vec![MaxLength(6), MinLength(5)];
+        let constraints: Vec<Constraint> = vec![MaxLength(6), MinLength(5)];
         assert_eq!(check_min_max_length(&constraints), Ok(()))
     }
 
     #[test]
     fn check_min_max_length_should_reject_incompatible_min_length_constraint() {
-        let constraints = vec![MinLength(5), MinLength(6)];
+        let constraints: Vec<Constraint> = vec![MinLength(5), MinLength(6)];
         assert_eq!(
             check_min_max_length(&constraints),
             Err("Incompatible set of MinLength constraints: [5, 6] were defined, having only one is possible".to_string())
@@ -242,7 +249,7 @@ mod tests {
 
     #[test]
     fn check_min_max_length_should_reject_incompatible_max_length_constraint() {
-        let constraints = vec![MaxLength(5), MaxLength(6)];
+        let constraints: Vec<Constraint> = vec![MaxLength(5), MaxLength(6)];
         assert_eq!(
             check_min_max_length(&constraints),
             Err("Incompatible set of MaxLength constraints: [5, 6] were defined, having only one is possible".to_string())
@@ -251,7 +258,7 @@ mod tests {
 
     #[test]
     fn check_min_max_length_should_reject_incompatible_min_and_max_length_constraint() {
-        let constraints = vec![MaxLength(5), MinLength(6)];
+        let constraints: Vec<Constraint> = vec![MaxLength(5), MinLength(6)];
         assert_eq!(
             check_min_max_length(&constraints),
             Err("Incompatible set of min/max length constraints: min=6, max=5".to_string())
@@ -262,13 +269,13 @@ mod tests {
 
     #[test]
     fn check_min_max_length_should_accept_compatible_min_and_max_score_constraint() {
-        let constraints = vec![MaxScore(6), MinScore(5)];
+        let constraints: Vec<Constraint> = vec![MaxScore(6), MinScore(5)];
         assert_eq!(check_min_max_score(&constraints), Ok(()))
     }
 
     #[test]
     fn check_min_max_length_should_reject_incompatible_min_score_constraint() {
-        let constraints = vec![MinScore(5), MinScore(6)];
+        let constraints: Vec<Constraint> = vec![MinScore(5), MinScore(6)];
         assert_eq!(
             check_min_max_score(&constraints),
             Err("Incompatible set of MinScore constraints: [5, 6] were defined, having only one is possible".to_string())
@@ -277,7 +284,7 @@ mod tests {
 
     #[test]
     fn check_min_max_length_should_reject_incompatible_max_score_constraint() {
-        let constraints = vec![MaxScore(5), MaxScore(6)];
+        let constraints: Vec<Constraint> = vec![MaxScore(5), MaxScore(6)];
         assert_eq!(
             check_min_max_score(&constraints),
             Err("Incompatible set of MaxScore constraints: [5, 6] were defined, having only one is possible".to_string())
@@ -286,7 +293,7 @@ mod tests {
 
     #[test]
     fn check_min_max_length_should_reject_incompatible_min_and_max_score_constraint() {
-        let constraints = vec![MaxScore(5), MinScore(6)];
+        let constraints: Vec<Constraint> = vec![MaxScore(5), MinScore(6)];
         assert_eq!(
             check_min_max_score(&constraints),
             Err("Incompatible set of min/max score constraints: min=6, max=5".to_string())
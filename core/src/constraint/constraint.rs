@@ -3,11 +3,12 @@ use crate::graph::VertexId;
 use crate::path::Path;
 use crate::path::ScoredPath;
 
-type ConstraintRef = Box<Constraint>;
+type ConstraintRef<W> = Box<Constraint<W>>;
 
 /// Constraints that can be applied to a ScoredPath
+/// Scores are any Weight, i64 by default
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Constraint {
+pub enum Constraint<W = i64> {
     /// Ensure the path contains the given VertexId
     ContainsVertex(VertexId),
     /// Ensure the path contains the given Edge
@@ -23,31 +24,31 @@ pub enum Constraint {
     /// Ensure the path has a maximum length
     MaxLength(usize),
     /// Ensure the path has a minimum score
-    MinScore(i64),
+    MinScore(W),
     /// Ensure the path has a maximum score
-    MaxScore(i64),
+    MaxScore(W),
     /// Ensure that at least one of the constraints is satified
-    Or(ConstraintRef, ConstraintRef),
+    Or(ConstraintRef<W>, ConstraintRef<W>),
     /// Ensure one or the other the constraint is satified
-    Xor(ConstraintRef, ConstraintRef),
+    Xor(ConstraintRef<W>, ConstraintRef<W>),
     /// Ensure both constraints are satified
-    And(ConstraintRef, ConstraintRef),
+    And(ConstraintRef<W>, ConstraintRef<W>),
     /// Ensure the constraint is not satisfied
-    Not(ConstraintRef),
+    Not(ConstraintRef<W>),
     // TODO could a Custom constraint to support user implemented constraints
     // But this will require dynamic dispatch :
     // Custom(Box<dyn Fn(ScoredPath) -> bool>, Box<dyn Fn(ScoredPath) -> bool>)
 }
 
-impl Constraint {
+impl<W: Ord> Constraint<W> {
     /// Check applied on a PARTIAL Path
     /// This check MUST return true IF the constraint has still a chance to be met later on
     /// (ie the Path will have more vertices added to it, and score will be increased)
-    pub fn check_partial(&self, partial: &ScoredPath) -> bool {
+    pub fn check_partial(&self, partial: &ScoredPath<W>) -> bool {
         use Constraint::*;
         match self {
             ContainsVertex(_) | ContainsEdge(_) => true,
-            OrderedVertices(ordered) => Constraint::check_vertices_order(&partial.path, ordered),
+            OrderedVertices(ordered) => Self::check_vertices_order(&partial.path, ordered),
             ContainsCycle => true,
             MinLength(_) | MinScore(_) => true,
             MaxLength(len) => partial.path.size() <= *len,
@@ -70,12 +71,12 @@ impl Constraint {
     }
 
     /// Check applied on a COMPLETE Path
-    pub fn check_complete(&self, full: &ScoredPath) -> bool {
+    pub fn check_complete(&self, full: &ScoredPath<W>) -> bool {
         use Constraint::*;
         match self {
             ContainsVertex(vid) => full.path.contains_vertex(vid),
             ContainsEdge(edge) => full.path.contains_edge(edge),
-            OrderedVertices(ordered) => Constraint::check_vertices_order(&full.path, ordered),
+            OrderedVertices(ordered) => Self::check_vertices_order(&full.path, ordered),
             ContainsCycle => full.path.contains_cycle(),
             MinLength(len) => full.path.size() >= *len,
             MaxLength(len) => full.path.size() <= *len,
@@ -89,6 +90,28 @@ impl Constraint {
         }
     }
 
+    /// Converts the scores of the constraint, and of the combined constraints
+    pub fn map_score<V, F>(&self, f: &F) -> Constraint<V>
+    where
+        F: Fn(&W) -> V,
+    {
+        use Constraint::*;
+        match self {
+            ContainsVertex(vid) => ContainsVertex(*vid),
+            ContainsEdge(edge) => ContainsEdge(*edge),
+            OrderedVertices(ordered) => OrderedVertices(ordered.clone()),
+            ContainsCycle => ContainsCycle,
+            MinLength(len) => MinLength(*len),
+            MaxLength(len) => MaxLength(*len),
+            MinScore(score) => MinScore(f(score)),
+            MaxScore(score) => MaxScore(f(score)),
+            Or(c1, c2) => Or(Box::new(c1.map_score(f)), Box::new(c2.map_score(f))),
+            Xor(c1, c2) => Xor(Box::new(c1.map_score(f)), Box::new(c2.map_score(f))),
+            And(c1, c2) => And(Box::new(c1.map_score(f)), Box::new(c2.map_score(f))),
+            Not(c1) => Not(Box::new(c1.map_score(f))),
+        }
+    }
+
     // Check the verices in the path appears by the specified order of ordered
     // Note: All vertices of ordered don't have to appear in the path
     fn check_vertices_order(path: &Path, ordered: &Vec<VertexId>) -> bool {
@@ -115,6 +138,7 @@ impl Constraint {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::weight::Real;
     use Constraint::*;
 
     // Partial contains vertex should always be true
@@ -416,6 +440,17 @@ mod tests {
         assert_eq!(Constraint::check_complete(&MaxScore(5), &path), true);
     }
 
+    #[test]
+    fn complete_max_score_should_support_fractional_scores() {
+        let path = ScoredPath {
+            path: path_of(vec![1, 2]),
+            score: Real(4.5),
+        };
+        assert!(!Constraint::check_complete(&MaxScore(Real(4.0)), &path));
+        assert!(Constraint::check_complete(&MaxScore(Real(4.5)), &path));
+        assert!(Constraint::check_partial(&MinScore(Real(5.0)), &path));
+    }
+
     // Or combinator
     #[test]
     fn complete_or_should_be_true_if_any_of_the_constraints_is_met() {
@@ -517,6 +552,20 @@ mod tests {
         );
     }
 
+    // Score conversion
+
+    #[test]
+    fn map_score_should_convert_nested_scores() {
+        let constraint = And(box_of(MaxScore(12)), box_of(Not(box_of(MinLength(3)))));
+        assert_eq!(
+            constraint.map_score(&|s: &i64| Real(*s as f64 / 2.0)),
+            And(
+                box_of(MaxScore(Real(6.0))),
+                box_of(Not(box_of(MinLength(3))))
+            )
+        );
+    }
+
     // Helper
 
     fn path_of(vertices: Vec<u64>) -> Path {
@@ -147,6 +147,40 @@ impl DirectedGraph {
     }
 }
 
+impl GraphView for DirectedGraph {
+    fn vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        Box::new(DirectedGraph::vertices(self).copied())
+    }
+
+    fn contains_vertex(&self, vertex_id: VertexId) -> bool {
+        DirectedGraph::contains_vertex(self, vertex_id)
+    }
+
+    fn outbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(DirectedGraph::outbound_edges(self, vertex_id).copied())
+    }
+
+    fn inbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(DirectedGraph::inbound_edges(self, vertex_id).copied())
+    }
+
+    fn edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(DirectedGraph::edges(self).copied())
+    }
+
+    fn contains_edge(&self, edge: Edge) -> bool {
+        DirectedGraph::contains_edge(self, edge)
+    }
+
+    fn vertex_count(&self) -> usize {
+        DirectedGraph::vertex_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        DirectedGraph::edge_count(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::graph::{Edge, VertexId};
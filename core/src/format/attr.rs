@@ -0,0 +1,203 @@
+//! 'attr' file format is a list of attribute commands that need to be applied in order to
+//! recreate the mapping of one attribute
+//!
+//! A command is one of :
+//! - AddVertexAttr <id> <value>
+//! - AddEdgeAttr <id> <id> <value>
+//! - RemoveVertexAttr <id>
+//! - RemoveEdgeAttr <id> <id>
+//!
+//! One command per line. A Commented line starts with #
+
+use crate::attribute::attribute_command::AttributeCommand;
+use crate::attribute::attribute_command::AttributeCommand::AddEdgeAttr;
+use crate::attribute::attribute_command::AttributeCommand::AddVertexAttr;
+use crate::attribute::attribute_command::AttributeCommand::RemoveEdgeAttr;
+use crate::attribute::attribute_command::AttributeCommand::RemoveVertexAttr;
+use crate::attribute::mapping::{AttributeMapping, EdgeAttrMapping, VertexAttrMapping};
+use crate::format::utils;
+use crate::graph::{Edge, VertexId};
+
+use lazy_static::*;
+use regex::Regex;
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+
+//
+// Reading an attribute file
+//
+
+/// Reads an attribute file into a list of ordered commands
+pub fn read_as_commands<T: FromStr>(file: File) -> Result<Vec<AttributeCommand<T>>, String> {
+    utils::read_as_commands(file, parse_line, is_comment)
+}
+
+/// Reads an attribute file into a mapping of vertex attributes
+/// Edge attribute commands are ignored
+pub fn read_vertex_mapping<T: FromStr>(
+    file: File,
+    name: &str,
+) -> Result<VertexAttrMapping<T>, String> {
+    let mut mapping = AttributeMapping::<VertexId, T>::new(name);
+    AttributeCommand::<T>::apply_vertex_commands_to(read_as_commands(file)?, &mut mapping);
+    Ok(mapping)
+}
+
+/// Reads an attribute file into a mapping of edge attributes
+/// Vertex attribute commands are ignored
+pub fn read_edge_mapping<T: FromStr>(file: File, name: &str) -> Result<EdgeAttrMapping<T>, String> {
+    let mut mapping = AttributeMapping::<Edge, T>::new(name);
+    AttributeCommand::<T>::apply_edge_commands_to(read_as_commands(file)?, &mut mapping);
+    Ok(mapping)
+}
+
+// Parses a line into an AttributeCommand
+fn parse_line<T: FromStr>(line: &str) -> Result<AttributeCommand<T>, String> {
+    lazy_static! {
+        static ref ADD_VERTEX_ATTR_RE: Regex = Regex::new(r"^AddVertexAttr (\d+)\s+(.+)$").unwrap();
+        static ref ADD_EDGE_ATTR_RE: Regex =
+            Regex::new(r"^AddEdgeAttr (\d+)\s+(\d+)\s+(.+)$").unwrap();
+        static ref REMOVE_VERTEX_ATTR_RE: Regex = Regex::new(r"^RemoveVertexAttr (\d+)$").unwrap();
+        static ref REMOVE_EDGE_ATTR_RE: Regex =
+            Regex::new(r"^RemoveEdgeAttr (\d+)\s+(\d+)$").unwrap();
+    }
+
+    if let Some(cap) = ADD_EDGE_ATTR_RE.captures_iter(line).next() {
+        let v1 = cap[1].parse::<u64>().unwrap();
+        let v2 = cap[2].parse::<u64>().unwrap();
+        let value = parse_value(&cap[3], line)?;
+        Ok(AddEdgeAttr(VertexId(v1), VertexId(v2), value))
+    } else if let Some(cap) = ADD_VERTEX_ATTR_RE.captures_iter(line).next() {
+        let v1 = cap[1].parse::<u64>().unwrap();
+        let value = parse_value(&cap[2], line)?;
+        Ok(AddVertexAttr(VertexId(v1), value))
+    } else if let Some(cap) = REMOVE_EDGE_ATTR_RE.captures_iter(line).next() {
+        let v1 = cap[1].parse::<u64>().unwrap();
+        let v2 = cap[2].parse::<u64>().unwrap();
+        Ok(RemoveEdgeAttr(VertexId(v1), VertexId(v2)))
+    } else if let Some(cap) = REMOVE_VERTEX_ATTR_RE.captures_iter(line).next() {
+        let v1 = cap[1].parse::<u64>().unwrap();
+        Ok(RemoveVertexAttr(VertexId(v1)))
+    } else {
+        Err(format!["Couldn't parse '{}'", line])
+    }
+}
+
+fn parse_value<T: FromStr>(value: &str, line: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| format!["Couldn't parse value '{}' in '{}'", value.trim(), line])
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim().starts_with('#')
+}
+
+//
+// Writing attribute files
+//
+
+/// Updates an attribute file with a list of AttributeCommand
+/// New commands will be appended at the end of the file, which is created if needed
+pub fn add_commands<T: Display>(
+    filename: &str,
+    commands: Vec<AttributeCommand<T>>,
+) -> std::io::Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)?;
+    let mut buffered = BufWriter::new(file);
+    for command in commands {
+        writeln![buffered, "{}", command_into_line(command)]?;
+    }
+    Ok(())
+}
+
+fn command_into_line<T: Display>(command: AttributeCommand<T>) -> String {
+    match command {
+        AddVertexAttr(VertexId(vid), value) => format!["AddVertexAttr {} {}", vid, value],
+        RemoveVertexAttr(VertexId(vid)) => format!["RemoveVertexAttr {}", vid],
+        AddEdgeAttr(VertexId(src), VertexId(dest), value) => {
+            format!["AddEdgeAttr {} {} {}", src, dest, value]
+        }
+        RemoveEdgeAttr(VertexId(src), VertexId(dest)) => {
+            format!["RemoveEdgeAttr {} {}", src, dest]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_add_vertex_attr_line_should_work() {
+        assert_eq![
+            parse_line::<u64>("AddVertexAttr 123456 42"),
+            Ok(AddVertexAttr(VertexId(123456), 42))
+        ]
+    }
+
+    #[test]
+    fn parse_add_edge_attr_line_should_work() {
+        assert_eq![
+            parse_line::<u64>("AddEdgeAttr 123456 784695 42"),
+            Ok(AddEdgeAttr(VertexId(123456), VertexId(784695), 42))
+        ]
+    }
+
+    #[test]
+    fn parse_add_attr_line_should_keep_the_whole_value() {
+        assert_eq![
+            parse_line::<String>("AddVertexAttr 1 a label with spaces"),
+            Ok(AddVertexAttr(
+                VertexId(1),
+                "a label with spaces".to_string()
+            ))
+        ]
+    }
+
+    #[test]
+    fn parse_remove_vertex_attr_line_should_work() {
+        assert_eq![
+            parse_line::<u64>("RemoveVertexAttr 123456"),
+            Ok(RemoveVertexAttr(VertexId(123456)))
+        ]
+    }
+
+    #[test]
+    fn parse_remove_edge_attr_line_should_work() {
+        assert_eq![
+            parse_line::<u64>("RemoveEdgeAttr 123456 784695"),
+            Ok(RemoveEdgeAttr(VertexId(123456), VertexId(784695)))
+        ]
+    }
+
+    #[test]
+    fn parse_attr_line_with_an_invalid_value_should_fail() {
+        assert_eq![
+            parse_line::<u64>("AddEdgeAttr 1 2 abc"),
+            Err("Couldn't parse value 'abc' in 'AddEdgeAttr 1 2 abc'".to_string())
+        ]
+    }
+
+    #[test]
+    fn command_into_line_translate_add_edge_attr() {
+        assert_eq![
+            command_into_line(AddEdgeAttr(VertexId(123456), VertexId(784695), 42)),
+            "AddEdgeAttr 123456 784695 42"
+        ]
+    }
+
+    #[test]
+    fn command_into_line_translate_remove_vertex_attr() {
+        assert_eq![
+            command_into_line::<u64>(RemoveVertexAttr(VertexId(123456))),
+            "RemoveVertexAttr 123456"
+        ]
+    }
+}
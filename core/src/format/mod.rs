@@ -1,3 +1,4 @@
+pub mod attr;
 pub mod gcmd;
 pub mod tgf;
 mod utils;
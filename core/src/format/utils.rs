@@ -26,18 +26,18 @@ where
     })
 }
 
-/// Reads a TGF file as a list of commands
-pub fn read_as_commands<ParseFn, IsCommentFn>(
+/// Reads a file as a list of commands
+pub fn read_as_commands<C, ParseFn, IsCommentFn>(
     file: File,
     parse_line: ParseFn,
     is_comment: IsCommentFn,
-) -> Result<Vec<GraphCommand>, String>
+) -> Result<Vec<C>, String>
 where
-    ParseFn: Fn(&str) -> Result<GraphCommand, String>,
+    ParseFn: Fn(&str) -> Result<C, String>,
     IsCommentFn: Fn(&str) -> bool,
 {
     let reader = BufReader::new(file);
-    let mut result: Vec<GraphCommand> = vec![];
+    let mut result: Vec<C> = vec![];
     for (index, line) in reader.lines().enumerate() {
         let line = line.unwrap(); // Ignore errors.
         if !line.is_empty() && !is_comment(&line) {
@@ -22,3 +22,48 @@ impl From<(VertexId, VertexId)> for Edge {
         Edge(pair.0, pair.1)
     }
 }
+
+/// Read-only access to a directed graph
+/// Implemented by `DirectedGraph` and by the views of the `view` module, which adapt a graph
+/// without copying it. Algorithms generic over this trait run on any of them.
+pub trait GraphView {
+    fn vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_>;
+
+    fn contains_vertex(&self, vertex_id: VertexId) -> bool;
+
+    fn outbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_>;
+
+    fn inbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_>;
+
+    fn edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.vertices().flat_map(move |v| self.outbound_edges(v)))
+    }
+
+    fn contains_edge(&self, edge: Edge) -> bool {
+        self.outbound_edges(edge.0).any(|e| e == edge)
+    }
+
+    fn head_option(&self) -> Option<VertexId> {
+        self.vertices().next()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.head_option().is_none()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.vertices().count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+
+    fn degree_out(&self, vertex_id: VertexId) -> usize {
+        self.outbound_edges(vertex_id).count()
+    }
+
+    fn degree_in(&self, vertex_id: VertexId) -> usize {
+        self.inbound_edges(vertex_id).count()
+    }
+}
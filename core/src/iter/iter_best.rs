@@ -1,29 +1,34 @@
 ///! Graph Iterator implementation
 use crate::directed_graph::DirectedGraph;
-use crate::graph::{Edge, VertexId};
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::iter::iter_datastructure::{MinPriorityQueue, SearchQueue};
 use crate::path::Path;
 
 use crate::path::ScoredPath;
+use crate::weight::Weight;
 use std::collections::HashSet;
 
 /// Best-First search iterator
 
-pub struct BestFirstIter<'a, F>
+pub struct BestFirstIter<'a, F, W, G = DirectedGraph>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
-    queue: MinPriorityQueue<ScoredPath>,
+    queue: MinPriorityQueue<ScoredPath<W>>,
     visited: HashSet<VertexId>,
-    graph: &'a DirectedGraph,
+    graph: &'a G,
     scorefn: F,
 }
 
-impl<'a, F> Iterator for BestFirstIter<'a, F>
+impl<'a, F, W, G> Iterator for BestFirstIter<'a, F, W, G>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
-    type Item = ScoredPath;
+    type Item = ScoredPath<W>;
     fn next(&mut self) -> Option<Self::Item> {
         match self.queue.pop() {
             None => None,
@@ -33,11 +38,11 @@ where
                     .outbound_edges(*vid)
                     .map(|Edge(_, v)| v)
                     .for_each(|v| {
-                        if !self.visited.contains(v) {
-                            self.visited.insert(*v);
-                            let new_path = weighted_path.path.append(*v);
+                        if !self.visited.contains(&v) {
+                            self.visited.insert(v);
+                            let new_path = weighted_path.path.append(v);
                             let new_scored_path = ScoredPath {
-                                path: weighted_path.path.append(*v),
+                                path: weighted_path.path.append(v),
                                 score: (self.scorefn)(self.graph, &new_path),
                             };
                             self.queue.push(new_scored_path)
@@ -50,16 +55,18 @@ where
 }
 
 /// Returns a new best first search iterator on the given graph, starting from the given start_vertex
-pub fn best_iter_from<F>(
-    graph: &DirectedGraph,
+pub fn best_iter_from<G, F, W>(
+    graph: &G,
     scorefn: F,
     start_vertex: VertexId,
-) -> BestFirstIter<F>
+) -> BestFirstIter<F, W, G>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
     let path = Path::empty().append(start_vertex);
-    let score = scorefn(&graph, &path);
+    let score = scorefn(graph, &path);
     let mut iter = empty_best_iter(graph, scorefn);
     iter.queue.push(ScoredPath { path, score });
     iter.visited.insert(start_vertex);
@@ -67,12 +74,14 @@ where
 }
 
 /// Builds an empty iterator from a given graph.
-fn empty_best_iter<F>(graph: &DirectedGraph, scorefn: F) -> BestFirstIter<F>
+fn empty_best_iter<G, F, W>(graph: &G, scorefn: F) -> BestFirstIter<F, W, G>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
     BestFirstIter {
-        queue: MinPriorityQueue::<ScoredPath>::new(),
+        queue: MinPriorityQueue::<ScoredPath<W>>::new(),
         visited: HashSet::new(),
         graph: graph,
         scorefn: scorefn,
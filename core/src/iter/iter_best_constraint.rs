@@ -1,29 +1,34 @@
 use crate::constraint::constraint::Constraint;
 ///! Best-First search Iterator with constraint implementation
 use crate::directed_graph::DirectedGraph;
-use crate::graph::{Edge, VertexId};
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::iter::iter_datastructure::{MinPriorityQueue, SearchQueue};
 use crate::path::Path;
 
 use crate::path::ScoredPath;
+use crate::weight::Weight;
 
 /// Best-First search iterator
 
-pub struct ConstrainedBestFirstIter<'a, F>
+pub struct ConstrainedBestFirstIter<'a, F, W, G = DirectedGraph>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
-    queue: MinPriorityQueue<ScoredPath>,
-    graph: &'a DirectedGraph,
+    queue: MinPriorityQueue<ScoredPath<W>>,
+    graph: &'a G,
     scorefn: F,
-    constraints: Vec<Constraint>,
+    constraints: Vec<Constraint<W>>,
 }
 
-impl<'a, F> Iterator for ConstrainedBestFirstIter<'a, F>
+impl<'a, F, W, G> Iterator for ConstrainedBestFirstIter<'a, F, W, G>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
-    type Item = ScoredPath;
+    type Item = ScoredPath<W>;
     fn next(&mut self) -> Option<Self::Item> {
         match self.queue.pop() {
             None => None,
@@ -33,9 +38,9 @@ where
                     .outbound_edges(*vid)
                     .map(|Edge(_, v)| v)
                     .for_each(|v| {
-                        let new_path = weighted_path.path.append(*v);
+                        let new_path = weighted_path.path.append(v);
                         let new_scored_path = ScoredPath {
-                            path: weighted_path.path.append(*v),
+                            path: weighted_path.path.append(v),
                             score: (self.scorefn)(self.graph, &new_path),
                         };
                         // If the newly generated partial path is still a good candidate
@@ -50,20 +55,25 @@ where
     }
 }
 
-fn check_all_partial_constraints(sp: &ScoredPath, constraints: &Vec<Constraint>) -> bool {
+fn check_all_partial_constraints<W: Ord>(
+    sp: &ScoredPath<W>,
+    constraints: &Vec<Constraint<W>>,
+) -> bool {
     constraints.iter().all(|c| c.check_partial(sp))
 }
 
 /// Returns a new constrained best first search iterator on the given graph,
 /// starting from the given start_vertex
-pub fn constrained_best_iter_from<F>(
-    graph: &DirectedGraph,
+pub fn constrained_best_iter_from<G, F, W>(
+    graph: &G,
     scorefn: F,
-    constraints: Vec<Constraint>,
+    constraints: Vec<Constraint<W>>,
     start_vertex: VertexId,
-) -> ConstrainedBestFirstIter<F>
+) -> ConstrainedBestFirstIter<F, W, G>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
     let path = Path::empty().append(start_vertex);
     let score = scorefn(graph, &path);
@@ -73,16 +83,18 @@ where
 }
 
 /// Builds an empty constrained iterator from a given graph.
-fn empty_constrained_best_iter<F>(
-    graph: &DirectedGraph,
+fn empty_constrained_best_iter<G, F, W>(
+    graph: &G,
     scorefn: F,
-    constraints: Vec<Constraint>,
-) -> ConstrainedBestFirstIter<F>
+    constraints: Vec<Constraint<W>>,
+) -> ConstrainedBestFirstIter<F, W, G>
 where
-    F: Fn(&DirectedGraph, &Path) -> i64,
+    G: GraphView,
+    F: Fn(&G, &Path) -> W,
+    W: Weight,
 {
     ConstrainedBestFirstIter {
-        queue: MinPriorityQueue::<ScoredPath>::new(),
+        queue: MinPriorityQueue::<ScoredPath<W>>::new(),
         graph: graph,
         scorefn: scorefn,
         constraints: constraints,
@@ -2,18 +2,18 @@
 use std::collections::HashSet;
 
 use crate::directed_graph::DirectedGraph;
-use crate::graph::{Edge, VertexId};
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::iter::iter_datastructure::{Queue, SearchQueue};
 use crate::path::Path;
 
 /// Breadth-First search iterator
-pub struct BreadthFirstIter<'a> {
+pub struct BreadthFirstIter<'a, G: GraphView = DirectedGraph> {
     queue: Queue<VertexId>,
     visited: HashSet<VertexId>,
-    graph: &'a DirectedGraph,
+    graph: &'a G,
 }
 
-impl<'a> Iterator for BreadthFirstIter<'a> {
+impl<'a, G: GraphView> Iterator for BreadthFirstIter<'a, G> {
     type Item = VertexId;
     fn next(&mut self) -> Option<VertexId> {
         match self.queue.pop() {
@@ -23,9 +23,9 @@ impl<'a> Iterator for BreadthFirstIter<'a> {
                     .outbound_edges(vid)
                     .map(|Edge(_, v)| v)
                     .for_each(|v| {
-                        if !self.visited.contains(v) {
-                            self.visited.insert(*v);
-                            self.queue.push(*v)
+                        if !self.visited.contains(&v) {
+                            self.visited.insert(v);
+                            self.queue.push(v)
                         }
                     });
                 Some(vid)
@@ -35,15 +35,15 @@ impl<'a> Iterator for BreadthFirstIter<'a> {
 }
 
 /// Returns a new breadth first search iterator on the given graph
-pub fn bfs_iter(graph: &DirectedGraph) -> BreadthFirstIter {
+pub fn bfs_iter<G: GraphView>(graph: &G) -> BreadthFirstIter<G> {
     match graph.head_option() {
         None => empty_bfs_iter(graph),
-        Some(head) => bfs_iter_from(graph, *head),
+        Some(head) => bfs_iter_from(graph, head),
     }
 }
 
 /// Returns a new breadth first search iterator on the given graph, starting from the given start_vertex
-pub fn bfs_iter_from(graph: &DirectedGraph, start_vertex: VertexId) -> BreadthFirstIter {
+pub fn bfs_iter_from<G: GraphView>(graph: &G, start_vertex: VertexId) -> BreadthFirstIter<G> {
     let mut iter = empty_bfs_iter(graph);
     iter.queue.push(start_vertex);
     iter.visited.insert(start_vertex);
@@ -51,7 +51,7 @@ pub fn bfs_iter_from(graph: &DirectedGraph, start_vertex: VertexId) -> BreadthFi
 }
 
 /// Builds an empty iterator from a given graph.
-fn empty_bfs_iter(graph: &DirectedGraph) -> BreadthFirstIter {
+fn empty_bfs_iter<G: GraphView>(graph: &G) -> BreadthFirstIter<G> {
     BreadthFirstIter {
         queue: Queue::<VertexId>::new(),
         visited: HashSet::new(),
@@ -61,12 +61,12 @@ fn empty_bfs_iter(graph: &DirectedGraph) -> BreadthFirstIter {
 
 /// Breadth-First Path iterator
 
-pub struct BreadthFirstPathIter<'a> {
+pub struct BreadthFirstPathIter<'a, G: GraphView = DirectedGraph> {
     queue: Queue<Path>,
-    graph: &'a DirectedGraph,
+    graph: &'a G,
 }
 
-impl<'a> Iterator for BreadthFirstPathIter<'a> {
+impl<'a, G: GraphView> Iterator for BreadthFirstPathIter<'a, G> {
     type Item = Path;
     fn next(&mut self) -> Option<Path> {
         match self.queue.pop() {
@@ -79,8 +79,8 @@ impl<'a> Iterator for BreadthFirstPathIter<'a> {
                     .outbound_edges(*vid)
                     .map(|Edge(_, v)| v)
                     .for_each(|v| {
-                        if !path.contains_vertex(v) {
-                            self.queue.push(path.append(*v))
+                        if !path.contains_vertex(&v) {
+                            self.queue.push(path.append(v))
                         }
                     });
                 Some(path)
@@ -90,14 +90,17 @@ impl<'a> Iterator for BreadthFirstPathIter<'a> {
 }
 
 /// Returns a new breadth first search iterator on the given graph, starting from the given start_vertex
-pub fn bfs_path_iter_from(graph: &DirectedGraph, start_vertex: VertexId) -> BreadthFirstPathIter {
+pub fn bfs_path_iter_from<G: GraphView>(
+    graph: &G,
+    start_vertex: VertexId,
+) -> BreadthFirstPathIter<G> {
     let mut iter = empty_bfs_path_iter(graph);
     iter.queue.push(Path::from(&vec![start_vertex]));
     iter
 }
 
 /// Builds an empty iterator from a given graph.
-fn empty_bfs_path_iter(graph: &DirectedGraph) -> BreadthFirstPathIter {
+fn empty_bfs_path_iter<G: GraphView>(graph: &G) -> BreadthFirstPathIter<G> {
     BreadthFirstPathIter {
         queue: Queue::<Path>::new(),
         graph: graph,
@@ -1,11 +1,12 @@
 //! Cycle iterator
+use crate::algorithm::connectivity;
 use crate::directed_graph::DirectedGraph;
-use crate::graph::{Edge, VertexId};
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::iter::iter_datastructure::{SearchQueue, Stack};
 use crate::path::Path;
 
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Represents a cycle.
 /// In a cycle representation no element appears twice.
@@ -30,6 +31,13 @@ impl Cycle {
         }
     }
 
+    /// Cycle made of a single vertex with an edge to itself
+    pub fn self_loop(vertex: VertexId) -> Cycle {
+        Cycle {
+            vertices: vec![vertex],
+        }
+    }
+
     /// Converts a cycle into a path
     /// Path will include first and last element that are identical
     pub fn as_path(&self) -> Path {
@@ -74,14 +82,14 @@ impl Cycle {
     }
 }
 
-pub struct CycleIter<'a> {
+pub struct CycleIter<'a, G: GraphView = DirectedGraph> {
     stack: Stack<Path>,
     returned: HashSet<Cycle>,
-    graph: &'a DirectedGraph,
+    graph: &'a G,
 }
 
 /// Iterates over all the unique cycles from a Graph
-impl<'a> Iterator for CycleIter<'a> {
+impl<'a, G: GraphView> Iterator for CycleIter<'a, G> {
     type Item = Cycle;
 
     fn next(&mut self) -> Option<Cycle> {
@@ -99,7 +107,7 @@ impl<'a> Iterator for CycleIter<'a> {
                     self.graph
                         .outbound_edges(*last)
                         .map(|Edge(_, v)| v)
-                        .for_each(|v| self.stack.push(path.append(*v)));
+                        .for_each(|v| self.stack.push(path.append(v)));
                 }
             }
         }
@@ -110,18 +118,20 @@ impl<'a> Iterator for CycleIter<'a> {
 }
 
 /// Returns a new cycle iterator on the given graph
-pub fn cycle_iter(graph: &DirectedGraph) -> CycleIter {
+/// Cycles are only searched from the vertices without inbound edges, and every cycle found
+/// is kept in memory. See circuit_iter to iterate on all the cycles of any graph
+pub fn cycle_iter<G: GraphView>(graph: &G) -> CycleIter<G> {
     let starting_vertices = find_starting_edges(graph);
     let mut cycle_iter = empty_cycle_iter(graph);
     for vertex in starting_vertices {
-        let path = Path::from(&vec![*vertex]);
+        let path = Path::from(&vec![vertex]);
         cycle_iter.stack.push(path);
     }
     cycle_iter
 }
 
 /// Builds an empty iterator from a given graph.
-fn empty_cycle_iter(graph: &DirectedGraph) -> CycleIter {
+fn empty_cycle_iter<G: GraphView>(graph: &G) -> CycleIter<G> {
     CycleIter {
         stack: Stack::<Path>::new(),
         returned: HashSet::new(),
@@ -130,19 +140,168 @@ fn empty_cycle_iter(graph: &DirectedGraph) -> CycleIter {
 }
 
 // Note: This is assuming a connected graph
-fn find_starting_edges(graph: &DirectedGraph) -> Vec<&VertexId> {
+fn find_starting_edges<G: GraphView>(graph: &G) -> Vec<VertexId> {
     let mut res = graph
         .vertices()
-        .filter(|vid| graph.inbound_edges(**vid).count() == 0)
-        .collect::<Vec<&VertexId>>();
+        .filter(|vid| graph.inbound_edges(*vid).count() == 0)
+        .collect::<Vec<VertexId>>();
 
     // If no vertex with no inbound edges can be found, we need to randomly add a vertex
     if res.is_empty() {
-        graph.head_option().iter().for_each(|x| res.push(x));
+        res.extend(graph.head_option());
     }
     res
 }
 
+/// Iterates over the elementary circuits of a graph, each returned exactly once
+/// Circuits are in canonical representation. Self loops are not returned as they are not cycles
+/// Implementation of Johnson algorithm, running in O((V + E)(C + 1)) for C circuits,
+/// with a memory bounded by the size of the graph
+/// See Johnson, "Finding all the elementary circuits of a directed graph" (1975)
+pub struct CircuitIter {
+    // Strongly connected components still to search, as subgraphs
+    components: Vec<DirectedGraph>,
+    search: Option<CircuitSearch>,
+}
+
+impl Iterator for CircuitIter {
+    type Item = Cycle;
+
+    fn next(&mut self) -> Option<Cycle> {
+        loop {
+            if let Some(search) = &mut self.search {
+                if let Some(cycle) = search.next_circuit() {
+                    return Some(cycle);
+                }
+                // All the circuits through the start vertex were found, so it is removed
+                let mut rest = self.search.take().unwrap().component;
+                rest.remove_vertex(smallest_vertex(&rest));
+                self.components.extend(component_subgraphs(&rest));
+            }
+            match self.components.pop() {
+                Some(component) => self.search = Some(CircuitSearch::new(component)),
+                None => return None,
+            }
+        }
+    }
+}
+
+/// Returns a new iterator on the elementary circuits of the given graph
+pub fn circuit_iter<G: GraphView>(graph: &G) -> CircuitIter {
+    CircuitIter {
+        components: component_subgraphs(graph),
+        search: None,
+    }
+}
+
+// Search of the circuits going through the smallest vertex of a strongly connected component
+struct CircuitSearch {
+    component: DirectedGraph,
+    start: VertexId,
+    path: Vec<VertexId>,
+    // Each vertex of the path with the successors left to visit
+    stack: Vec<(VertexId, Vec<VertexId>)>,
+    blocked: HashSet<VertexId>,
+    // Vertices to unblock when the key vertex is unblocked
+    blocked_by: HashMap<VertexId, HashSet<VertexId>>,
+    // Vertices of the path from which a circuit was found
+    closed: HashSet<VertexId>,
+}
+
+impl CircuitSearch {
+    fn new(component: DirectedGraph) -> CircuitSearch {
+        let start = smallest_vertex(&component);
+        let successors = successors(&component, start);
+        let mut blocked = HashSet::new();
+        blocked.insert(start);
+        CircuitSearch {
+            component,
+            start,
+            path: vec![start],
+            stack: vec![(start, successors)],
+            blocked,
+            blocked_by: HashMap::new(),
+            closed: HashSet::new(),
+        }
+    }
+
+    fn next_circuit(&mut self) -> Option<Cycle> {
+        while let Some((vertex, remaining)) = self.stack.last_mut() {
+            let vertex = *vertex;
+            if let Some(next) = remaining.pop() {
+                if next == self.start {
+                    self.closed.extend(self.path.iter());
+                    return Some(Cycle {
+                        vertices: self.path.clone(),
+                    });
+                } else if !self.blocked.contains(&next) {
+                    self.path.push(next);
+                    self.stack.push((next, successors(&self.component, next)));
+                    self.closed.remove(&next);
+                    self.blocked.insert(next);
+                }
+                continue;
+            }
+            if self.closed.contains(&vertex) {
+                self.unblock(vertex);
+            } else {
+                for Edge(_, next) in self.component.outbound_edges(vertex) {
+                    self.blocked_by.entry(*next).or_default().insert(vertex);
+                }
+            }
+            self.stack.pop();
+            self.path.pop();
+        }
+        None
+    }
+
+    fn unblock(&mut self, vertex: VertexId) {
+        let mut to_unblock = vec![vertex];
+        while let Some(vertex) = to_unblock.pop() {
+            if self.blocked.remove(&vertex) {
+                if let Some(vertices) = self.blocked_by.remove(&vertex) {
+                    to_unblock.extend(vertices);
+                }
+            }
+        }
+    }
+}
+
+// Starting from the smallest vertex makes the circuits canonical
+fn smallest_vertex(component: &DirectedGraph) -> VertexId {
+    *component
+        .vertices()
+        .min()
+        .expect("A strongly connected component is not empty")
+}
+
+fn successors(graph: &DirectedGraph, vertex: VertexId) -> Vec<VertexId> {
+    graph
+        .outbound_edges(vertex)
+        .map(|Edge(_, dst)| *dst)
+        .collect()
+}
+
+// Subgraphs of the strongly connected components that contain a cycle, without self loops
+fn component_subgraphs<G: GraphView>(graph: &G) -> Vec<DirectedGraph> {
+    connectivity::strongly_connected_components(graph)
+        .into_iter()
+        .filter(|vertices| vertices.len() > 1)
+        .map(|vertices| {
+            let members: HashSet<VertexId> = vertices.iter().copied().collect();
+            let mut subgraph = DirectedGraph::new();
+            for vertex in &vertices {
+                for edge in graph.outbound_edges(*vertex) {
+                    if edge.0 != edge.1 && members.contains(&edge.1) {
+                        subgraph.add_edge(edge);
+                    }
+                }
+            }
+            subgraph
+        })
+        .collect()
+}
+
 // Helpers
 
 // Extracts a cycle made by the last element of a Path
@@ -325,6 +484,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn circuit_iterator_should_return_all_cycles_in_graph() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        g.add_edge(edge(3, 4));
+        g.add_edge(edge(4, 5));
+        g.add_edge(edge(5, 1));
+        g.add_edge(edge(2, 4));
+        g.add_edge(edge(5, 3));
+
+        let cycles = circuit_iter(&g).sorted().collect::<Vec<Cycle>>();
+        assert_eq!(
+            cycles,
+            vec![
+                cycle(vec![1, 2, 3, 4, 5]).unwrap(),
+                cycle(vec![1, 2, 4, 5]).unwrap(),
+                cycle(vec![3, 4, 5]).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn circuit_iterator_should_work_on_a_disconnected_graph() {
+        let mut g = DirectedGraph::new();
+        // First component
+        g.add_edge(edge(1, 3));
+        g.add_edge(edge(3, 4));
+        g.add_edge(edge(4, 7));
+        g.add_edge(edge(4, 5));
+        g.add_edge(edge(5, 7));
+        g.add_edge(edge(5, 6));
+        g.add_edge(edge(2, 3));
+        g.add_edge(edge(6, 1));
+        g.add_edge(edge(6, 3));
+        // Second component, no vertex with no inbound edges
+        g.add_edge(edge(10, 20));
+        g.add_edge(edge(20, 30));
+        g.add_edge(edge(30, 40));
+        g.add_edge(edge(40, 50));
+        g.add_edge(edge(50, 10));
+        g.add_edge(edge(20, 40));
+        g.add_edge(edge(50, 30));
+
+        let cycles = circuit_iter(&g).sorted().collect::<Vec<Cycle>>();
+        assert_eq!(
+            cycles,
+            vec![
+                cycle(vec![1, 3, 4, 5, 6]).unwrap(),
+                cycle(vec![3, 4, 5, 6]).unwrap(),
+                cycle(vec![10, 20, 30, 40, 50]).unwrap(),
+                cycle(vec![10, 20, 40, 50]).unwrap(),
+                cycle(vec![30, 40, 50]).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn circuit_iterator_should_return_each_cycle_of_a_complete_graph_once() {
+        let mut g = DirectedGraph::new();
+        for src in 1..=5 {
+            for dst in 1..=5 {
+                g.add_edge(edge(src, dst));
+            }
+        }
+        // Sum over k of C(5, k) * (k - 1)!, self loops excluded
+        let cycles = circuit_iter(&g).collect::<Vec<Cycle>>();
+        assert_eq!(cycles.len(), 84);
+        assert_eq!(cycles.iter().unique().count(), 84);
+        assert!(cycles.iter().all(|c| c.is_canonical()));
+    }
+
+    #[test]
+    fn circuit_iterator_should_return_nothing_on_a_dag() {
+        let mut g = DirectedGraph::new();
+        g.add_edge(edge(1, 2));
+        g.add_edge(edge(2, 3));
+        g.add_edge(edge(1, 3));
+        g.add_edge(edge(3, 3));
+
+        assert_eq!(circuit_iter(&g).count(), 0);
+    }
+
     // Helpers
 
     fn vertex(id: u64) -> VertexId {
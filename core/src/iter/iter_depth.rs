@@ -2,19 +2,19 @@
 use std::collections::HashSet;
 
 use crate::directed_graph::DirectedGraph;
-use crate::graph::{Edge, VertexId};
+use crate::graph::{Edge, GraphView, VertexId};
 use crate::iter::iter_datastructure::{SearchQueue, Stack};
 use crate::path::Path;
 
 /// Depth-First search iterator
 /// Generates vertex as visited by a depth-first search
-pub struct DepthFirstIter<'a> {
+pub struct DepthFirstIter<'a, G: GraphView = DirectedGraph> {
     stack: Stack<VertexId>,
     visited: HashSet<VertexId>,
-    graph: &'a DirectedGraph,
+    graph: &'a G,
 }
 
-impl<'a> Iterator for DepthFirstIter<'a> {
+impl<'a, G: GraphView> Iterator for DepthFirstIter<'a, G> {
     type Item = VertexId;
 
     // Note:
@@ -28,9 +28,9 @@ impl<'a> Iterator for DepthFirstIter<'a> {
                     .outbound_edges(vid)
                     .map(|Edge(_, v)| v)
                     .for_each(|v| {
-                        if !self.visited.contains(v) {
-                            self.visited.insert(*v);
-                            self.stack.push(*v)
+                        if !self.visited.contains(&v) {
+                            self.visited.insert(v);
+                            self.stack.push(v)
                         }
                     });
                 Some(vid)
@@ -40,15 +40,15 @@ impl<'a> Iterator for DepthFirstIter<'a> {
 }
 
 /// Returns a new depth first search iterator on the given graph
-pub fn dfs_iter(graph: &DirectedGraph) -> DepthFirstIter {
+pub fn dfs_iter<G: GraphView>(graph: &G) -> DepthFirstIter<G> {
     match graph.head_option() {
         None => empty_dfs_iter(graph),
-        Some(head) => dfs_iter_from(graph, *head),
+        Some(head) => dfs_iter_from(graph, head),
     }
 }
 
 /// Returns a new depth first search iterator on the given graph, starting from the given start_vertex
-pub fn dfs_iter_from(graph: &DirectedGraph, start_vertex: VertexId) -> DepthFirstIter {
+pub fn dfs_iter_from<G: GraphView>(graph: &G, start_vertex: VertexId) -> DepthFirstIter<G> {
     let mut dfs_iter = empty_dfs_iter(graph);
     dfs_iter.stack.push(start_vertex);
     dfs_iter.visited.insert(start_vertex);
@@ -56,7 +56,7 @@ pub fn dfs_iter_from(graph: &DirectedGraph, start_vertex: VertexId) -> DepthFirs
 }
 
 /// Builds an empty iterator for a given graph.
-fn empty_dfs_iter(graph: &DirectedGraph) -> DepthFirstIter {
+fn empty_dfs_iter<G: GraphView>(graph: &G) -> DepthFirstIter<G> {
     DepthFirstIter {
         stack: Stack::<VertexId>::new(),
         visited: HashSet::new(),
@@ -66,12 +66,12 @@ fn empty_dfs_iter(graph: &DirectedGraph) -> DepthFirstIter {
 
 /// Depth-First search iterator, Returning a full path from the first vertex
 /// Useful to generate all possible path without cycle from a given vertex
-pub struct DepthFirstPathIter<'a> {
+pub struct DepthFirstPathIter<'a, G: GraphView = DirectedGraph> {
     stack: Stack<Path>,
-    graph: &'a DirectedGraph,
+    graph: &'a G,
 }
 
-impl<'a> Iterator for DepthFirstPathIter<'a> {
+impl<'a, G: GraphView> Iterator for DepthFirstPathIter<'a, G> {
     type Item = Path;
 
     // Note:
@@ -88,8 +88,8 @@ impl<'a> Iterator for DepthFirstPathIter<'a> {
                     .outbound_edges(*vid)
                     .map(|Edge(_, v)| v)
                     .for_each(|v| {
-                        if !path.contains_vertex(v) {
-                            self.stack.push(path.append(*v));
+                        if !path.contains_vertex(&v) {
+                            self.stack.push(path.append(v));
                         }
                     });
                 Some(path)
@@ -99,14 +99,17 @@ impl<'a> Iterator for DepthFirstPathIter<'a> {
 }
 
 /// Returns a new depth first search iterator on the given graph, starting from the given start_vertex
-pub fn dfs_iter_path_from(graph: &DirectedGraph, start_vertex: VertexId) -> DepthFirstPathIter {
+pub fn dfs_iter_path_from<G: GraphView>(
+    graph: &G,
+    start_vertex: VertexId,
+) -> DepthFirstPathIter<G> {
     let mut dfs_iter = empty_dfs_path_iter(graph);
     dfs_iter.stack.push(Path::from(&vec![start_vertex]));
     dfs_iter
 }
 
 /// Builds an empty path iterator for a given graph.
-fn empty_dfs_path_iter(graph: &DirectedGraph) -> DepthFirstPathIter {
+fn empty_dfs_path_iter<G: GraphView>(graph: &G) -> DepthFirstPathIter<G> {
     DepthFirstPathIter {
         stack: Stack::<Path>::new(),
         graph: graph,
@@ -8,3 +8,5 @@ pub mod graph_command;
 pub mod iter;
 pub mod path;
 pub mod search;
+pub mod view;
+pub mod weight;
@@ -77,20 +77,21 @@ impl Path {
 }
 
 /// Scored path - understand as a weighted path
+/// The score is any Weight, i64 by default
 
 #[derive(Eq, PartialEq, Clone, Hash, Debug)]
-pub struct ScoredPath {
+pub struct ScoredPath<W = i64> {
     pub path: Path,
-    pub score: i64,
+    pub score: W,
 }
 
-impl PartialOrd for ScoredPath {
+impl<W: Ord> PartialOrd for ScoredPath<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.score.partial_cmp(&other.score)
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for ScoredPath {
+impl<W: Ord> Ord for ScoredPath<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.score.cmp(&other.score)
     }
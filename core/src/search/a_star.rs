@@ -1,31 +1,34 @@
 //! A* implementation
 use crate::constraint::constraint::Constraint;
-use crate::directed_graph::DirectedGraph;
-use crate::graph::VertexId;
+use crate::graph::{GraphView, VertexId};
 use crate::iter;
 use crate::path::Path;
 use crate::path::ScoredPath;
+use crate::weight::Weight;
 
 /// Find the shortest path using A* algorithm
+/// Scores are any Weight, g + h staying at the bounds of the type on overflow
 ///
 /// g: cost function of the path so far
 /// h: heuristic
 /// start
 /// end
-pub fn shortest_path<G, H>(
-    graph: &DirectedGraph,
+pub fn shortest_path<V, G, H, W>(
+    graph: &V,
     g: G, // computing current cost of the path so far
     h: H, // heuristic
     start: VertexId,
     end: VertexId,
-) -> Option<ScoredPath>
+) -> Option<ScoredPath<W>>
 where
-    G: Fn(&DirectedGraph, &Path) -> i64,
-    H: Fn(&DirectedGraph, &Path) -> i64,
+    V: GraphView,
+    G: Fn(&V, &Path) -> W,
+    H: Fn(&V, &Path) -> W,
+    W: Weight,
 {
     iter::iter_best::best_iter_from(
         graph,
-        |dg, path| g(dg, path) + h(dg, path), // f = g + h
+        |dg, path| g(dg, path).saturating_add(h(dg, path)), // f = g + h
         start,
     )
     .find(|sp| sp.path.last().map(|x| *x) == Some(end))
@@ -38,22 +41,24 @@ where
 /// h: heuristic
 /// start
 /// end
-pub fn constrained_shortest_path<G, H>(
-    graph: &DirectedGraph,
+pub fn constrained_shortest_path<V, G, H, W>(
+    graph: &V,
     g: G, // Cost of the path so far
     h: H, // Heuristic - estimated cost to target
     start: VertexId,
     end: VertexId,
-    constraints: Vec<Constraint>,
-) -> Option<ScoredPath>
+    constraints: Vec<Constraint<W>>,
+) -> Option<ScoredPath<W>>
 where
-    G: Fn(&DirectedGraph, &Path) -> i64,
-    H: Fn(&DirectedGraph, &Path) -> i64,
+    V: GraphView,
+    G: Fn(&V, &Path) -> W,
+    H: Fn(&V, &Path) -> W,
+    W: Weight,
 {
     let all_constraints = constraints.clone();
     iter::iter_best_constraint::constrained_best_iter_from(
         graph,
-        |dg, path| g(dg, path) + h(dg, path), // f = g + h
+        |dg, path| g(dg, path).saturating_add(h(dg, path)), // f = g + h
         constraints,
         start,
     )
@@ -64,20 +69,21 @@ where
 }
 
 /// Weight of one per edge
-pub fn one_weighted_edge(_: &DirectedGraph, path: &Path) -> i64 {
+pub fn one_weighted_edge<V: GraphView>(_: &V, path: &Path) -> i64 {
     use std::convert::TryInto;
     (path.size() - 1).try_into().unwrap()
 }
 
 /// Zero information heuristic function
 /// Equivalent to not having an heuristic
-pub fn zero_heuristic(_: &DirectedGraph, _: &Path) -> i64 {
+pub fn zero_heuristic<V: GraphView>(_: &V, _: &Path) -> i64 {
     0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::directed_graph::DirectedGraph;
     use crate::graph::Edge;
 
     // score returns the node id of the last node of the path
@@ -1 +1,3 @@
 pub mod a_star;
+pub mod pareto;
+pub mod resource_constrained;
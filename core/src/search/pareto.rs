@@ -0,0 +1,258 @@
+//! Multi-objective shortest paths: Pareto front of the paths optimising several edge weights
+use crate::constraint::constraint::Constraint;
+use crate::graph::{Edge, GraphView, VertexId};
+use crate::iter::iter_datastructure::{MinPriorityQueue, SearchQueue};
+use crate::path::{Path, ScoredPath};
+use crate::weight::{Overflow, Weight};
+use std::collections::HashMap;
+
+// Scores and sizes of the expanded paths, by last vertex and satisfied constraints
+type Labels<W> = HashMap<(VertexId, Vec<bool>), Vec<(Vec<W>, usize)>>;
+
+/// Checks if a score vector is at least as good as another one on all the objectives
+pub fn dominates<W: Ord>(scores: &[W], other: &[W]) -> bool {
+    scores.iter().zip(other).all(|(s, o)| s <= o)
+}
+
+/// Finds the Pareto front of the paths from start to end: the paths whose scores, one per
+/// objective, are not dominated by the scores of another path. Paths are sorted by scores.
+///
+/// Label-setting search: partial paths are expanded by increasing scores, and a partial path
+/// is dropped when it is dominated by another one ending on the same vertex, satisfying the
+/// same constraints and having at most as many vertices. Weights must not be negative.
+/// Constraints are checked on partial paths to prune the search, and on complete paths.
+/// Their scores apply to the first objective. Returns an error if a score overflows
+pub fn pareto_shortest_paths<G, F, W>(
+    graph: &G,
+    objectives: &[F],
+    start: VertexId,
+    end: VertexId,
+    constraints: Vec<Constraint<W>>,
+) -> Result<Vec<ScoredPath<Vec<W>>>, Overflow>
+where
+    G: GraphView,
+    F: Fn(&Edge) -> W,
+    W: Weight,
+{
+    if objectives.is_empty() || !graph.contains_vertex(start) {
+        return Ok(vec![]);
+    }
+    let mut queue = MinPriorityQueue::<ScoredPath<Vec<W>>>::new();
+    queue.push(ScoredPath {
+        path: Path::from(&vec![start]),
+        score: vec![W::zero(); objectives.len()],
+    });
+    let mut labels: Labels<W> = HashMap::new();
+    let mut res = vec![];
+
+    while let Some(label) = queue.pop() {
+        let first = primary(&label);
+        let satisfied: Vec<bool> = constraints
+            .iter()
+            .map(|c| c.check_complete(&first))
+            .collect();
+        let vertex = *label.path.last().expect("Paths have at least one vertex");
+        let size = label.path.size();
+        let expanded = labels.entry((vertex, satisfied.clone())).or_default();
+        // A longer path can't replace a shorter one when constraints limit the length
+        if expanded
+            .iter()
+            .any(|(scores, s)| *s <= size && dominates(scores, &label.score))
+        {
+            continue;
+        }
+        expanded.push((label.score.clone(), size));
+
+        if vertex == end && satisfied.iter().all(|s| *s) {
+            // Paths are found by increasing scores, so no later path can dominate this one
+            if !res
+                .iter()
+                .any(|found: &ScoredPath<Vec<W>>| dominates(&found.score, &label.score))
+            {
+                res.push(label);
+            }
+            continue;
+        }
+        for edge in graph.outbound_edges(vertex) {
+            let next = ScoredPath {
+                path: label.path.append(edge.1),
+                score: label
+                    .score
+                    .iter()
+                    .zip(objectives)
+                    .map(|(score, objective)| score.checked_add(objective(&edge)))
+                    .collect::<Option<Vec<W>>>()
+                    .ok_or(Overflow)?,
+            };
+            let first = primary(&next);
+            if constraints.iter().all(|c| c.check_partial(&first)) {
+                queue.push(next);
+            }
+        }
+    }
+    Ok(res)
+}
+
+// Path scored by its first objective, on which constraints are checked
+fn primary<W: Weight>(label: &ScoredPath<Vec<W>>) -> ScoredPath<W> {
+    ScoredPath {
+        path: label.path.clone(),
+        score: label.score[0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+    use crate::weight::Real;
+    use Constraint::*;
+
+    #[test]
+    fn pareto_shortest_paths_should_find_all_non_dominated_paths() {
+        let (g, objectives) = build_test_graph();
+        let front =
+            pareto_shortest_paths(&g, &objectives, VertexId(1), VertexId(4), vec![]).unwrap();
+        assert_eq![
+            front,
+            vec![
+                scored_path_of(vec![1, 2, 4], vec![2, 10]),
+                scored_path_of(vec![1, 3, 4], vec![5, 4]),
+                scored_path_of(vec![1, 4], vec![9, 1]),
+            ]
+        ];
+    }
+
+    #[test]
+    fn pareto_shortest_paths_should_be_a_shortest_path_with_one_objective() {
+        let (g, objectives) = build_test_graph();
+        let front =
+            pareto_shortest_paths(&g, &objectives[..1], VertexId(1), VertexId(4), vec![]).unwrap();
+        assert_eq![front, vec![scored_path_of(vec![1, 2, 4], vec![2])]];
+    }
+
+    #[test]
+    fn pareto_shortest_paths_should_honor_constraints() {
+        let (g, objectives) = build_test_graph();
+        let front = pareto_shortest_paths(
+            &g,
+            &objectives,
+            VertexId(1),
+            VertexId(4),
+            vec![ContainsVertex(VertexId(3))],
+        )
+        .unwrap();
+        assert_eq![front, vec![scored_path_of(vec![1, 3, 4], vec![5, 4])]];
+        // The maximum score applies to the latency
+        let front =
+            pareto_shortest_paths(&g, &objectives, VertexId(1), VertexId(4), vec![MaxScore(4)])
+                .unwrap();
+        assert_eq![front, vec![scored_path_of(vec![1, 2, 4], vec![2, 10])]];
+        // A dominated path to 2 is still expanded when it satisfies other constraints
+        let front = pareto_shortest_paths(
+            &g,
+            &objectives,
+            VertexId(1),
+            VertexId(4),
+            vec![ContainsVertex(VertexId(3)), ContainsVertex(VertexId(2))],
+        )
+        .unwrap();
+        assert_eq![front, vec![scored_path_of(vec![1, 3, 2, 4], vec![7, 11])]];
+    }
+
+    #[test]
+    fn pareto_shortest_paths_should_keep_dominated_paths_with_fewer_vertices() {
+        // 1 -> 3 -> 2 is cheaper than 1 -> 2, but too long to reach 4 with at most 3 vertices
+        let mut g = DirectedGraph::new();
+        for (src, dst) in [(1, 2), (1, 3), (3, 2), (2, 4)] {
+            g.add_edge(edge(src, dst));
+        }
+        let objectives: Vec<Objective<'_, i64>> =
+            vec![Box::new(|e: &Edge| if *e == edge(1, 2) { 10 } else { 1 })];
+        let front = pareto_shortest_paths(
+            &g,
+            &objectives,
+            VertexId(1),
+            VertexId(4),
+            vec![MaxLength(3)],
+        )
+        .unwrap();
+        assert_eq![front, vec![scored_path_of(vec![1, 2, 4], vec![11])]];
+    }
+
+    #[test]
+    fn pareto_shortest_paths_should_be_empty_when_end_is_not_reachable() {
+        let (g, objectives) = build_test_graph();
+        let front =
+            pareto_shortest_paths(&g, &objectives, VertexId(4), VertexId(1), vec![]).unwrap();
+        assert_eq![front, vec![]];
+    }
+
+    #[test]
+    fn pareto_shortest_paths_should_support_fractional_weights() {
+        let (g, objectives) = build_test_graph();
+        let (latency, cost) = (&objectives[0], &objectives[1]);
+        let real_objectives: Vec<Objective<'_, Real>> = vec![
+            Box::new(|e: &Edge| Real(latency(e) as f64 / 2.0)),
+            Box::new(|e: &Edge| Real(cost(e) as f64)),
+        ];
+        let front =
+            pareto_shortest_paths(&g, &real_objectives, VertexId(1), VertexId(4), vec![]).unwrap();
+        assert_eq![front.len(), 3];
+        assert_eq![front[0].score, vec![Real(1.0), Real(10.0)]];
+    }
+
+    #[test]
+    fn pareto_shortest_paths_should_detect_overflows() {
+        let (g, objectives) = build_test_graph();
+        let (latency, cost) = (&objectives[0], &objectives[1]);
+        let overflowing: Vec<Objective<'_, i64>> = vec![
+            Box::new(|e: &Edge| latency(e)),
+            Box::new(|e: &Edge| if *e == edge(2, 4) { i64::MAX } else { cost(e) }),
+        ];
+        let front = pareto_shortest_paths(&g, &overflowing, VertexId(1), VertexId(4), vec![]);
+        assert_eq![front, Err(Overflow)];
+    }
+
+    // Helpers
+
+    type Objective<'a, W> = Box<dyn Fn(&Edge) -> W + 'a>;
+
+    // 1 -> 2 -> 4 is fast and expensive, 1 -> 4 is slow and cheap, 1 -> 3 -> 4 is in between
+    // 1 -> 3 -> 2 -> 4 is dominated by 1 -> 2 -> 4
+    // Objectives are the latency and the cost
+    fn build_test_graph() -> (DirectedGraph, Vec<Objective<'static, i64>>) {
+        let weights: HashMap<Edge, (i64, i64)> = vec![
+            ((1, 2), (1, 5)),
+            ((2, 4), (1, 5)),
+            ((1, 3), (2, 2)),
+            ((3, 4), (3, 2)),
+            ((1, 4), (9, 1)),
+            ((3, 2), (4, 4)),
+        ]
+        .into_iter()
+        .map(|((src, dst), w)| (edge(src, dst), w))
+        .collect();
+        let mut g = DirectedGraph::new();
+        for e in weights.keys() {
+            g.add_edge(*e);
+        }
+        let latencies = weights.clone();
+        let latency = move |e: &Edge| latencies[e].0;
+        let cost = move |e: &Edge| weights[e].1;
+        (g, vec![Box::new(latency), Box::new(cost)])
+    }
+
+    fn scored_path_of(vertices: Vec<u64>, score: Vec<i64>) -> ScoredPath<Vec<i64>> {
+        ScoredPath {
+            path: Path {
+                vertices: vertices.into_iter().map(VertexId).collect(),
+            },
+            score,
+        }
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
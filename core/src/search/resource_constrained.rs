@@ -0,0 +1,328 @@
+//! Resource-constrained shortest path: edges consume resources whose totals are bounded
+use crate::constraint::constraint::Constraint;
+use crate::graph::{Edge, GraphView, VertexId};
+use crate::iter::iter_datastructure::{Queue, SearchQueue};
+use crate::path::{Path, ScoredPath};
+use crate::search::pareto::dominates;
+use crate::weight::{Overflow, Weight};
+use std::collections::HashMap;
+
+/// Resource consumed by each edge of a path, the total consumption being bounded by the limit
+pub struct Resource<F, W> {
+    pub consumption: F,
+    pub limit: W,
+}
+
+/// Path found by resource_constrained_shortest_path, with its total consumption of each resource
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ResourcePath<W> {
+    pub scored_path: ScoredPath<W>,
+    pub consumption: Vec<W>,
+}
+
+/// Finds the path from start to end with the smallest score within the resource limits
+///
+/// Label-correcting search: each partial path is a label, and a label is dropped when another
+/// one ending on the same vertex and satisfying the same constraints has a smaller or equal
+/// score, consumption of each resource and length. Scores and consumptions must not be negative.
+/// Constraints are checked on partial paths to prune the search, and on complete paths.
+/// Returns an error if the score of a path overflows
+pub fn resource_constrained_shortest_path<G, S, F, W>(
+    graph: &G,
+    scorefn: S,
+    resources: &[Resource<F, W>],
+    start: VertexId,
+    end: VertexId,
+    constraints: Vec<Constraint<W>>,
+) -> Result<Option<ResourcePath<W>>, Overflow>
+where
+    G: GraphView,
+    S: Fn(&Edge) -> W,
+    F: Fn(&Edge) -> W,
+    W: Weight,
+{
+    if !graph.contains_vertex(start) {
+        return Ok(None);
+    }
+    let mut search = Search {
+        labels: HashMap::new(),
+        next_index: 0,
+        by_vertex: HashMap::new(),
+        constraints: &constraints,
+    };
+    let mut queue: Queue<usize> = Queue::<usize>::new();
+    let first = Label {
+        path: ScoredPath {
+            path: Path::from(&vec![start]),
+            score: W::zero(),
+        },
+        consumption: vec![W::zero(); resources.len()],
+    };
+    if let Some(index) = search.insert(first) {
+        queue.push(index);
+    }
+
+    while let Some(index) = queue.pop() {
+        // Labels dominated after being queued are not expanded
+        let label = match search.labels.get(&index) {
+            Some(label) => label.clone(),
+            None => continue,
+        };
+        let vertex = *label
+            .path
+            .path
+            .last()
+            .expect("Paths have at least one vertex");
+        for edge in graph.outbound_edges(vertex) {
+            let consumption: Option<Vec<W>> = label
+                .consumption
+                .iter()
+                .zip(resources)
+                .map(|(total, resource)| {
+                    total
+                        .checked_add((resource.consumption)(&edge))
+                        .filter(|total| *total <= resource.limit)
+                })
+                .collect();
+            let next = match consumption {
+                Some(consumption) => Label {
+                    path: ScoredPath {
+                        path: label.path.path.append(edge.1),
+                        score: label
+                            .path
+                            .score
+                            .checked_add(scorefn(&edge))
+                            .ok_or(Overflow)?,
+                    },
+                    consumption,
+                },
+                None => continue,
+            };
+            if !constraints.iter().all(|c| c.check_partial(&next.path)) {
+                continue;
+            }
+            if let Some(index) = search.insert(next) {
+                queue.push(index);
+            }
+        }
+    }
+
+    Ok(search
+        .labels
+        .into_iter()
+        .filter(|(_, l)| l.path.path.last() == Some(&end))
+        .filter(|(_, l)| constraints.iter().all(|c| c.check_complete(&l.path)))
+        .min_by_key(|(index, l)| (l.path.score, l.path.path.size(), *index))
+        .map(|(_, l)| ResourcePath {
+            scored_path: l.path,
+            consumption: l.consumption,
+        }))
+}
+
+#[derive(Clone)]
+struct Label<W> {
+    path: ScoredPath<W>,
+    consumption: Vec<W>,
+}
+
+impl<W: Weight> Label<W> {
+    fn dominates(&self, other: &Label<W>) -> bool {
+        self.path.score <= other.path.score
+            && self.path.path.size() <= other.path.path.size()
+            && dominates(&self.consumption, &other.consumption)
+    }
+}
+
+struct Search<'a, W> {
+    // Labels not dominated yet, by index of creation
+    labels: HashMap<usize, Label<W>>,
+    next_index: usize,
+    // Indices of the labels, by last vertex and satisfied constraints
+    by_vertex: HashMap<(VertexId, Vec<bool>), Vec<usize>>,
+    constraints: &'a [Constraint<W>],
+}
+
+impl<'a, W: Weight> Search<'a, W> {
+    // Adds the label unless it is dominated, and removes the labels it dominates
+    fn insert(&mut self, label: Label<W>) -> Option<usize> {
+        let vertex = *label
+            .path
+            .path
+            .last()
+            .expect("Paths have at least one vertex");
+        let satisfied = self
+            .constraints
+            .iter()
+            .map(|c| c.check_complete(&label.path))
+            .collect();
+        let labels = &mut self.labels;
+        let indices = self.by_vertex.entry((vertex, satisfied)).or_default();
+        if indices.iter().any(|i| labels[i].dominates(&label)) {
+            return None;
+        }
+        indices.retain(|i| {
+            if label.dominates(&labels[i]) {
+                labels.remove(i);
+                false
+            } else {
+                true
+            }
+        });
+        let index = self.next_index;
+        self.next_index += 1;
+        indices.push(index);
+        labels.insert(index, label);
+        Some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DirectedGraph;
+    use crate::weight::Real;
+    use Constraint::*;
+
+    #[test]
+    fn resource_constrained_shortest_path_should_respect_the_limits() {
+        let (g, cost) = build_test_graph();
+        // Without a tight limit, the cheapest route is the slow one
+        let path = rcsp(&g, &cost, 100, vec![]).unwrap();
+        assert_eq![path.scored_path, scored_path_of(vec![1, 2, 3, 5], 3)];
+        assert_eq![path.consumption, vec![60]];
+        let path = rcsp(&g, &cost, 50, vec![]).unwrap();
+        assert_eq![path.scored_path, scored_path_of(vec![1, 2, 6, 5], 5)];
+        assert_eq![path.consumption, vec![40]];
+    }
+
+    #[test]
+    fn resource_constrained_shortest_path_should_honor_constraints() {
+        let (g, cost) = build_test_graph();
+        // Under 50ms and 2 hops
+        let path = rcsp(&g, &cost, 50, vec![MaxLength(3)]).unwrap();
+        assert_eq![path.scored_path, scored_path_of(vec![1, 4, 5], 6)];
+        let path = rcsp(&g, &cost, 70, vec![MaxLength(4)]).unwrap();
+        assert_eq![path.scored_path, scored_path_of(vec![1, 2, 3, 5], 3)];
+        let path = rcsp(&g, &cost, 100, vec![ContainsVertex(VertexId(4))]).unwrap();
+        assert_eq![path.scored_path, scored_path_of(vec![1, 4, 5], 6)];
+    }
+
+    #[test]
+    fn resource_constrained_shortest_path_should_be_none_when_limits_are_too_low() {
+        let (g, cost) = build_test_graph();
+        assert_eq![rcsp(&g, &cost, 20, vec![]), None];
+        assert_eq![rcsp(&g, &cost, 50, vec![MaxLength(2)]), None];
+    }
+
+    #[test]
+    fn resource_constrained_shortest_path_should_support_several_resources() {
+        let (g, cost) = build_test_graph();
+        let resources: Vec<Resource<Consumption, Real>> = vec![
+            Resource {
+                consumption: Box::new(|e: &Edge| Real(latency(e) as f64 / 10.0)),
+                limit: Real(5.0),
+            },
+            Resource {
+                consumption: Box::new(|_: &Edge| Real(1.0)),
+                limit: Real(2.0),
+            },
+        ];
+        let path = resource_constrained_shortest_path(
+            &g,
+            |e: &Edge| Real(cost(e) as f64),
+            &resources,
+            VertexId(1),
+            VertexId(5),
+            vec![],
+        )
+        .unwrap()
+        .expect("A path is within the limits");
+        assert_eq![path.scored_path.path, path_of(vec![1, 4, 5])];
+        assert_eq![path.consumption, vec![Real(3.0), Real(2.0)]];
+    }
+
+    #[test]
+    fn resource_constrained_shortest_path_should_detect_overflows() {
+        let (g, cost) = build_test_graph();
+        let resources = [Resource {
+            consumption: latency,
+            limit: 100,
+        }];
+        let path = resource_constrained_shortest_path(
+            &g,
+            |e: &Edge| if *e == edge(3, 5) { i64::MAX } else { cost(e) },
+            &resources,
+            VertexId(1),
+            VertexId(5),
+            vec![],
+        );
+        assert_eq![path, Err(Overflow)];
+    }
+
+    // Helpers
+
+    type Consumption = Box<dyn Fn(&Edge) -> Real>;
+
+    // Routes from 1 to 5, with (cost, latency):
+    // 1 -> 2 -> 3 -> 5 costs 3 in 60ms, 1 -> 2 -> 6 -> 5 costs 5 in 40ms
+    // and 1 -> 4 -> 5 costs 6 in 30ms
+    fn build_test_graph() -> (DirectedGraph, impl Fn(&Edge) -> i64) {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in [(1, 2), (2, 3), (3, 5), (2, 6), (6, 5), (1, 4), (4, 5)] {
+            g.add_edge(edge(src, dst));
+        }
+        let cost = |e: &Edge| match ((e.0).0, (e.1).0) {
+            (2, 6) | (6, 5) => 2,
+            (1, 4) | (4, 5) => 3,
+            _ => 1,
+        };
+        (g, cost)
+    }
+
+    fn latency(e: &Edge) -> i64 {
+        match ((e.0).0, (e.1).0) {
+            (1, 2) => 10,
+            (2, 6) | (6, 5) => 15,
+            (1, 4) | (4, 5) => 15,
+            _ => 25,
+        }
+    }
+
+    fn rcsp<F: Fn(&Edge) -> i64>(
+        g: &DirectedGraph,
+        cost: F,
+        max_latency: i64,
+        constraints: Vec<Constraint>,
+    ) -> Option<ResourcePath<i64>> {
+        let resources = [Resource {
+            consumption: latency,
+            limit: max_latency,
+        }];
+        resource_constrained_shortest_path(
+            g,
+            cost,
+            &resources,
+            VertexId(1),
+            VertexId(5),
+            constraints,
+        )
+        .expect("Test scores don't overflow")
+    }
+
+    fn scored_path_of(vertices: Vec<u64>, score: i64) -> ScoredPath {
+        ScoredPath {
+            path: path_of(vertices),
+            score,
+        }
+    }
+
+    fn path_of(vertices: Vec<u64>) -> Path {
+        Path {
+            vertices: vertices.into_iter().map(VertexId).collect(),
+        }
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
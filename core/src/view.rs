@@ -0,0 +1,278 @@
+//! Read-only views of a graph, adapting it without copying it
+use crate::graph::{Edge, GraphView, VertexId};
+use std::collections::HashSet;
+
+/// Graph with all its edges reversed
+pub struct Reversed<'a, G: GraphView> {
+    graph: &'a G,
+}
+
+impl<'a, G: GraphView> Reversed<'a, G> {
+    pub fn new(graph: &'a G) -> Reversed<'a, G> {
+        Reversed { graph }
+    }
+}
+
+impl<'a, G: GraphView> GraphView for Reversed<'a, G> {
+    fn vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        self.graph.vertices()
+    }
+
+    fn contains_vertex(&self, vertex_id: VertexId) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+
+    fn outbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.graph.inbound_edges(vertex_id).map(|e| e.reverse()))
+    }
+
+    fn inbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.graph.outbound_edges(vertex_id).map(|e| e.reverse()))
+    }
+
+    fn contains_edge(&self, edge: Edge) -> bool {
+        self.graph.contains_edge(edge.reverse())
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+}
+
+/// Subgraph made of the given vertices and of the edges between them
+/// Vertices missing from the graph are ignored
+pub struct InducedSubgraph<'a, G: GraphView> {
+    graph: &'a G,
+    vertices: HashSet<VertexId>,
+}
+
+impl<'a, G: GraphView> InducedSubgraph<'a, G> {
+    pub fn new(graph: &'a G, vertices: HashSet<VertexId>) -> InducedSubgraph<'a, G> {
+        let vertices = vertices
+            .into_iter()
+            .filter(|v| graph.contains_vertex(*v))
+            .collect();
+        InducedSubgraph { graph, vertices }
+    }
+}
+
+impl<'a, G: GraphView> GraphView for InducedSubgraph<'a, G> {
+    fn vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        Box::new(self.vertices.iter().copied())
+    }
+
+    fn contains_vertex(&self, vertex_id: VertexId) -> bool {
+        self.vertices.contains(&vertex_id)
+    }
+
+    fn outbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        if !self.contains_vertex(vertex_id) {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(
+            self.graph
+                .outbound_edges(vertex_id)
+                .filter(move |e| self.vertices.contains(&e.1)),
+        )
+    }
+
+    fn inbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        if !self.contains_vertex(vertex_id) {
+            return Box::new(std::iter::empty());
+        }
+        Box::new(
+            self.graph
+                .inbound_edges(vertex_id)
+                .filter(move |e| self.vertices.contains(&e.0)),
+        )
+    }
+
+    fn contains_edge(&self, edge: Edge) -> bool {
+        self.contains_vertex(edge.0)
+            && self.contains_vertex(edge.1)
+            && self.graph.contains_edge(edge)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+}
+
+/// Graph with all its vertices and the edges accepted by a predicate
+pub struct EdgeFiltered<'a, G: GraphView, F: Fn(&Edge) -> bool> {
+    graph: &'a G,
+    predicate: F,
+}
+
+impl<'a, G: GraphView, F: Fn(&Edge) -> bool> EdgeFiltered<'a, G, F> {
+    pub fn new(graph: &'a G, predicate: F) -> EdgeFiltered<'a, G, F> {
+        EdgeFiltered { graph, predicate }
+    }
+}
+
+impl<'a, G: GraphView, F: Fn(&Edge) -> bool> GraphView for EdgeFiltered<'a, G, F> {
+    fn vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        self.graph.vertices()
+    }
+
+    fn contains_vertex(&self, vertex_id: VertexId) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+
+    fn outbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(
+            self.graph
+                .outbound_edges(vertex_id)
+                .filter(move |e| (self.predicate)(e)),
+        )
+    }
+
+    fn inbound_edges(&self, vertex_id: VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(
+            self.graph
+                .inbound_edges(vertex_id)
+                .filter(move |e| (self.predicate)(e)),
+        )
+    }
+
+    fn contains_edge(&self, edge: Edge) -> bool {
+        (self.predicate)(&edge) && self.graph.contains_edge(edge)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::{cycle, reachability, topo_sort};
+    use crate::directed_graph::DirectedGraph;
+
+    #[test]
+    fn reversed_should_swap_outbound_and_inbound_edges() {
+        let g = build_test_graph();
+        let r = Reversed::new(&g);
+        assert_eq![sorted(r.outbound_edges(VertexId(2))), vec![edge(2, 1)]];
+        assert_eq![
+            sorted(r.inbound_edges(VertexId(2))),
+            vec![edge(3, 2), edge(4, 2)]
+        ];
+        assert![r.contains_edge(edge(2, 1))];
+        assert![!r.contains_edge(edge(1, 2))];
+        assert_eq![r.vertex_count(), 5];
+        assert_eq![r.edge_count(), g.edge_count()];
+        // Reversing twice gives the graph back
+        let rr = Reversed::new(&r);
+        assert_eq![sorted(rr.edges()), sorted(GraphView::edges(&g))];
+    }
+
+    #[test]
+    fn induced_subgraph_should_keep_the_edges_between_its_vertices() {
+        let g = build_test_graph();
+        let s = InducedSubgraph::new(&g, vertices(vec![1, 2, 3, 42]));
+        assert_eq![s.vertex_count(), 3];
+        assert![!s.contains_vertex(VertexId(42))];
+        assert_eq![sorted(s.edges()), vec![edge(1, 2), edge(2, 3), edge(3, 1)]];
+        assert_eq![sorted(s.outbound_edges(VertexId(2))), vec![edge(2, 3)]];
+        assert_eq![sorted(s.inbound_edges(VertexId(1))), vec![edge(3, 1)]];
+        assert_eq![sorted(s.outbound_edges(VertexId(4))), vec![]];
+        assert![!s.contains_edge(edge(2, 4))];
+    }
+
+    #[test]
+    fn edge_filtered_should_keep_all_the_vertices_and_the_accepted_edges() {
+        let g = build_test_graph();
+        let f = EdgeFiltered::new(&g, |e: &Edge| e.1 != VertexId(2));
+        assert_eq![f.vertex_count(), 5];
+        assert_eq![
+            sorted(f.edges()),
+            vec![edge(2, 3), edge(2, 4), edge(3, 1), edge(4, 5)]
+        ];
+        assert_eq![sorted(f.inbound_edges(VertexId(2))), vec![]];
+        assert![!f.contains_edge(edge(1, 2))];
+        assert![f.contains_edge(edge(2, 3))];
+    }
+
+    #[test]
+    fn views_should_compose() {
+        let g = build_test_graph();
+        let s = InducedSubgraph::new(&g, vertices(vec![1, 2, 4, 5]));
+        let f = EdgeFiltered::new(&s, |e: &Edge| e.0 != VertexId(4));
+        let r = Reversed::new(&f);
+        assert_eq![sorted(r.edges()), vec![edge(2, 1), edge(4, 2)]];
+    }
+
+    #[test]
+    fn algorithms_should_run_on_views() {
+        let g = build_test_graph();
+        assert_eq![topo_sort::topological_sort(&g), None];
+        // Without the edge closing the cycle, the graph is a DAG
+        let dag = EdgeFiltered::new(&g, |e: &Edge| *e != edge(3, 1));
+        assert_eq![
+            topo_sort::lexicographic_topological_sort(&dag),
+            Some(vertices_of(vec![1, 2, 3, 4, 5]))
+        ];
+        let r = Reversed::new(&g);
+        assert_eq![
+            reachability::descendants(&r, VertexId(4)),
+            vertices(vec![1, 2, 3])
+        ];
+        let s = InducedSubgraph::new(&g, vertices(vec![2, 3, 4, 5]));
+        assert![!reachability::is_reachable(&s, VertexId(3), VertexId(5))];
+        assert![reachability::is_reachable(&g, VertexId(3), VertexId(5))];
+    }
+
+    #[test]
+    fn dag_and_cycle_algorithms_should_run_on_views() {
+        let g = build_test_graph();
+        assert_eq![cycle::count(&g), 1];
+        let s = InducedSubgraph::new(&g, vertices(vec![2, 3, 4, 5]));
+        assert_eq![cycle::first(&s), None];
+        let f = EdgeFiltered::new(&g, |e: &Edge| *e != edge(3, 1));
+        let dag = topo_sort::try_dag(&f).unwrap();
+        assert_eq![
+            topo_sort::waves(&dag),
+            vec![
+                vertices_of(vec![1]),
+                vertices_of(vec![2]),
+                vertices_of(vec![3, 4]),
+                vertices_of(vec![5])
+            ]
+        ];
+    }
+
+    // Helpers
+
+    // 1 -> 2 -> 3 -> 1 and 2 -> 4 -> 5
+    fn build_test_graph() -> DirectedGraph {
+        let mut g = DirectedGraph::new();
+        for (src, dst) in [(1, 2), (2, 3), (3, 1), (2, 4), (4, 5)] {
+            g.add_edge(edge(src, dst));
+        }
+        g
+    }
+
+    fn sorted<'a>(edges: Box<dyn Iterator<Item = Edge> + 'a>) -> Vec<Edge> {
+        let mut res: Vec<Edge> = edges.collect();
+        res.sort();
+        res
+    }
+
+    fn vertices(ids: Vec<u64>) -> HashSet<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn vertices_of(ids: Vec<u64>) -> Vec<VertexId> {
+        ids.into_iter().map(VertexId).collect()
+    }
+
+    fn edge(src: u64, dst: u64) -> Edge {
+        Edge(VertexId(src), VertexId(dst))
+    }
+}
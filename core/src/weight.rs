@@ -0,0 +1,216 @@
+//! Numeric types usable as edge weights and path scores
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::ops::Neg;
+use std::str::FromStr;
+
+/// Weight of an edge or score of a path
+/// Additions are checked: overflowing weights are detected instead of wrapping around
+pub trait Weight: Copy + Ord + Hash + Debug + Display {
+    /// Neutral element of the addition, score of a path without edges
+    fn zero() -> Self;
+
+    /// Smallest weight
+    fn min_value() -> Self;
+
+    /// Biggest weight, used as the score of unreachable vertices
+    fn max_value() -> Self;
+
+    /// Adds two weights, returns None on overflow
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Adds two weights, staying at the bounds of the type on overflow
+    fn saturating_add(self, other: Self) -> Self {
+        self.checked_add(other).unwrap_or_else(|| {
+            if other < Self::zero() {
+                Self::min_value()
+            } else {
+                Self::max_value()
+            }
+        })
+    }
+}
+
+impl Weight for i64 {
+    fn zero() -> i64 {
+        0
+    }
+
+    fn min_value() -> i64 {
+        i64::MIN
+    }
+
+    fn max_value() -> i64 {
+        i64::MAX
+    }
+
+    fn checked_add(self, other: i64) -> Option<i64> {
+        i64::checked_add(self, other)
+    }
+}
+
+impl Weight for u64 {
+    fn zero() -> u64 {
+        0
+    }
+
+    fn min_value() -> u64 {
+        0
+    }
+
+    fn max_value() -> u64 {
+        u64::MAX
+    }
+
+    fn checked_add(self, other: u64) -> Option<u64> {
+        u64::checked_add(self, other)
+    }
+}
+
+/// Weight that can be negated, to find longest paths with shortest path algorithms
+pub trait SignedWeight: Weight {
+    /// Negates a weight, returns None on overflow
+    fn checked_neg(self) -> Option<Self>;
+}
+
+/// Error of a path search whose scores overflow their Weight type
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Overflow;
+
+impl SignedWeight for i64 {
+    fn checked_neg(self) -> Option<i64> {
+        i64::checked_neg(self)
+    }
+}
+
+/// Floating point weight with a total ordering, for fractional weights like latencies
+/// Bounds are the infinities, and an addition overflows when finite weights give an infinite
+/// or NaN sum
+#[derive(Clone, Copy, Debug)]
+pub struct Real(pub f64);
+
+impl PartialEq for Real {
+    fn eq(&self, other: &Real) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Real {}
+
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Real) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Real {
+    fn cmp(&self, other: &Real) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Real {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+impl Display for Real {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for Real {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Real, Self::Err> {
+        s.parse::<f64>().map(Real)
+    }
+}
+
+impl Neg for Real {
+    type Output = Real;
+
+    fn neg(self) -> Real {
+        Real(-self.0)
+    }
+}
+
+impl Weight for Real {
+    fn zero() -> Real {
+        Real(0.0)
+    }
+
+    fn min_value() -> Real {
+        Real(f64::NEG_INFINITY)
+    }
+
+    fn max_value() -> Real {
+        Real(f64::INFINITY)
+    }
+
+    fn checked_add(self, other: Real) -> Option<Real> {
+        let sum = self.0 + other.0;
+        if sum.is_nan() || (sum.is_infinite() && self.0.is_finite() && other.0.is_finite()) {
+            None
+        } else {
+            Some(Real(sum))
+        }
+    }
+}
+
+impl SignedWeight for Real {
+    fn checked_neg(self) -> Option<Real> {
+        Some(-self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_add_should_detect_overflows() {
+        assert_eq![Weight::checked_add(2i64, 3), Some(5)];
+        assert_eq![Weight::checked_add(i64::MAX, 1), None];
+        assert_eq![Weight::checked_add(0u64, u64::MAX), Some(u64::MAX)];
+        assert_eq![Weight::checked_add(1u64, u64::MAX), None];
+        assert_eq![Real(0.5).checked_add(Real(0.25)), Some(Real(0.75))];
+        assert_eq![Real(f64::MAX).checked_add(Real(f64::MAX)), None];
+    }
+
+    #[test]
+    fn saturating_add_should_stay_at_the_bounds() {
+        assert_eq![Weight::saturating_add(i64::MAX, 1), i64::MAX];
+        assert_eq![Weight::saturating_add(i64::MIN, -1), i64::MIN];
+        assert_eq![Weight::saturating_add(u64::MAX, 1), u64::MAX];
+        assert_eq![
+            Real(f64::MAX).saturating_add(Real(f64::MAX)),
+            Real::max_value()
+        ];
+        assert_eq![
+            Real::max_value().saturating_add(Real(1.0)),
+            Real::max_value()
+        ];
+    }
+
+    #[test]
+    fn checked_neg_should_detect_overflows() {
+        assert_eq![SignedWeight::checked_neg(i64::MAX), Some(-i64::MAX)];
+        assert_eq![SignedWeight::checked_neg(i64::MIN), None];
+        assert_eq![Real(1.5).checked_neg(), Some(Real(-1.5))];
+    }
+
+    #[test]
+    fn real_should_be_totally_ordered() {
+        let mut weights = vec![Real(2.5), Real(-1.0), Real::max_value(), Real(0.0)];
+        weights.sort();
+        assert_eq![
+            weights,
+            vec![Real(-1.0), Real(0.0), Real(2.5), Real::max_value()]
+        ];
+        assert_eq!["1.5".parse::<Real>(), Ok(Real(1.5))];
+    }
+}